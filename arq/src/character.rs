@@ -6,6 +6,7 @@ use crate::character::character_details::{build_default_character_details, Chara
 use crate::character::equipment::Equipment;
 use crate::character::stats::attributes::AttributeScore;
 use crate::map::objects::container::Container;
+use crate::map::objects::identification::Identification;
 use crate::map::position::Position;
 use crate::map::tile::{Colour, Symbol};
 
@@ -34,10 +35,15 @@ pub struct Character {
     name : String,
     character_details: CharacterDetails,
     symbol: Symbol,
-    health: i8,
+    health: u16,
+    max_health: u16,
+    // Whoever or whatever last hurt this character, so we know what killed them
+    last_damaged_by: Option<String>,
     position: Position,
     inventory: Container,
-    equipment: Equipment
+    equipment: Equipment,
+    // Which kinds of magic item this character has learnt to recognise
+    identification: Identification
 }
 
 pub fn determine_class(name: String) -> Option<Class> {
@@ -72,13 +78,13 @@ impl Character {
         let character_details = build_default_character_details();
         let equipment = Equipment::new();
 
-        let player = Character { id, name, character_details, symbol, health, position, inventory, equipment };
+        let player = Character { id, name, character_details, symbol, health, max_health: health, last_damaged_by: None, position, inventory, equipment, identification: Identification::new(String::new()) };
         return player;
     }
 
-    pub fn new_detailed(name : String, position: Position, character_details: CharacterDetails, symbol: Symbol, health: i8, inventory: Container, equipment: Equipment) -> Character {
+    pub fn new_detailed(name : String, position: Position, character_details: CharacterDetails, symbol: Symbol, health: u16, inventory: Container, equipment: Equipment) -> Character {
         let id = Uuid::new_v4();
-        let player = Character { id, name, character_details, symbol, health, position, inventory, equipment };
+        let player = Character { id, name, character_details, symbol, health, max_health: health, last_damaged_by: None, position, inventory, equipment, identification: Identification::new(String::new()) };
         return player;
     }
 
@@ -94,12 +100,47 @@ impl Character {
         self.character_details.clone()
     }
 
-    pub fn get_health(&self) -> i8 {
-        return self.health.clone();
+    pub fn get_health(&self) -> u16 {
+        self.health
     }
 
-    pub fn set_health(&mut self, health: i8) {
-        self.health = health;
+    // Health can never exceed the maximum
+    pub fn set_health(&mut self, health: u16) {
+        self.health = health.min(self.max_health);
+    }
+
+    pub fn get_max_health(&self) -> u16 {
+        self.max_health
+    }
+
+    pub fn set_max_health(&mut self, max_health: u16) {
+        self.max_health = max_health;
+        self.health = self.health.min(max_health);
+    }
+
+    /*
+     * Reduces health by the given amount (stopping at 0) and remembers the source of the damage
+     * The source is a description such as a character's name or "a spike trap"
+     */
+    pub fn take_damage(&mut self, amount: u16, source: String) {
+        self.health = self.health.saturating_sub(amount);
+        self.last_damaged_by = Some(source);
+    }
+
+    pub fn heal(&mut self, amount: u16) {
+        self.health = self.health.saturating_add(amount).min(self.max_health);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    // Only dead characters have a cause of death
+    pub fn get_cause_of_death(&self) -> Option<String> {
+        if !self.is_dead() {
+            return None;
+        }
+        Some(self.last_damaged_by.clone().unwrap_or(String::from("unknown causes")))
     }
 
     pub fn get_symbol(&self) -> char {
@@ -138,6 +179,18 @@ impl Character {
         return &mut self.equipment;
     }
 
+    pub fn get_identification(&self) -> &Identification {
+        &self.identification
+    }
+
+    pub fn get_identification_mut(&mut self) -> &mut Identification {
+        &mut self.identification
+    }
+
+    pub fn set_identification(&mut self, identification: Identification) {
+        self.identification = identification;
+    }
+
     pub fn get_race(&mut self) -> Race {
         self.character_details.get_race().clone()
     }
@@ -183,6 +236,7 @@ mod tests {
     use crate::character::equipment::Equipment;
     use crate::character::Character;
     use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::identification::Identification;
     use crate::map::position::Position;
     use crate::map::tile::{Colour, Symbol};
 
@@ -196,12 +250,61 @@ mod tests {
         let position = Position { x: 1, y: 1};
         let inventory = Container::new(Uuid::new_v4(), "Test Person's Inventory".to_owned(), 'X', 1.0, 1, ContainerType::OBJECT, 100);
         let equipment = Equipment::new();
-        let mut character = Character { id, name, character_details, symbol, health, position, inventory, equipment };
+        let identification = Identification::new(String::new());
+        let mut character = Character { id, name, character_details, symbol, health, max_health: health, last_damaged_by: None, position, inventory, equipment, identification };
 
         assert_eq!("Test Person", character.get_name());
         assert_eq!(100, character.get_health());
+        assert_eq!(100, character.get_max_health());
         assert_eq!(Colour::Green, character.get_colour());
         assert_eq!(position, character.get_global_position());
         assert_eq!(0, character.get_inventory_mut().get_contents().len());
     }
+
+    fn build_test_character() -> Character {
+        let inventory = Container::new(Uuid::new_v4(), "Test Person's Inventory".to_owned(), 'X', 1.0, 1, ContainerType::OBJECT, 100);
+        Character::new(String::from("Test Person"), Position::zero(), Symbol { character: '@', colour: Colour::Green }, inventory)
+    }
+
+    #[test]
+    fn test_take_damage() {
+        // GIVEN a character at full health
+        let mut character = build_test_character();
+
+        // WHEN they take some damage
+        character.take_damage(30, String::from("Ruggo"));
+
+        // THEN they're hurt, but still alive
+        assert_eq!(70, character.get_health());
+        assert!(!character.is_dead());
+        assert_eq!(None, character.get_cause_of_death());
+    }
+
+    #[test]
+    fn test_take_damage_overkill() {
+        // GIVEN a character at full health
+        let mut character = build_test_character();
+
+        // WHEN they take more damage than they have health
+        character.take_damage(50, String::from("Ruggo"));
+        character.take_damage(u16::MAX, String::from("a spike trap"));
+
+        // THEN health stops at 0 and the last source of damage killed them
+        assert_eq!(0, character.get_health());
+        assert!(character.is_dead());
+        assert_eq!(Some(String::from("a spike trap")), character.get_cause_of_death());
+    }
+
+    #[test]
+    fn test_heal_capped_at_max_health() {
+        // GIVEN a hurt character
+        let mut character = build_test_character();
+        character.take_damage(10, String::from("Ruggo"));
+
+        // WHEN they're healed by more than they lost
+        character.heal(50);
+
+        // THEN they're back at their max health
+        assert_eq!(character.get_max_health(), character.get_health());
+    }
 }
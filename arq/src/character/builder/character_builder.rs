@@ -55,6 +55,13 @@ pub fn build_dev_player_inventory() -> Container {
     Future TODO - Consider storing patterns in a proper data store so we can look them up by CharacterType alone / keep the code lightweight
  */
 impl CharacterPattern {
+    pub fn for_type(character_type: CharacterType) -> Result<CharacterPattern, ErrorWrapper> {
+        match character_type {
+            NewPlayer => CharacterPattern::new_player(),
+            GoblinWarrior => CharacterPattern::goblin()
+        }
+    }
+
     pub fn new_player() -> Result<CharacterPattern, ErrorWrapper> {
         let attributes: Vec<AttributeScore> = AttributeScores::default().scores;
 
@@ -119,7 +126,7 @@ pub struct CharacterBlueprint {
     pub details: CharacterDetails,
     pub position: Option<Position>,
     pub symbol: Symbol,
-    pub health: i8,
+    pub health: u16,
     pub inventory: Container,
     pub equipment: Equipment
 }
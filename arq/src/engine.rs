@@ -1,9 +1,14 @@
 pub mod game_engine;
 pub mod pathfinding;
 pub mod level;
+pub mod spawn_table;
+pub mod loot_table;
+pub mod world;
+pub mod run_history;
 pub mod command;
 pub mod container_util;
 pub mod process;
 pub mod combat;
+pub mod travel;
 
 pub mod engine_helpers;
@@ -1,31 +1,179 @@
+use std::convert::TryFrom;
+
 use crate::character::battle::Battle;
 use crate::character::equipment::WeaponSlot;
+use crate::character::Character;
+use crate::map::objects::items::ItemType;
+use crate::sound::sound::{play_effect, SoundSinks};
+use crate::sound::sound_effects::SoundEffect;
 use crate::view::combat_view::{CombatCallbackData, CombatResult};
 use crate::view::util::callback::CallbackHandler;
 
+// Damage dealt by a character with nothing in the weapon slot used
+const UNARMED_DAMAGE: u16 = 5;
+
 #[derive(Clone)]
 pub enum CombatTurnChoice {
     ATTACK(WeaponSlot),
     FLEE
 }
 
-pub struct Combat {
-    pub(crate) battle: Battle
+pub struct Combat<'a> {
+    pub(crate) battle: Battle,
+    pub(crate) sound_sinks: Option<&'a mut SoundSinks>
+}
+
+fn weapon_damage(character: &mut Character, slot: WeaponSlot) -> u16 {
+    match character.get_equipment().get_item(slot.to_equipment_slot()).map(|i| &i.item_type) {
+        Some(ItemType::WEAPON(weapon)) => u16::try_from(weapon.damage.max(0)).unwrap_or(u16::MAX),
+        _ => UNARMED_DAMAGE
+    }
+}
+
+impl Combat<'_> {
+    /*
+     * The player strikes the first NPC with the weapon in the given slot, and if it survives it strikes back
+     * A blow that would do no damage at all misses
+     * Ends the battle once either side is dead
+     */
+    fn exchange_blows(&mut self, slot: WeaponSlot) -> Vec<String> {
+        let mut messages = Vec::new();
+        let characters = &mut self.battle.characters;
+        let (player_name, player_damage) = match characters.get_player_mut() {
+            Some(player) => (player.get_name(), weapon_damage(player, slot)),
+            None => return messages
+        };
+
+        let (npc_name, npc_damage) = match characters.get_npcs_mut().first_mut() {
+            Some(npc) => {
+                if player_damage == 0 {
+                    messages.push(format!("You miss {}.", npc.get_name()));
+                    play_effect(&mut self.sound_sinks, SoundEffect::Miss);
+                } else {
+                    npc.take_damage(player_damage, player_name);
+                    messages.push(format!("You hit {} for {} damage.", npc.get_name(), player_damage));
+                    play_effect(&mut self.sound_sinks, SoundEffect::Hit);
+                }
+                if npc.is_dead() {
+                    play_effect(&mut self.sound_sinks, SoundEffect::Death);
+                    messages.push(format!("{} dies!", npc.get_name()));
+                    self.battle.in_progress = false;
+                    return messages;
+                }
+                (npc.get_name(), weapon_damage(npc, WeaponSlot::PRIMARY))
+            },
+            None => return messages
+        };
+
+        if let Some(player) = characters.get_player_mut() {
+            if npc_damage == 0 {
+                messages.push(format!("{} misses you.", npc_name));
+                play_effect(&mut self.sound_sinks, SoundEffect::Miss);
+            } else {
+                player.take_damage(npc_damage, npc_name.clone());
+                messages.push(format!("{} hits you for {} damage.", npc_name, npc_damage));
+                play_effect(&mut self.sound_sinks, SoundEffect::Hit);
+            }
+            if player.is_dead() {
+                play_effect(&mut self.sound_sinks, SoundEffect::Death);
+                messages.push(String::from("You die..."));
+                self.battle.in_progress = false;
+            }
+        }
+        messages
+    }
 }
 
-impl CallbackHandler<CombatCallbackData> for Combat {
+impl CallbackHandler<CombatCallbackData> for Combat<'_> {
     fn handle_callback(&mut self, data: CombatCallbackData) -> Option<CombatCallbackData> {
         let mut result_data: CombatCallbackData = data.clone();
         let mut messages : Vec<String> = Vec::new();
         match data.choice {
-            CombatTurnChoice::ATTACK(_) => {
+            CombatTurnChoice::ATTACK(slot) => {
                 messages.push(String::from("You attempt attack..."));
+                messages.append(&mut self.exchange_blows(slot));
             }
             CombatTurnChoice::FLEE => {
-                messages.push(String::from("You attempt to run away..."));
+                messages.push(String::from("You run away!"));
+                self.battle.in_progress = false;
             }
         }
-        result_data.result = Some(CombatResult { messages });
+        result_data.result = Some(CombatResult { messages, battle: self.battle.clone() });
         Some(result_data)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::character::battle::Battle;
+    use crate::character::builder::character_builder::{CharacterBuilder, CharacterPattern};
+    use crate::character::characters::Characters;
+    use crate::character::equipment::{Equipment, EquipmentSlot, WeaponSlot};
+    use crate::engine::combat::{Combat, CombatTurnChoice};
+    use crate::map::objects::container::Container;
+    use crate::map::objects::items::{Item, ItemForm, MaterialType, Weapon};
+    use crate::map::objects::weapon_builder::BladedWeaponType;
+    use crate::view::combat_view::CombatCallbackData;
+    use crate::view::util::callback::CallbackHandler;
+
+    fn build_combat() -> Combat<'static> {
+        let player = CharacterBuilder::new(CharacterPattern::new_player().unwrap()).build(String::from("Player"));
+        let npc = CharacterBuilder::new(CharacterPattern::goblin().unwrap()).build(String::from("Ruggo"));
+        Combat { battle: Battle { characters: Characters::new(Some(player), vec![npc]), in_progress: true }, sound_sinks: None }
+    }
+
+    #[test]
+    fn test_attack_exchanges_blows() {
+        // GIVEN a battle between the player and a goblin
+        let mut combat = build_combat();
+
+        // WHEN the player attacks
+        let data = CombatCallbackData { choice: CombatTurnChoice::ATTACK(WeaponSlot::PRIMARY), result: None };
+        combat.handle_callback(data);
+
+        // THEN both sides are hurt
+        let characters = &combat.battle.characters;
+        let player = characters.get_player().unwrap();
+        let npc = &characters.get_npcs()[0];
+        assert!(player.get_health() < player.get_max_health());
+        assert!(npc.get_health() < npc.get_max_health());
+    }
+
+    #[test]
+    fn test_attack_misses() {
+        // GIVEN a battle where the player's weapon can't do any damage
+        let mut combat = build_combat();
+        let blunt = Item::weapon(Uuid::new_v4(), String::from("Blunt Sword"), ItemForm::BLADED(BladedWeaponType::ARMING), MaterialType::IRON, '|', 1.0, 1, Weapon { damage: 0 });
+        let mut equipment = Equipment::new();
+        equipment.equip(Container::wrap(blunt), EquipmentSlot::PRIMARY).unwrap();
+        combat.battle.characters.get_player_mut().unwrap().set_equipment(equipment);
+
+        // WHEN the player attacks with it
+        let data = CombatCallbackData { choice: CombatTurnChoice::ATTACK(WeaponSlot::PRIMARY), result: None };
+        let messages = combat.handle_callback(data).unwrap().result.unwrap().messages;
+
+        // THEN they miss, leaving the goblin unhurt
+        assert_eq!("You miss Ruggo.", messages[1]);
+        let npc = &combat.battle.characters.get_npcs()[0];
+        assert_eq!(npc.get_max_health(), npc.get_health());
+    }
+
+    #[test]
+    fn test_attack_until_player_dies() {
+        // GIVEN a battle where the player is almost dead
+        let mut combat = build_combat();
+        combat.battle.characters.get_player_mut().unwrap().set_health(1);
+
+        // WHEN the player attacks and the goblin survives
+        let data = CombatCallbackData { choice: CombatTurnChoice::ATTACK(WeaponSlot::PRIMARY), result: None };
+        combat.handle_callback(data);
+
+        // THEN the goblin's counter-attack kills the player and ends the battle
+        let player = combat.battle.characters.get_player().unwrap();
+        assert!(player.is_dead());
+        assert_eq!(Some(String::from("Ruggo")), player.get_cause_of_death());
+        assert!(!combat.battle.in_progress);
+    }
+}
@@ -1,4 +1,5 @@
 pub(crate) mod command;
 pub(crate) mod open_command;
 pub(crate) mod look_command;
-pub(crate) mod inventory_command;
\ No newline at end of file
+pub(crate) mod inventory_command;
+pub(crate) mod pickup_command;
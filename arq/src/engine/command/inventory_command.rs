@@ -5,29 +5,36 @@ use std::io::Error;
 use termion::event::Key;
 
 use crate::character::equipment::get_potential_slots;
+use crate::character::Character;
 use crate::engine::command::command::Command;
 use crate::engine::container_util;
 use crate::engine::level::Level;
 use crate::error::errors::ErrorWrapper;
+use crate::input::KeyInputResolver;
 use crate::map::objects::container::Container;
-use crate::map::objects::items::Item;
+use crate::map::objects::identification::{PotionType, ScrollType};
+use crate::map::objects::items::{Item, ItemForm};
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::bindings::action_bindings::Action;
 use crate::ui::bindings::inventory_bindings::InventoryInput;
 use crate::ui::ui::UI;
 use crate::view::character_info_view::{CharacterInfoView, TabChoice};
 use crate::view::framehandler::character_info::CharacterInfoFrameHandler;
-use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{DropItems, EquipItems, MoveItems, MoveToContainerChoice};
-use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, MoveItemsData, MoveToContainerChoiceData};
+use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{DropItems, EquipItems, InspectItem, MoveItems, MoveToContainerChoice, UseItem};
+use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, MoveItemsData, MoveToContainerChoiceData, UseItemData};
 use crate::view::util::callback::Callback;
 use crate::view::View;
 
-const UI_USAGE_HINT: &str = "Up/Down - Move, Enter/q - Toggle/clear selection\nTab - Change tab, Esc - Exit";
+const UI_USAGE_HINT: &str = "Up/Down - Move, Enter/q - Toggle/clear selection\nTab - Change tab, i - Inspect, Esc - Exit";
+const HEALING_POTION_AMOUNT: u16 = 20;
+const POISON_POTION_DAMAGE: u16 = 10;
+const FIRE_SCROLL_DAMAGE: u16 = 5;
 
 pub struct InventoryCommand<'a, B: 'static + ratatui::backend::Backend> {
     pub level: &'a mut Level,
     pub ui: &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
 }
 
 struct CallbackState<'a> {
@@ -105,7 +112,7 @@ fn drop_items(items: Vec<Item>, mut state: CallbackState) -> Option<ContainerFra
                 // Find the "container" wrappper matching the item returned
                 if let Some(ref mut container) = state.container {
                     if let Some(container_item) = &mut container.find_mut(&item) {
-                        let mut dropping_container_item = container_item.clone();
+                        let mut dropping_container_item = container_item.clone_quantity(item.get_quantity());
                         let self_item = dropping_container_item.get_self_item_mut();
                         self_item.unequip();
                         if pos_container.can_fit_container_item(&dropping_container_item) {
@@ -130,6 +137,61 @@ fn drop_items(items: Vec<Item>, mut state: CallbackState) -> Option<ContainerFra
     return Some(DropItems(undropped));
 }
 
+// An identify scroll reveals the first kind of item carried anywhere in the player's inventory that isn't yet known
+fn identify_carried_item(player: &mut Character) -> String {
+    let carried = player.get_inventory_mut().to_cloned_nested_item_list();
+    let identification = player.get_identification_mut();
+    if let Some(unidentified) = carried.iter().find(|i| !i.is_identified(identification)) {
+        let appearance = unidentified.get_display_name(identification);
+        identification.identify(&unidentified.get_item_form());
+        return format!("The {} is a {}.", appearance, unidentified.get_name());
+    }
+    String::from("You have nothing left to identify.")
+}
+
+/*
+ * Uses up one potion or scroll, applying it's effect to the player
+ * Using an item identifies that kind of item for the rest of the run
+ */
+fn use_item(mut data: UseItemData, state: CallbackState) -> Option<ContainerFrameHandlerInputResult> {
+    let item = data.item.clone();
+    let player = state.level.characters.get_player_mut().unwrap();
+    let appearance = item.get_display_name(player.get_identification());
+    let form = item.get_item_form();
+    // Identify first, so an identify scroll doesn't pick itself
+    let newly_identified = player.get_identification_mut().identify(&form);
+    let effect = match form {
+        ItemForm::POTION(PotionType::Healing) => {
+            player.heal(HEALING_POTION_AMOUNT);
+            String::from("You feel better.")
+        },
+        ItemForm::POTION(PotionType::Poison) => {
+            player.take_damage(POISON_POTION_DAMAGE, String::from("a potion of poison"));
+            String::from("You feel sick.")
+        },
+        ItemForm::SCROLL(ScrollType::Identify) => {
+            identify_carried_item(player)
+        },
+        ItemForm::SCROLL(ScrollType::Fire) => {
+            player.take_damage(FIRE_SCROLL_DAMAGE, String::from("a scroll of fire"));
+            String::from("The scroll bursts into flames!")
+        },
+        _ => {
+            data.message = Some(format!("You can't use the {}.", appearance));
+            return Some(UseItem(data));
+        }
+    };
+    data.identification = Some(player.get_identification().clone());
+    log::info!("[inventory usage] Used item: {}", item.get_name());
+    data.used = true;
+    data.message = if newly_identified {
+        Some(format!("The {} was a {}. {}", appearance, item.get_name(), effect))
+    } else {
+        Some(effect)
+    };
+    Some(UseItem(data))
+}
+
 fn build_container_choices<'a>(data: &'a MoveToContainerChoiceData, level: &'a mut Level) -> Result<ContainerFrameHandlerInputResult, Error> {
     let inventory = level.characters.get_player_mut().unwrap().get_inventory_mut();
     let sub_containers_result = container_util::build_container_choices(&data.source, inventory);
@@ -151,6 +213,13 @@ fn handle_callback(state: CallbackState) -> Option<ContainerFrameHandlerInputRes
             log::info!("[inventory usage] Received data for MoveItems with {} items", data.to_move.len());
             return container_util::move_player_items(data, state.level);
         },
+        InspectItem(data) => {
+            return container_util::inspect_item(data, state.level);
+        },
+        UseItem(ref data) => {
+            log::info!("[inventory usage] Received data for UseItem: {}", data.item.get_name());
+            use_item(data.clone(), state)
+        },
         EquipItems(ref data) => {
             log::info!("[inventory usage] Received data for EquipItems with {} items", data.len());
             return equip_items(data.clone(), state);
@@ -188,13 +257,13 @@ impl <B: ratatui::backend::Backend> InventoryCommand<'_, B> {
         //let c = self.level.characters.get_player_mut().unwrap().get_inventory_mut();
         //let mut callback_container: Container = c.clone();
 
-        let frame_handler = CharacterInfoFrameHandler { tab_choice: TabChoice::INVENTORY, container_frame_handlers: Vec::new(), choice_frame_handler: None, character_view: None };
+        let frame_handler = CharacterInfoFrameHandler { tab_choice: TabChoice::INVENTORY, container_frame_handlers: Vec::new(), choice_frame_handler: None, character_view: None, tab_areas: Vec::new() };
 
         let level = &mut self.level;
         let player = &mut level.characters.get_player_mut().unwrap().clone();
         let updated_inventory;
         {
-            let mut character_info_view = CharacterInfoView { character: player, ui: &mut self.ui, terminal_manager: &mut self.terminal_manager, frame_handler, callback: Box::new(|_| {None}) };
+            let mut character_info_view = CharacterInfoView { character: player, ui: self.ui, terminal_manager: self.terminal_manager, key_input: &mut *self.key_input, frame_handler, callback: Box::new(|_| {None}) };
             character_info_view.set_callback(Box::new(|data| {
                 let mut current_inventory = level.characters.get_player_mut().unwrap().get_inventory_mut().clone();
                 handle_callback(CallbackState { level, container: Some(&mut current_inventory), data })
@@ -242,12 +311,13 @@ mod tests {
     use crate::character::equipment::EquipmentSlot::PRIMARY;
     use crate::engine::command::inventory_command::{equip_items, handle_callback, CallbackState};
     use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::identification::{Identification, PotionType, ScrollType};
     use crate::map::objects::items::{Item, ItemForm, MaterialType, Weapon};
     use crate::map::objects::weapon_builder::BladedWeaponType;
     use crate::map::position::Position;
     use crate::map::tile::Colour;
     use crate::test::utils::test_utils::build_test_level;
-    use crate::view::framehandler::container::ContainerFrameHandlerInputResult;
+    use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, UseItemData};
     use crate::view::framehandler::container::ContainerFrameHandlerInputResult::EquipItems;
 
     fn build_test_container() -> Container {
@@ -460,4 +530,85 @@ mod tests {
         }
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_use_item_callback() {
+        // GIVEN a hurt player carrying 2 unidentified healing potions
+        let mut level = build_test_level(None, None);
+        let player = level.characters.get_player_mut().unwrap();
+        player.set_identification(Identification::new(String::from("test")));
+        let max_health = player.get_max_health();
+        player.take_damage(30, String::from("a test"));
+        let mut potions = Item::magic(Uuid::new_v4(), ItemForm::POTION(PotionType::Healing), '!', 0.5, 20);
+        potions.set_quantity(2);
+        let appearance = potions.get_display_name(player.get_identification());
+        let mut container = build_test_container();
+        container.add_item(potions.clone()).expect("Failed to add the potions!");
+
+        // WHEN we call to use one of them
+        let mut using = potions.clone();
+        using.set_quantity(1);
+        let data = ContainerFrameHandlerInputResult::UseItem(UseItemData { item: using, used: false, message: None, identification: None });
+        let result = handle_callback(CallbackState { level: &mut level, container: Some(&mut container), data });
+
+        // THEN we expect it to have been used, healing the player
+        match result {
+            Some(ContainerFrameHandlerInputResult::UseItem(data)) => {
+                assert!(data.used);
+                assert_eq!(format!("The {} was a Potion of Healing. You feel better.", appearance), data.message.unwrap());
+                // AND what's now known is passed back to re-list the items by
+                assert_eq!("Potion of Healing", potions.get_display_name(&data.identification.unwrap()));
+            },
+            _ => {
+                assert!(false, "Expected a valid Some(UseItem(data)) to return!");
+            }
+        }
+        assert_eq!(max_health.saturating_sub(10), level.characters.get_player_mut().unwrap().get_health());
+        // AND healing potions are known to the player from now on
+        assert_eq!("Potion of Healing", potions.get_display_name(level.characters.get_player_mut().unwrap().get_identification()));
+
+        // AND items that can't be used aren't
+        let test_item = container.get(0).get_self_item().clone();
+        let data = ContainerFrameHandlerInputResult::UseItem(UseItemData { item: test_item, used: false, message: None, identification: None });
+        match handle_callback(CallbackState { level: &mut level, container: Some(&mut container), data }) {
+            Some(ContainerFrameHandlerInputResult::UseItem(data)) => {
+                assert!(!data.used);
+            },
+            _ => {
+                assert!(false, "Expected a valid Some(UseItem(data)) to return!");
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_identify_scroll() {
+        // GIVEN a player carrying an identify scroll, and a poison potion tucked away in a bag
+        let mut level = build_test_level(None, None);
+        let player = level.characters.get_player_mut().unwrap();
+        player.set_identification(Identification::new(String::from("test")));
+        let scroll = Item::magic(Uuid::new_v4(), ItemForm::SCROLL(ScrollType::Identify), '?', 0.1, 30);
+        let potion = Item::magic(Uuid::new_v4(), ItemForm::POTION(PotionType::Poison), '!', 0.5, 20);
+        let appearance = potion.get_display_name(player.get_identification());
+        let mut bag = Container::new(Uuid::new_v4(), "Bag".to_owned(), 'X', 1.0, 5, ContainerType::OBJECT, 10);
+        bag.add_item(potion.clone()).expect("Failed to add the potion!");
+        let inventory = player.get_inventory_mut();
+        inventory.add(bag).expect("Failed to add the bag!");
+        inventory.add_item(scroll.clone()).expect("Failed to add the scroll!");
+        let mut container = inventory.clone();
+
+        // WHEN we call to read the scroll
+        let data = ContainerFrameHandlerInputResult::UseItem(UseItemData { item: scroll, used: false, message: None, identification: None });
+        let result = handle_callback(CallbackState { level: &mut level, container: Some(&mut container), data });
+
+        // THEN we expect the potion in the bag to have been identified
+        match result {
+            Some(ContainerFrameHandlerInputResult::UseItem(data)) => {
+                assert!(data.used);
+                assert!(data.message.unwrap().ends_with(&format!("The {} is a Potion of Poison.", appearance)));
+            },
+            _ => {
+                assert!(false, "Expected a valid Some(UseItem(data)) to return!");
+            }
+        }
+        assert_eq!("Potion of Poison", potion.get_display_name(level.characters.get_player_mut().unwrap().get_identification()));
+    }
+}
@@ -6,6 +6,7 @@ use termion::event::Key;
 use crate::engine::command::command::Command;
 use crate::engine::level::Level;
 use crate::error::errors::{error_result, ErrorWrapper};
+use crate::input::KeyInputResolver;
 use crate::map::objects::container::Container;
 use crate::map::objects::container::ContainerType::AREA;
 use crate::map::position::Position;
@@ -16,12 +17,13 @@ use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::bindings::action_bindings::Action;
 use crate::ui::bindings::input_bindings::KeyBindings;
 use crate::ui::bindings::look_bindings::{map_look_input_to_side, LookInput, LookKeyBindings};
-use crate::ui::ui::{get_input_key, Draw, UI};
+use crate::ui::ui::UI;
 
 pub struct LookCommand<'a, B: 'static + ratatui::backend::Backend> {
     pub level: &'a mut Level,
     pub ui: &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub bindings : LookKeyBindings
 }
 
@@ -139,7 +141,7 @@ impl <B: ratatui::backend::Backend> Command<LookInput> for LookCommand<'_, B> {
         self.ui.set_console_buffer("Where do you want to look?. Arrow keys to choose. Repeat usage to choose current location.".to_string());
         self.re_render().unwrap();
         
-        let key = get_input_key()?;
+        let key = self.key_input.get_input_key()?;
         let input  = self.bindings.get_input(key);
         let side = map_look_input_to_side(input);
         
@@ -149,7 +151,7 @@ impl <B: ratatui::backend::Backend> Command<LookInput> for LookCommand<'_, B> {
             self.re_render()?;
             let prompt =  describe_position(p, &mut self.level)?;
             self.print(prompt)?;
-            get_input_key().expect("The next keyboard key should have been captured.");
+            self.key_input.get_input_key().expect("The next keyboard key should have been captured.");
         }
         Ok(())
     }
@@ -196,7 +198,7 @@ mod tests {
         assert!(player_pattern_result.is_ok(), "Failed to build player CharacterPattern!");
         let player =  CharacterBuilder::new(player_pattern_result.unwrap())
             .build(String::from("Test Player"));
-        return  Level { map: Some(map) , characters: Characters::new( Some(player), Vec::new()) };
+        return  Level::new(Some(map), Characters::new(Some(player), Vec::new()));
     }
 
     #[test]
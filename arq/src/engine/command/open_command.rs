@@ -3,23 +3,24 @@ use std::io;
 use std::io::Error;
 
 use termion::event::Key;
-use termion::input::TermRead;
 
 use crate::engine::command::command::Command;
 use crate::engine::container_util;
 use crate::engine::engine_helpers::input_handler;
 use crate::engine::level::Level;
 use crate::error::errors::{error_result, ErrorWrapper};
-use crate::input::{IoKeyInputResolver, KeyInputResolver, MockKeyInputResolver};
+use crate::input::KeyInputResolver;
 use crate::map::objects::container::Container;
 use crate::map::position::Position;
+use crate::sound::sound::{play_effect, SoundSinks};
+use crate::sound::sound_effects::SoundEffect;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::bindings::action_bindings::Action;
 use crate::ui::bindings::input_bindings::KeyBindings;
 use crate::ui::bindings::open_bindings::{map_open_input_to_side, OpenInput, OpenKeyBindings};
-use crate::ui::ui::{get_input_key, UI};
+use crate::ui::ui::UI;
 use crate::view::framehandler::container;
-use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{MoveItems, MoveToContainerChoice, TakeItems};
+use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{InspectItem, MoveItems, MoveToContainerChoice, TakeItems};
 use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, MoveItemsData, MoveToContainerChoiceData};
 use crate::view::model::usage_line::{UsageCommand, UsageLine};
 use crate::view::util::callback::Callback;
@@ -30,11 +31,12 @@ pub struct OpenCommand<'a, B: 'static + ratatui::backend::Backend> {
     pub level: &'a mut Level,
     pub ui: &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
-    pub input_resolver: Box<dyn KeyInputResolver>,
-    pub key_bindings: OpenKeyBindings
+    pub key_input : &'a mut dyn KeyInputResolver,
+    pub key_bindings: OpenKeyBindings,
+    pub sound_sinks: Option<&'a mut SoundSinks>
 }
 
-const UI_USAGE_HINT: &str = "Up/Down - Move\nEnter/q - Toggle/clear selection\nEsc - Exit";
+const UI_USAGE_HINT: &str = "Up/Down - Move\nEnter/q - Toggle/clear selection, i - Inspect\nEsc - Exit";
 const NOTHING_ERROR : &str = "There's nothing here to open.";
 
 fn handle_callback<'a>(level : &'a mut Level, position: Position, data : ContainerFrameHandlerInputResult) -> Option<ContainerFrameHandlerInputResult> {
@@ -45,6 +47,9 @@ fn handle_callback<'a>(level : &'a mut Level, position: Position, data : Contain
             data.position = Some(position.clone());
             return container_util::take_items(data , level);
         },
+        InspectItem(data) => {
+            return container_util::inspect_item(data, level);
+        },
         MoveItems(mut data) => {
             log::info!("[open usage] Received data for MoveItems with {} items", data.to_move.len());
             data.position = Some(position.clone());
@@ -125,30 +130,26 @@ impl <B: ratatui::backend::Backend> OpenCommand<'_, B> {
 
         let mut commands : Vec<UsageCommand> = vec![
             UsageCommand::new('o', String::from("open") ),
-            UsageCommand::new('t', String::from("take"))
+            UsageCommand::new('t', String::from("take")),
+            UsageCommand::new('s', String::from("sort")),
+            UsageCommand::new('f', String::from("filter")),
+            UsageCommand::new('/', String::from("search"))
         ];
         let usage_line = UsageLine::new(commands);
-        let container_view = container::build_container_frame_handler(subview_container, usage_line);
+        let container_view = container::build_container_frame_handler(subview_container, usage_line, self.level.get_player_identification(), self.ui.get_theme().clone());
 
         let ui = &mut self.ui;
         let terminal_manager = &mut self.terminal_manager;
         let frame_handler = WorldContainerViewFrameHandlers { container_frame_handlers: vec![container_view], choice_frame_handler: None };
         let level = &mut self.level;
-        
-        let mock_input_resolver = &mut self.input_resolver.as_any_mut().downcast_mut::<MockKeyInputResolver>();
-        
-        let mut input_handler : Box<dyn KeyInputResolver> = Box::new(IoKeyInputResolver{});
-        if let Some(mock) = mock_input_resolver {
-            input_handler = Box::new(MockKeyInputResolver { key_results: mock.key_results.clone() });
-        }
-        
+
         let mut world_container_view = WorldContainerView {
             ui,
             terminal_manager,
             frame_handlers: frame_handler,
             container: view_container,
             callback: Box::new(|_data| {None}),
-            input_resolver: input_handler
+            key_input: &mut *self.key_input
         };
         world_container_view.set_callback(Box::new(|input_result| {
             return handle_callback(level, p.clone(), input_result);
@@ -177,7 +178,7 @@ impl <B: ratatui::backend::Backend> Command<OpenInput> for OpenCommand<'_, B> {
         self.ui.set_console_buffer("What do you want to open?. Arrow keys to choose. Repeat usage to choose current location.".to_string());
         self.re_render().unwrap();
 
-        let key = get_input_key()?;
+        let key = self.key_input.get_input_key()?;
         let input  = self.key_bindings.get_input(key);
        
         let mut message = NOTHING_ERROR.to_string();
@@ -219,6 +220,7 @@ impl <B: ratatui::backend::Backend> Command<OpenInput> for OpenCommand<'_, B> {
                 self.ui.clear_console_buffer();
                 self.re_render()?;
                 log::info!("Player opening container of type {:?} and length: {}", c.container_type, c.get_total_count());
+                play_effect(&mut self.sound_sinks, SoundEffect::ChestOpen);
                 self.open_container(p.clone(), &c)?;
             } else {
                 return ErrorWrapper::internal_result(message)
@@ -320,13 +322,14 @@ mod tests {
             }
         }
 
-        // AND we expect the inventory to contain the 2 items taken
+        // AND we expect the 2 items taken to be stacked onto the identical items already in the inventory
         let inventory = level.characters.get_player_mut().unwrap().get_inventory_mut();
         let updated_inventory = inventory.get_contents();
-        assert_eq!(initial_top_level_inventory_item_count + 2, updated_inventory.len());
-        // AND the bottom 2 items should be the moved items
-        assert_eq!(chosen_item_1, *updated_inventory.get(initial_top_level_inventory_item_count).unwrap().get_self_item());
-        assert_eq!(chosen_item_2, *updated_inventory.get(initial_top_level_inventory_item_count + 1).unwrap().get_self_item());
+        assert_eq!(initial_top_level_inventory_item_count, updated_inventory.len());
+        for chosen_item in [&chosen_item_1, &chosen_item_2] {
+            let stack = updated_inventory.iter().find(|c| c.get_self_item().can_stack_with(chosen_item)).unwrap();
+            assert_eq!(2, stack.get_self_item().get_quantity());
+        }
         
         // And the container we took from should have 2 less items in the top level
         let updated_container = level.get_map_mut().unwrap().find_container(&container, container_pos).unwrap();
@@ -399,8 +402,9 @@ mod tests {
             level: game_engine.levels.get_level_mut(),
             ui: &mut game_engine.ui_wrapper.ui, 
             terminal_manager: &mut game_engine.ui_wrapper.terminal_manager,
-            input_resolver: Box::new(MockKeyInputResolver { key_results }), 
-            key_bindings: build_default_open_keybindings()
+            key_input: &mut MockKeyInputResolver { key_results },
+            key_bindings: build_default_open_keybindings(),
+            sound_sinks: None
         };
         
         // WHEN we call to handle the opening of a container
@@ -0,0 +1,182 @@
+use crate::engine::command::command::Command;
+use crate::engine::container_util;
+use crate::engine::level::Level;
+use crate::error::errors::ErrorWrapper;
+use crate::input::KeyInputResolver;
+use crate::map::objects::container::Container;
+use crate::map::objects::identification::Identification;
+use crate::map::objects::items::{Item, ItemCategory};
+use crate::map::position::Position;
+use crate::sound::sound::{play_effect, SoundSinks};
+use crate::sound::sound_effects::SoundEffect;
+use crate::terminal::terminal_manager::TerminalManager;
+use crate::ui::bindings::action_bindings::Action;
+use crate::ui::ui::UI;
+use crate::view::framehandler::container::ContainerFrameHandlerInputResult::TakeItems;
+use crate::view::framehandler::container::TakeItemsData;
+use crate::view::pickup_view::PickUpView;
+use crate::view::View;
+
+const NOTHING_ERROR : &str = "There's nothing here to pick up.";
+
+/*
+ * Takes items straight from the floor under the player into their inventory
+ * A single item is taken right away, otherwise the player chooses which to take
+ */
+pub struct PickUpCommand<'a, B: 'static + ratatui::backend::Backend> {
+    pub level: &'a mut Level,
+    pub ui: &'a mut UI,
+    pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
+    pub sound_sinks: Option<&'a mut SoundSinks>
+}
+
+// The floor container under the player, and the items on it that can be carried (i.e no chests)
+fn find_floor_items(level: &mut Level) -> Option<(Position, Container, Vec<Item>)> {
+    let position = level.get_player_mut()?.get_global_position();
+    let floor = level.get_map_mut()?.find_container_mut(position)?.clone();
+    let items: Vec<Item> = floor.get_contents().iter()
+        .filter(|c| !c.is_fixed_container())
+        .map(|c| c.get_self_item().clone())
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+    Some((position, floor, items))
+}
+
+fn describe_items(items: &[Item], identification: &Identification) -> String {
+    match items {
+        [item] => format!("the {}", item.get_stack_name(identification)),
+        _ => format!("{} items", items.len())
+    }
+}
+
+// Moves the items from the floor into the player's inventory, returning a message describing what happened
+pub fn pick_up_items(level: &mut Level, position: Position, floor: Container, items: Vec<Item>, mut sound_sinks: Option<&mut SoundSinks>) -> String {
+    let data = TakeItemsData { source: floor, to_take: items.clone(), position: Some(position) };
+    let untaken = match container_util::take_items(data, level) {
+        Some(TakeItems(result)) => result.to_take,
+        _ => items.clone()
+    };
+    let taken: Vec<Item> = items.into_iter().filter(|i| !untaken.iter().any(|u| u.id_equals(i))).collect();
+    let identification = level.get_player_identification();
+
+    let mut messages = Vec::new();
+    if !taken.is_empty() {
+        play_effect(&mut sound_sinks, SoundEffect::Pickup);
+        messages.push(format!("You pick up {}.", describe_items(&taken, &identification)));
+    }
+    if !untaken.is_empty() {
+        messages.push(format!("You can't carry {}.", describe_items(&untaken, &identification)));
+    }
+    messages.join(" ")
+}
+
+// Picks up anything under the player in the given categories, returning a message if anything was there to pick up
+pub fn auto_pick_up(level: &mut Level, categories: &[ItemCategory], sound_sinks: Option<&mut SoundSinks>) -> Option<String> {
+    if categories.is_empty() {
+        return None;
+    }
+    let (position, floor, items) = find_floor_items(level)?;
+    let to_take: Vec<Item> = items.into_iter().filter(|i| categories.contains(&i.get_category())).collect();
+    if to_take.is_empty() {
+        return None;
+    }
+    Some(pick_up_items(level, position, floor, to_take, sound_sinks))
+}
+
+impl <B: ratatui::backend::Backend> Command<()> for PickUpCommand<'_, B> {
+    fn can_handle_action(&self, action: Action) -> bool {
+        matches!(action, Action::PickUp)
+    }
+
+    fn start(&mut self) -> Result<(), ErrorWrapper> {
+        Ok(())
+    }
+
+    fn handle_input(&mut self, _input: Option<&()>) -> Result<(), ErrorWrapper> {
+        let (position, floor, items) = match find_floor_items(self.level) {
+            Some(found) => found,
+            None => {
+                self.ui.set_console_message(NOTHING_ERROR.to_string());
+                return Ok(());
+            }
+        };
+
+        let to_take = if items.len() == 1 {
+            items
+        } else {
+            let mut view = PickUpView::new(self.ui, self.terminal_manager, self.key_input, items, self.level.get_player_identification());
+            match view.begin()?.view_specific_result {
+                Some(chosen) => chosen,
+                None => return Ok(())
+            }
+        };
+
+        let message = pick_up_items(self.level, position, floor, to_take, self.sound_sinks.as_deref_mut());
+        self.ui.set_console_message(message);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::engine::command::pickup_command::{auto_pick_up, pick_up_items};
+    use crate::engine::level::Level;
+    use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::items::{Item, ItemCategory, ItemForm, MaterialType};
+    use crate::map::position::Position;
+    use crate::test::utils::test_utils::build_test_level;
+
+    fn build_floor(items: Vec<Item>) -> Container {
+        let mut floor = Container::new(Uuid::new_v4(), "Floor".to_owned(), ' ', 0.0, 0, ContainerType::AREA, 100);
+        floor.add_items(items);
+        floor
+    }
+
+    fn build_level(items: Vec<Item>) -> Level {
+        // The test level's player stands at 0,0
+        build_test_level(Some((Position::zero(), build_floor(items))), None)
+    }
+
+    fn floor_item_names(level: &mut Level) -> Vec<String> {
+        let floor = level.get_map_mut().unwrap().find_container_mut(Position::zero()).unwrap();
+        floor.get_contents().iter().map(|c| c.get_self_item().get_name()).collect()
+    }
+
+    #[test]
+    fn test_pick_up_items() {
+        // GIVEN a Gold Bar on the floor under the player
+        let bar = Item::new(Uuid::new_v4(), "Gold Bar".to_owned(), MaterialType::GOLD, 'X', 1.0, 100);
+        let mut level = build_level(vec![bar.clone()]);
+        let floor = level.get_map_mut().unwrap().find_container_mut(Position::zero()).unwrap().clone();
+
+        // WHEN we pick it up
+        let message = pick_up_items(&mut level, Position::zero(), floor, vec![bar], None);
+
+        // THEN it's moved from the floor into the player's inventory
+        assert_eq!("You pick up the Gold Bar.", message);
+        assert!(floor_item_names(&mut level).is_empty());
+        let inventory = level.get_player_mut().unwrap().get_inventory_mut();
+        assert!(inventory.get_contents().iter().any(|c| c.get_self_item().get_name() == "Gold Bar"));
+    }
+
+    #[test]
+    fn test_auto_pick_up() {
+        // GIVEN a coin and a sword on the floor under the player
+        let coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        let sword = Item::new(Uuid::new_v4(), "Sword".to_owned(), MaterialType::STEEL, 'X', 1.0, 10);
+        let mut level = build_level(vec![coin, sword]);
+
+        // WHEN nothing is configured for auto-pickup
+        // THEN nothing is picked up
+        assert_eq!(None, auto_pick_up(&mut level, &[], None));
+
+        // AND when coins are configured, only the coin is picked up
+        assert_eq!(Some(String::from("You pick up the Gold Coin.")), auto_pick_up(&mut level, &[ItemCategory::Coins], None));
+        assert_eq!(vec![String::from("Sword")], floor_item_names(&mut level));
+    }
+}
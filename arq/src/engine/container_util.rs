@@ -1,12 +1,14 @@
 use log::{error, info};
 use std::io;
 
+use crate::character::equipment::get_potential_slots;
+use crate::character::stats::attributes::Attribute;
 use crate::character::Character;
 use crate::engine::level::Level;
 use crate::map::objects::container::Container;
 use crate::map::objects::items::Item;
 use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{MoveItems, TakeItems};
-use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, MoveItemsData, TakeItemsData};
+use crate::view::framehandler::container::{ContainerFrameHandlerInputResult, InspectItemData, MoveItemsData, TakeItemsData};
 
 pub struct AddToTargetResult {
     pub moved : Vec<Container>,
@@ -14,6 +16,13 @@ pub struct AddToTargetResult {
     pub updated_target : Option<Container>
 }
 
+// The container to remove from the source once the item has been moved, holding only the quantity moved
+pub fn build_removal(container_item: &Container, item: &Item) -> Container {
+    let mut removal = container_item.clone();
+    removal.get_self_item_mut().set_quantity(item.get_quantity());
+    removal
+}
+
 fn add_to_target(source : Container, target: &mut Container, to_add: Vec<Item>) -> AddToTargetResult {
     let mut moved = Vec::new();
     let mut unmoved = Vec::new();
@@ -22,10 +31,12 @@ fn add_to_target(source : Container, target: &mut Container, to_add: Vec<Item>)
     log::info!("Adding items from: {} ({}) to: {} ({})", from_container_name, from_container_id, target.get_self_item().get_name(), target.get_self_item().get_id());
     for item in to_add {
         if let Some(container_item) = source.find(&item) {
-            if target.can_fit_container_item(container_item) {
-                match target.add(container_item.clone()) {
+            // Only the quantity chosen is moved from a stack
+            let adding = container_item.clone_quantity(item.get_quantity());
+            if target.can_fit_container_item(&adding) {
+                match target.add(adding) {
                     Ok(()) => {
-                        moved.push(container_item.clone());
+                        moved.push(build_removal(container_item, &item));
                     },
                     Err(e) => {
                         error!("Couldn't add the item to the target container: {}", e)
@@ -52,12 +63,13 @@ pub fn take_items(data: TakeItemsData, level : &mut Level) -> Option<ContainerFr
             for item in data.to_take {
                 if let Some(container_item) = data.source.find(&item) {
                     let inventory = player.get_inventory_mut();
-                    if inventory.can_fit_container_item(container_item) {
+                    let taking = container_item.clone_quantity(item.get_quantity());
+                    if inventory.can_fit_container_item(&taking) {
                         log::info!("Taking item: {}", item.get_name());
-                        match player.get_inventory_mut().add(container_item.clone()) {
+                        match player.get_inventory_mut().add(taking) {
                             Ok(()) => {
                                 // If it's added to the player inventory, go ahead and add it to the taken list for removal
-                                taken.push(container_item.clone());
+                                taken.push(build_removal(container_item, &item));
                             },
                             Err(e) => {
                                 error!("Failed to take item, couldn't add it to the Player's inventory.. {}", e);
@@ -88,6 +100,30 @@ pub fn take_items(data: TakeItemsData, level : &mut Level) -> Option<ContainerFr
     return None
 }
 
+/*
+ * Fills in whatever the player has equipped in the slots the inspected item would use, for comparison
+ * Looking at an unidentified item gives the player an Intelligence check to recognise it
+ */
+pub fn inspect_item(mut data: InspectItemData, level : &mut Level) -> Option<ContainerFrameHandlerInputResult> {
+    if let Some(player) = level.characters.get_player_mut() {
+        if !data.item.is_identified(player.get_identification()) {
+            let intelligence = player.get_attribute_scores().iter()
+                .find(|s| s.attribute == Attribute::Intelligence)
+                .map_or(0, |s| s.score);
+            if player.get_identification_mut().try_recall(&data.item.get_item_form(), intelligence) {
+                log::info!("Player recognised item: {}", data.item.get_name());
+            }
+        }
+        data.identification = player.get_identification().clone();
+        let equipment = player.get_equipment();
+        data.equipped = get_potential_slots(data.item.item_type.clone()).into_iter()
+            .filter_map(|slot| equipment.get_item(slot).cloned())
+            .filter(|equipped| !equipped.id_equals(&data.item))
+            .collect();
+    }
+    Some(ContainerFrameHandlerInputResult::InspectItem(data))
+}
+
 fn find_container_mut(root : &mut Container, target: Item) -> Option<&mut Container> {
     let _target_result: Option<&mut Container> = None;
     let root_name = root.get_self_item().get_name().clone();
@@ -281,14 +317,15 @@ mod tests {
 
     use crate::character::builder::character_builder::{CharacterBuilder, CharacterPattern};
     use crate::character::characters::Characters;
-    use crate::engine::container_util::{move_items, move_player_items};
+    use crate::engine::container_util::{move_items, move_player_items, take_items};
     use crate::engine::level::Level;
     use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::items::{Item, ItemForm, MaterialType};
     use crate::map::position::{build_square_area, Position};
     use crate::map::tile::TileType;
     use crate::map::Tiles;
-    use crate::view::framehandler::container::ContainerFrameHandlerInputResult::MoveItems;
-    use crate::view::framehandler::container::MoveItemsData;
+    use crate::view::framehandler::container::ContainerFrameHandlerInputResult::{MoveItems, TakeItems};
+    use crate::view::framehandler::container::{MoveItemsData, TakeItemsData};
 
     fn build_test_level(container_position: Position, area_container: Container) -> Level {
         let tile_library = crate::map::tile::build_library();
@@ -314,7 +351,7 @@ mod tests {
         assert!(player_pattern_result.is_ok(), "Failed to build player CharacterPattern!");
         let player =  CharacterBuilder::new(player_pattern_result.unwrap())
             .build(String::from("Test Player"));
-        return  Level { map: Some(map) , characters: Characters::new( Some(player), Vec::new())  };
+        return  Level::new(Some(map), Characters::new(Some(player), Vec::new()));
     }
 
     fn build_player_test_level() -> Level {
@@ -337,7 +374,39 @@ mod tests {
         assert!(player_pattern_result.is_ok(), "Failed to build player CharacterPattern!");
         let player =  CharacterBuilder::new(player_pattern_result.unwrap())
             .build(String::from("Test Player"));
-        return  Level { map: Some(map) , characters: Characters::new( Some(player), Vec::new())  };
+        return  Level::new(Some(map), Characters::new(Some(player), Vec::new()));
+    }
+
+    #[test]
+    fn test_take_items_from_stack() {
+        // GIVEN a chest holding a stack of 10 gold coins
+        let mut chest = Container::new(Uuid::new_v4(), "Chest".to_owned(), 'X', 1.0, 1, ContainerType::AREA, 100);
+        let mut coins = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        coins.set_quantity(10);
+        chest.add_item(coins.clone()).unwrap();
+        let container_pos = Position { x: 1, y: 1 };
+        let mut level = build_test_level(container_pos, chest.clone());
+
+        // WHEN we take 4 of them, twice
+        let mut taking = coins.clone();
+        taking.set_quantity(4);
+        for _i in 0..2 {
+            let data = TakeItemsData { source: level.get_map_mut().unwrap().find_container(&chest, container_pos).unwrap().clone(), to_take: vec![taking.clone()], position: Some(container_pos) };
+            let result = take_items(data, &mut level);
+            // THEN everything asked for is taken
+            assert!(matches!(result, Some(TakeItems(ref d)) if d.to_take.is_empty()));
+        }
+
+        // AND 2 are left in the chest
+        let map_chest = level.get_map_mut().unwrap().find_container(&chest, container_pos).unwrap();
+        assert_eq!(2, map_chest.get_contents()[0].get_self_item().get_quantity());
+        // AND the player has a single stack of 8
+        let inventory = level.get_player_mut().unwrap().get_inventory_mut();
+        let taken: Vec<u32> = inventory.get_contents().iter()
+            .filter(|c| c.get_self_item().can_stack_with(&coins))
+            .map(|c| c.get_self_item().get_quantity())
+            .collect();
+        assert_eq!(vec![8], taken);
     }
 
     #[test]
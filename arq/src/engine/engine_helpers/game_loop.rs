@@ -1,14 +1,19 @@
-use crate::engine::game_engine::GameEngine;
+use crate::engine::game_engine::{GameEngine, PlayerTurn};
 use crate::error::errors::{ErrorType, ErrorWrapper};
-use crate::ui::ui::get_input_key;
 use crate::view::game_over_view::GameOverChoice;
 
 pub async fn game_loop<B: ratatui::backend::Backend + Send>(engine: &mut GameEngine<B>) -> Result<Option<GameOverChoice>, ErrorWrapper> {
-    let game_over_result = engine.player_turn().await;
-    match game_over_result {
-        Ok(goc) => {
-            npc_turns(engine)?;
-            return Ok(goc);
+    let player_turn_result = engine.player_turn().await;
+    match player_turn_result {
+        Ok(PlayerTurn { game_over_choice: Some(goc), .. }) => {
+            return Ok(Some(goc));
+        },
+        Ok(PlayerTurn { game_over_choice: None, turn_taken }) => {
+            // Nothing else happens while the player only looks around
+            if turn_taken {
+                npc_turns(engine)?;
+            }
+            return engine.check_player_death();
         },
         Err(e) => {
             match e.error_type {
@@ -29,7 +34,9 @@ pub async fn game_loop<B: ratatui::backend::Backend + Send>(engine: &mut GameEng
     }
 }
 
-fn npc_turns<B: ratatui::backend::Backend + Send>(_engine: &mut GameEngine<B>)  -> Result<(), ErrorWrapper> {
-    // TODO NPC movement
+fn npc_turns<B: ratatui::backend::Backend + Send>(engine: &mut GameEngine<B>)  -> Result<(), ErrorWrapper> {
+    let diagonal = engine.settings.is_diagonal_movement();
+    engine.levels.get_level_mut().chase_player(diagonal);
+    engine.levels.end_turn();
     return Ok(());
 }
@@ -7,7 +7,7 @@ use crate::engine::command::open_command::OpenCommand;
 use crate::engine::engine_helpers::menu::menu_command;
 use crate::engine::game_engine::GameEngine;
 use crate::error::errors::ErrorWrapper;
-use crate::input::IoKeyInputResolver;
+use crate::input::KeyInputResolver;
 use crate::ui::bindings::action_bindings::Action;
 use crate::ui::bindings::input_bindings::{AllKeyBindings, KeyBindings};
 use crate::view::game_over_view::GameOverChoice;
@@ -15,15 +15,29 @@ use crate::view::game_over_view::GameOverChoice;
 pub struct InputHandler {
   current_action: Option<Action>,
   keybindings: AllKeyBindings,  
+  // Where all of the game's input is read from, the terminal unless a session is being recorded or replayed
+  key_input: Box<dyn KeyInputResolver>
 }
 
 impl InputHandler {
-    pub fn new(keybindings: AllKeyBindings) -> InputHandler {
+    pub fn new(keybindings: AllKeyBindings, key_input: Box<dyn KeyInputResolver>) -> InputHandler {
         InputHandler {
             current_action: None,
-            keybindings
+            keybindings,
+            key_input
         }
     }
+
+    pub fn get_key_input(&mut self) -> &mut dyn KeyInputResolver {
+        self.key_input.as_mut()
+    }
+
+    // Only swapped out once the game's running to script it's input for tests
+    #[cfg(test)]
+    pub fn set_key_input(&mut self, key_input: Box<dyn KeyInputResolver>) {
+        self.key_input = key_input;
+    }
+
     pub async fn handle_input(&mut self, key : Key) -> Option<Action> {
         let action_bindings = &self.keybindings.action_key_bindings;
         let action_input = action_bindings.get_input(key);
@@ -45,6 +59,8 @@ mod tests {
     use crate::engine::engine_helpers::input_handler::InputHandler;
     use crate::engine::game_engine::*;
     use crate::engine::level::Levels;
+    use crate::input::event_source::ScriptedEventSource;
+    use crate::input::IoKeyInputResolver;
     use crate::map::position::Position;
     use crate::map::position::{build_square_area, Area};
     use crate::map::tile::{build_library, TileDetails, TileType};
@@ -95,7 +111,7 @@ mod tests {
     #[test]
     fn test_build_game_engine() {
         let terminal_manager = terminal_manager::init_test(MIN_RESOLUTION).unwrap();
-        let _game_engine = build_game_engine(terminal_manager);
+        let _game_engine = build_game_engine(terminal_manager, Box::new(IoKeyInputResolver::new(Box::new(ScriptedEventSource::new(Vec::new())))));
     }
 
     #[tokio::test]
@@ -1,31 +1,34 @@
 use std::future::Future;
-use std::io;
 use std::io::Error;
 use std::pin::Pin;
 
 use log::{error, info};
-use termion::input::TermRead;
 
 use crate::engine::game_engine::GameEngine;
+use crate::engine::run_history::{load_run_history, RunOutcome};
 use crate::error::errors::ErrorWrapper;
-use crate::settings::Settings;
+use crate::settings::{Setting, Settings};
+use crate::sound::music::MusicContext;
 use crate::ui::ui::StartMenuChoice;
 use crate::view::game_over_view::GameOverChoice;
+use crate::view::leaderboard_view::LeaderboardView;
 use crate::view::settings_menu_view::SettingsMenuView;
 use crate::view::View;
-use crate::widget::stateful::dropdown_widget::get_resolution_dropdown_options;
+use crate::widget::stateful::dropdown_widget::{get_resolution_dropdown_options, DropdownOption, DropdownSetting};
 use crate::widget::widgets::{build_settings_widgets, WidgetList};
 use crate::widget::StatefulWidgetType;
 
 pub async fn start_menu<B: ratatui::backend::Backend + Send>(engine: &mut GameEngine<B>, _choice: Option<StartMenuChoice>) -> Pin<Box<dyn Future< Output = Result<Option<GameOverChoice>, ErrorWrapper> > + '_ >> {
     Box::pin(async move {
+        engine.set_music_context(MusicContext::Menu);
         let game_running= engine.is_game_running();
         let ui_wrapper = &mut engine.ui_wrapper;
+        let key_input = engine.input_handler.get_key_input();
         ui_wrapper.clear_screen()?;
 
         // Hide additional widgets when paused
         ui_wrapper.ui.render_additional = false;
-        let start_choice = ui_wrapper.draw_start_menu(game_running)?.view_specific_result.unwrap();
+        let start_choice = ui_wrapper.draw_start_menu(game_running, &mut *key_input)?.view_specific_result.unwrap();
         match start_choice {
             StartMenuChoice::Play => {
                 ui_wrapper.ui.render_additional = true;
@@ -46,6 +49,7 @@ pub async fn start_menu<B: ratatui::backend::Backend + Send>(engine: &mut GameEn
                 let mut settings_menu = SettingsMenuView {
                     ui: &mut ui_wrapper.ui,
                     terminal_manager: &mut ui_wrapper.terminal_manager,
+                    key_input,
                     menu: crate::view::util::widget_menu::WidgetMenu {
                         selected_widget: Some(0),
                         widgets: WidgetList { widgets, widget_index: Some(0) }
@@ -60,12 +64,19 @@ pub async fn start_menu<B: ratatui::backend::Backend + Send>(engine: &mut GameEn
             },
             StartMenuChoice::Info => {
                 info!("Showing info..");
-                let _ui = &mut engine.ui_wrapper.ui;
                 engine.ui_wrapper.draw_info()?;
-                io::stdin().keys().next();
+                engine.input_handler.get_key_input().get_input_key()?;
+            },
+            StartMenuChoice::Leaderboard => {
+                info!("Showing leaderboard..");
+                let history = load_run_history(engine.get_run_history_path());
+                let mut leaderboard = LeaderboardView::new(&mut engine.ui_wrapper.ui, &mut engine.ui_wrapper.terminal_manager, engine.input_handler.get_key_input(), history);
+                leaderboard.begin()?;
             },
             StartMenuChoice::Quit => {
                 if engine.is_game_running() {
+                    // Quitting part way through still counts as a run
+                    engine.record_run(RunOutcome::Quit)?;
                     engine.stop_game();
                 }
                 return Ok(Some(GameOverChoice::EXIT));
@@ -130,6 +141,13 @@ fn handle_settings_menu_selection(settings: &mut Settings, widgets: WidgetList)
                     }
 
                 }
+
+                if let Some(s) = settings.theme_settings.iter_mut().find(|x| x.name == t.get_name()) {
+                    choose_dropdown_option(s, t.get_selection());
+                }
+                if let Some(s) = settings.glyph_settings.iter_mut().find(|x| x.name == t.get_name()) {
+                    choose_dropdown_option(s, t.get_selection());
+                }
             },
             _ => {}
         }
@@ -138,3 +156,13 @@ fn handle_settings_menu_selection(settings: &mut Settings, widgets: WidgetList)
     Ok(())
 }
 
+// Chooses the option with the display name selected in the widget
+fn choose_dropdown_option<T: Clone>(setting: &mut Setting<DropdownSetting<DropdownOption<T>>>, selection: String) {
+    let option_chosen = setting.value.options.iter().find(|opt| opt.display_name == selection);
+    if let Some(option) = option_chosen {
+        info!("{} selected: {:?}", setting.name, option.display_name);
+        setting.value.chosen_option = option.clone();
+    } else {
+        error!("No option selected for {}!", setting.name)
+    }
+}
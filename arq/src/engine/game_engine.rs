@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::io::{Error, ErrorKind};
 
 use log::info;
@@ -9,29 +10,43 @@ use ratatui::backend::Backend;
 use crate::character::battle::Battle;
 use crate::character::builder::character_builder::{build_dev_player_inventory, CharacterBuilder, CharacterPattern};
 use crate::character::characters::Characters;
+use crate::character::equipment::EquipmentSlot;
 use crate::engine::combat::Combat;
 use crate::engine::command::command::Command;
 use crate::engine::command::inventory_command::InventoryCommand;
 use crate::engine::command::look_command::LookCommand;
 use crate::engine::command::open_command::OpenCommand;
+use crate::engine::command::pickup_command::{auto_pick_up, PickUpCommand};
 use crate::engine::engine_helpers::game_loop::game_loop;
 use crate::engine::engine_helpers::input_handler::InputHandler;
 use crate::engine::engine_helpers::menu::menu_command;
-use crate::engine::engine_helpers::spawning::{respawn_npcs, respawn_player};
-use crate::engine::level::{init_level_manager, LevelChange, LevelChangeResult, Levels};
+use crate::engine::engine_helpers::spawning::respawn_player;
+use crate::engine::pathfinding::Pathfinding;
+use crate::engine::travel::{Travel, TravelGoal, TravelInterruption};
+use crate::engine::level::{init_level_manager, Level, LevelChange, LevelChangeResult, Levels};
 use crate::engine::process::map_generation::MapGeneration;
+use crate::engine::run_history::{record_run, RunOutcome, RunRecord, RUN_HISTORY_FILE};
+use crate::engine::world::{build_world, World};
 use crate::error::errors::ErrorWrapper;
-use crate::input::IoKeyInputResolver;
-use crate::map::position::{Area, Side};
+use crate::input::event_source::ScriptedEventSource;
+use crate::input::{InputEvent, IoKeyInputResolver, KeyInputResolver};
+use crate::map::objects::identification::Identification;
+use crate::map::objects::items::Item;
+use crate::map::map_exporter::write_level_export;
+use crate::map::overworld_generator::{build_overworld_generator, Overworld, OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y};
+use crate::map::position::{build_rectangular_area, Direction, Position};
+use crate::map::tile::TileType;
 use crate::map::Map;
-use crate::settings::{build_settings, Settings, SETTING_BG_MUSIC, SETTING_RESOLUTION, SETTING_RNG_SEED};
+use crate::settings::{build_settings, Settings, SETTING_RESOLUTION, SETTING_RNG_SEED};
 use crate::sound::sound::{build_sound_sinks, SoundSinks};
+use crate::sound::music::MusicContext;
+use crate::sound::sound_effects::SoundEffect;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::bindings::action_bindings::Action;
 use crate::ui::bindings::input_bindings::{CommandSpecificKeyBindings, KeyBindings};
-use crate::ui::resolution::Resolution;
-use crate::ui::ui::{build_ui, get_input_key};
-use crate::ui::ui_wrapper::UIWrapper;
+use crate::ui::theme::build_theme;
+use crate::ui::ui::build_ui;
+use crate::ui::ui_wrapper::{build_map_view_areas, UIWrapper};
 use crate::view::combat_view::CombatView;
 use crate::view::dialog_view::DialogView;
 use crate::view::framehandler::map_generation::MapGenerationFrameHandler;
@@ -40,6 +55,7 @@ use crate::view::model::usage_line::{UsageCommand, UsageLine};
 use crate::view::util::callback::Callback;
 use crate::view::util::callback::CallbackHandler;
 use crate::view::util::progress_display::ProgressDisplay;
+use crate::view::verify_display_size;
 use crate::view::View;
 use crate::widget::character_stat_line::CharacterStatLineWidget;
 use crate::widget::StandardWidgetType;
@@ -48,9 +64,28 @@ pub struct GameEngine<B: 'static + Backend>  {
     pub ui_wrapper : UIWrapper<B>,
     pub(crate) settings: Settings,
     pub levels: Levels,
+    // The overworld and it's dungeons, None when playing on a standalone set of levels
+    world: Option<World>,
+    // The deepest dungeon level reached this run, for the run history
+    deepest_level: usize,
+    // Where finished runs are recorded, see RUN_HISTORY_FILE
+    run_history_path: String,
     sound_sinks: Option<SoundSinks>,
     game_running : bool,
-    pub(crate) input_handler: InputHandler
+    pub(crate) input_handler: InputHandler,
+    // Any travel the player is part way through, taking a step each turn
+    travel: Option<Travel>,
+    // Whether the player's current turn has been used up, i.e by moving rather than just looking at their inventory
+    turn_taken: bool
+}
+
+/*
+ * What came of the player's turn
+ * NPCs only get to act (and the turn count only goes up) when the player's turn was actually used up
+ */
+pub(crate) struct PlayerTurn {
+    pub game_over_choice: Option<GameOverChoice>,
+    pub turn_taken: bool
 }
 
 impl <B : Backend + Send> GameEngine<B> {
@@ -62,6 +97,25 @@ impl <B : Backend + Send> GameEngine<B> {
         self.game_running = false
     }
 
+    // Plays within the given world, i.e with the overworld to return to on leaving a dungeon
+    #[cfg(test)]
+    pub(crate) fn set_world(&mut self, world: World) {
+        self.world = Some(world);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn get_world(&self) -> Option<&World> {
+        self.world.as_ref()
+    }
+
+    pub fn set_run_history_path(&mut self, path: String) {
+        self.run_history_path = path;
+    }
+
+    pub fn get_run_history_path(&self) -> &str {
+        &self.run_history_path
+    }
+
     pub fn rebuild(&mut self) {
         let settings = build_settings();
         // Grab the randomised seed
@@ -70,6 +124,8 @@ impl <B : Backend + Send> GameEngine<B> {
         let rng = Seeder::from(map_seed).into_rng();
         self.game_running = false;
         self.levels = init_level_manager(seed_copy, rng);
+        self.world = None;
+        self.deepest_level = 0;
         self.settings = settings;
     }
 
@@ -83,95 +139,81 @@ impl <B : Backend + Send> GameEngine<B> {
         self.levels.rng = rng;
 
         let bg_music_volume = self.settings.get_bg_music_volume();
+        let sfx_volume = self.settings.get_sfx_volume();
         if let Some(sinks) = &mut self.sound_sinks {
             sinks.get_bg_sink_mut().configure(bg_music_volume);
+            sinks.get_sfx_sink_mut().configure(sfx_volume);
         }
 
+        self.ui_wrapper.ui.set_theme(build_theme(self.settings.get_theme()));
+        self.ui_wrapper.ui.set_glyph_set(self.settings.get_glyph_set());
+
         let resolution = self.settings.get_resolution();
-        if let Some(res) = resolution.value {
-            info!("Re-init UI with resolution: {:?}", res);
-            self.ui_wrapper.ui.re_init(Area::from_resolution(res));
-        } else {
-            // Get the current terminal size and use that for the fullscreen size
-            let resolution = Resolution::from_rect(self.ui_wrapper.terminal_manager.terminal.get_frame().size());
-            info!("Re-init UI with fullscreen resolution: {:?}", resolution);
-            self.ui_wrapper.ui.re_init(Area::from_resolution(resolution));
-        }
+        info!("Re-init UI with resolution: {}", resolution.display_name);
+        self.ui_wrapper.ui.set_resolution(resolution.value);
+        verify_display_size(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, self.input_handler.get_key_input());
         Ok(())
     }
 
     fn setup_sinks(&mut self) -> Result<(), ErrorWrapper> {
         if self.sound_sinks.is_none() {
-            // Start the sound sinks and play bg music
-            let mut sinks = build_sound_sinks();
-            let bg_music_volume = self.settings.find_u32_setting_value(SETTING_BG_MUSIC.to_string()).unwrap();
-            sinks.get_bg_sink_mut().configure(bg_music_volume);
-            sinks.get_bg_sink_mut().play();
-            self.sound_sinks = Some(sinks);
+            self.start_sound(build_sound_sinks());
         }
         Ok(())
     }
 
+    // Starts playing bg music through the given sinks, at the volume from settings
+    pub(crate) fn start_sound(&mut self, mut sinks: SoundSinks) {
+        let bg_music_volume = self.settings.get_bg_music_volume();
+        sinks.get_bg_sink_mut().configure(bg_music_volume);
+        sinks.get_bg_sink_mut().play();
+        sinks.get_sfx_sink_mut().configure(self.settings.get_sfx_volume());
+        self.sound_sinks = Some(sinks);
+    }
+
     pub fn init(&mut self) -> Result<(), ErrorWrapper> {
         self.setup_sinks()?;
+        self.init_ui()
+    }
 
+    // Sizes the UI to the chosen resolution, this is everything init does besides starting the sound
+    pub(crate) fn init_ui(&mut self) -> Result<(), ErrorWrapper> {
+        self.ui_wrapper.ui.set_theme(build_theme(self.settings.get_theme()));
+        self.ui_wrapper.ui.set_glyph_set(self.settings.get_glyph_set());
         let ui_wrapper = &mut self.ui_wrapper;
-        let ui = &mut ui_wrapper.ui;
-        let terminal_manager = &mut ui_wrapper.terminal_manager;
-
         let resolution_option = self.settings.find_dropdown_setting_value(SETTING_RESOLUTION.to_string()).unwrap();
-
-        let resolution_value = resolution_option.value.clone();
-        terminal_manager.terminal.draw(|frame| {
-            let frame_area: Area;
-            if resolution_option.display_name == "FULLSCREEN" {
-                info!("FULLSCREEN resolution selected. Using frame size: {:?}", frame.size());
-                frame_area = Area::from_rect(frame.size());
-            } else {
-                let res_value = resolution_value.unwrap();
-                info!("resolution selected. Using resolution: {:?}", res_value);
-                frame_area = Area::from_resolution(res_value)
-            }
-            ui.init(frame_area);
-        })?;
-
-        let ui_layout = &mut ui.ui_layout;
-        let ui_layout = ui_layout.as_mut().ok_or("Failed to get ui_layout, has it been initialised?").unwrap();
-        terminal_manager.terminal.draw(|frame| {
-            ui_layout.init_areas(frame.size());
-        })?;
-
+        info!("{} resolution selected.", resolution_option.display_name);
+        ui_wrapper.ui.set_resolution(resolution_option.value);
+        // Fullscreen follows the terminal from here on, as does the layout whenever it's resized
+        verify_display_size(&mut ui_wrapper.ui, &mut ui_wrapper.terminal_manager, self.input_handler.get_key_input());
         Ok(())
     }
 
 
     // TODO remove testing/dev characters
-    fn initialise_characters(&mut self) -> Result<(), ErrorWrapper> {
+    fn initialise_characters(&mut self, start_position: Position) -> Result<(), ErrorWrapper> {
         info!("Building player...");
         let player_pattern_result = CharacterPattern::new_player();
         if player_pattern_result.is_err() {
             return Err(player_pattern_result.unwrap_err())
         }
-        let player = CharacterBuilder::new(player_pattern_result.unwrap()).build(String::from("Player"));
-        info!("Building NPCs...");
-        let npc_pattern_result = CharacterPattern::goblin();
-        if npc_pattern_result.is_err() {
-            return Err(npc_pattern_result.unwrap_err())
-        }
-        let test_npc = CharacterBuilder::new(npc_pattern_result.unwrap()).build(String::from("Ruggo"));
+        let mut player = CharacterBuilder::new(player_pattern_result.unwrap())
+            .position(start_position)
+            .build(String::from("Player"));
+        // Magic items look different every run, and nothing's known about them yet
+        player.set_identification(Identification::new(self.levels.get_seed()));
 
-        let characters = Characters::new(Some(player), vec![test_npc]);
         // Uncomment to use character creation
         //let mut updated_character = self.show_character_creation(characters.get(0).unwrap().clone())?;
-        self.levels.get_level_mut().characters = characters;
-        let spawn_room = respawn_player(self, LevelChange::DOWN);
-        return if let Some(sr) = spawn_room {
-            respawn_npcs(self, sr.clone());
-            self.build_testing_inventory();
-            Ok(())
-        } else {
-            ErrorWrapper::internal_result(String::from("Player was not properly spawned/no player spawn room returned!"))
-        }
+        self.levels.get_level_mut().characters.set_player(player);
+        self.build_testing_inventory();
+        Ok(())
+    }
+
+    fn generate_overworld(&mut self) -> Overworld {
+        let map_area = build_rectangular_area(Position::zero(), OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y);
+        info!("Generating overworld using RNG seed: {}", self.levels.get_seed());
+        build_overworld_generator(&mut self.levels.rng, map_area, 3).generate()
     }
 
     async fn generate_map(&mut self) -> Result<Map, ErrorWrapper> {
@@ -184,6 +226,7 @@ impl <B : Backend + Send> GameEngine<B> {
 
         let progress_display = ProgressDisplay {
             terminal_manager: &mut self.ui_wrapper.terminal_manager,
+            key_input: self.input_handler.get_key_input(),
             frame_handler: map_framehandler
         };
         let mut level_generator = MapGeneration {
@@ -196,11 +239,13 @@ impl <B : Backend + Send> GameEngine<B> {
     }
 
     async fn initialise(&mut self) -> Result<(), ErrorWrapper> {
-        self.ui_wrapper.print_and_re_render(String::from("Generating a new level.."))?;
+        self.ui_wrapper.print_and_re_render(String::from("Generating the overworld.."))?;
 
-        let map = self.generate_map().await.unwrap();
-        self.levels.add_level(map);
-        return match self.initialise_characters() {
+        // The overworld is the only level of it's own set of levels, and isn't populated by a spawn table
+        let overworld = self.generate_overworld();
+        self.world = Some(build_world(self.levels.get_seed(), &overworld));
+        self.levels.add_level_directly(Level::new(Some(overworld.map), Characters::default()));
+        match self.initialise_characters(overworld.town_centre) {
             Err(e) => {
                 Err(e)
             },
@@ -211,33 +256,50 @@ impl <B : Backend + Send> GameEngine<B> {
         }
     }
 
+    // Recorded sessions remember the seed of each game, and replayed ones swap in the recorded seed
+    fn sync_session_seed(&mut self) {
+        let settings_seed = self.settings.get_rng_seed().unwrap_or(self.levels.get_seed());
+        let seed = self.input_handler.get_key_input().sync_seed(settings_seed.clone());
+        if seed != settings_seed {
+            info!("Using the session's recorded seed: {}", seed);
+            if let Some(setting) = self.settings.string_settings.iter_mut().find(|s| s.name == SETTING_RNG_SEED) {
+                setting.value = seed.clone();
+            }
+        }
+        self.levels = init_level_manager(seed.clone(), Seeder::from(seed).into_rng());
+    }
+
     fn add_or_update_additional_widgets(&mut self) {
         let additional_widgets = self.ui_wrapper.ui.get_additional_widgets();
         if additional_widgets.is_empty() {
-            let level_number = self.levels.get_current_level() as i32 + 1;
+            let level_number = self.get_depth() as i32;
             let level = self.levels.get_level_mut();
             let player = level.characters.get_player_mut().unwrap();
             let stat_line = CharacterStatLineWidget::new(
                 level_number,
                 player.get_health(),
+                player.get_max_health(),
                 player.get_details(),
                 player.get_inventory_mut().get_loot_value());
             self.ui_wrapper.ui.get_additional_widgets_mut().push(StandardWidgetType::StatLine(stat_line));
 
             let mut commands : Vec<UsageCommand> = vec![
-                UsageCommand::new('i', String::from("Inventory/Info") )
+                UsageCommand::new('i', String::from("Inventory/Info") ),
+                UsageCommand::new('g', String::from("Pick up") )
             ];
             let map_usage_line = UsageLine::new(commands);
             self.ui_wrapper.ui.get_additional_widgets_mut().push(StandardWidgetType::UsageLine(map_usage_line));
 
         } else {
+            let depth = self.get_depth() as i32;
             let widgets_mut = self.ui_wrapper.ui.get_additional_widgets_mut();
             match widgets_mut.get_mut(0) {
                 Some(StandardWidgetType::StatLine(s)) => {
-                    let level_number = self.levels.get_current_level() as i32 + 1;
+                    let level_number = depth;
                     let level = self.levels.get_level_mut();
                     let player = level.characters.get_player_mut().unwrap();
                     s.set_health(player.get_health());
+                    s.set_max_health(player.get_max_health());
                     s.set_level(level_number);
                     s.set_loot_score(player.get_inventory_mut().get_loot_value());
                 }
@@ -249,6 +311,7 @@ impl <B : Backend + Send> GameEngine<B> {
     pub(crate) async fn start_game(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper>{
         let mut generated = false;
         while !generated {
+            self.sync_session_seed();
             let init_result = self.initialise().await;
             match init_result {
                 Ok(()) => {
@@ -259,7 +322,7 @@ impl <B : Backend + Send> GameEngine<B> {
                 Err(e) => {
                     // Rebuild the engine to reset the seed and try again
                     log::error!("Initialisation failed with error {}. Trying another map seed...", e);
-                    let mut error_dialog = DialogView::new(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, String::from("Initialisation failed, trying another map seed..."));
+                    let mut error_dialog = DialogView::new(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, self.input_handler.get_key_input(), String::from("Initialisation failed, trying another map seed..."));
                     error_dialog.begin()?;
                     self.rebuild();
                 }
@@ -267,21 +330,7 @@ impl <B : Backend + Send> GameEngine<B> {
         }
 
         while self.game_running {
-            self.add_or_update_additional_widgets();
-            self.ui_wrapper.ui.show_console();
-
-            let level = self.levels.get_level_mut();
-
-            match self.ui_wrapper.draw_map_view(level) {
-                Err(e) => {
-                    log::error!("Error when attempting to draw map: {}", e);
-                    return Err(e.into());
-                }
-                _ => {
-                }
-            }
-
-            let result = game_loop(self).await?;
+            let result = self.play_turn().await?;
             if result.is_some() {
                 return Ok(result);
             }
@@ -290,46 +339,158 @@ impl <B : Backend + Send> GameEngine<B> {
         Ok(None)
     }
 
+    // Draws the map view and then plays out a single turn
+    pub(crate) async fn play_turn(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        self.set_music_context(MusicContext::Level(self.get_depth()));
+        self.add_or_update_additional_widgets();
+        self.ui_wrapper.ui.show_console();
+
+        let level = self.levels.get_level_mut();
+
+        if let Err(e) = self.ui_wrapper.draw_map_view(level, self.input_handler.get_key_input()) {
+            log::error!("Error when attempting to draw map: {}", e);
+            return Err(e);
+        }
+
+        game_loop(self).await
+    }
+
     fn build_testing_inventory(&mut self) {
         let player = self.levels.get_level_mut().characters.get_player_mut().unwrap();
         player.set_inventory(build_dev_player_inventory());
     }
 
-    async fn attempt_player_movement(&mut self, side: Side) -> PlayerMovementResult {
+    async fn attempt_player_movement(&mut self, direction: Direction) -> PlayerMovementResult {
         let levels = &mut self.levels;
         let level = levels.get_level_mut();
-        let updated_position = level.find_player_side_position(side).clone();
+        let player_position = level.characters.get_player().unwrap().get_global_position();
+        let updated_position = level.find_player_position_towards(direction);
         match updated_position {
             Some(pos) => {
                 let level_change;
                 if let Some(m) = &level.map {
-                    if m.is_traversable(pos) {
+                    if m.can_step_between(player_position, pos) {
                         let player = level.characters.get_player_mut().unwrap();
                         player.set_position(pos);
+                        let through_door = m.tiles.get_tile(pos).is_some_and(|t| t.tile_type == TileType::Door);
+                        if let Some(sinks) = &mut self.sound_sinks {
+                            sinks.play_effect(if through_door { SoundEffect::DoorOpen } else { SoundEffect::Footstep });
+                        }
                     }
 
                     if let Some(room) = m.rooms.iter()
                         .find(|r| r.get_inside_area().contains_position(pos)) {
                         // Future TODO move to a specific controller instead?
-                        level_change = self.ui_wrapper.check_room_entry_exits(level.clone(), room, pos);
+                        level_change = self.ui_wrapper.check_room_entry_exits(level.clone(), room, pos, self.input_handler.get_key_input());
                         let must_generate_map = levels.must_build_level(level_change.clone());
-                        return PlayerMovementResult { must_generate_map, level_change: Some(level_change) };
+                        return PlayerMovementResult { must_generate_map, level_change: Some(level_change), enter_dungeon: None };
+                    }
+
+                    let on_dungeon_entrance = m.tiles.get_tile(pos).is_some_and(|t| t.tile_type == TileType::DungeonEntrance);
+                    if let (true, Some(world)) = (on_dungeon_entrance, &self.world) {
+                        if let Some(index) = world.find_dungeon_at(pos) {
+                            let dungeon_name = world.get_dungeons()[index].get_name();
+                            let enter = self.ui_wrapper.yes_or_no(
+                                level.clone(),
+                                format!("You've found the entrance to {}. Would you like to go in?", dungeon_name),
+                                Some(String::from("You head down into the dungeon..")),
+                                self.input_handler.get_key_input());
+                            if let Ok(true) = enter {
+                                return PlayerMovementResult { must_generate_map: false, level_change: None, enter_dungeon: Some(index) };
+                            }
+                        }
                     }
                 }
 
             }
             _ => {}
         }
-        return PlayerMovementResult { must_generate_map: false, level_change: None };
+        PlayerMovementResult { must_generate_map: false, level_change: None, enter_dungeon: None }
     }
 
-    fn handle_game_over(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
-        let player_score = self.levels.get_level_mut().characters.get_player_mut().unwrap().get_inventory_mut().get_loot_value();
+    async fn enter_dungeon(&mut self, index: usize) -> Result<(), ErrorWrapper> {
+        let player = self.levels.leave_current_level();
+        if let Some(world) = self.world.as_mut() {
+            world.enter_dungeon(index, &mut self.levels);
+        }
+
+        if self.levels.is_empty() {
+            let map = self.generate_map().await?;
+            self.levels.add_level(map)?;
+        }
+        self.levels.enter_current_level(player);
+        respawn_player(self, LevelChange::DOWN);
+        self.update_deepest_level();
+        self.play_sound_effect(SoundEffect::LevelChange);
+        Ok(())
+    }
+
+    // How far down the player is, where the overworld doesn't count towards depth
+    fn get_depth(&self) -> usize {
+        if self.world.as_ref().is_none_or(|w| w.is_in_dungeon()) {
+            self.levels.get_current_level() + 1
+        } else {
+            0
+        }
+    }
+
+    fn update_deepest_level(&mut self) {
+        self.deepest_level = self.deepest_level.max(self.get_depth());
+    }
+
+    // Plays the sound for something that's just happened, if there's any sound at all
+    fn play_sound_effect(&mut self, effect: SoundEffect) {
+        if let Some(sinks) = &mut self.sound_sinks {
+            sinks.play_effect(effect);
+        }
+    }
+
+    // Changes the music to suit what's going on, if there's any sound at all
+    pub(crate) fn set_music_context(&mut self, context: MusicContext) {
+        if let Some(sinks) = &mut self.sound_sinks {
+            sinks.set_music_context(context);
+        }
+    }
+
+    // Adds the finished run to the persisted run history
+    pub(crate) fn record_run(&mut self, outcome: RunOutcome) -> Result<RunRecord, ErrorWrapper> {
+        // Dungeon levels have seeds of their own, so prefer the world's
+        let seed = self.world.as_ref().map(|w| w.get_seed()).unwrap_or(self.levels.get_seed());
+        let turns = self.levels.get_turn();
+        let deepest_level = self.deepest_level;
+        let player = self.levels.get_level_mut().characters.get_player_mut()
+            .ok_or(ErrorWrapper::new_internal(String::from("There's no player to record the run of!")))?;
+        let run = RunRecord::from_player(player, seed, deepest_level, turns, outcome);
+        record_run(&self.run_history_path, run.clone())?;
+        Ok(run)
+    }
+
+    // Returns the player to the overworld, outside the entrance of the dungeon they left
+    fn leave_dungeon(&mut self) {
+        let player = self.levels.leave_current_level();
+        let levels = &mut self.levels;
+        let entrance = self.world.as_mut().and_then(|world| world.leave_dungeon(levels));
+        self.levels.enter_current_level(player);
+        if let (Some(entrance), Some(player)) = (entrance, self.levels.get_level_mut().get_player_mut()) {
+            player.set_position(entrance);
+        }
+    }
+
+    // Shows the game over screen, after recording the run
+    fn handle_game_over(&mut self, outcome: RunOutcome) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        self.set_music_context(MusicContext::GameOver);
+        let run = self.record_run(outcome)?;
+        let headline = match &run.outcome {
+            RunOutcome::Escaped => String::from("You left the dungeon."),
+            RunOutcome::Quit => String::from("You gave up."),
+            RunOutcome::Died(cause) => format!("You died. You were killed by {}.", cause)
+        };
 
         let mut menu = build_game_over_menu(
-            format!("You left the dungeon.\nLoot Total: {}", player_score),
+            format!("{}\nLoot Total: {}\nScore: {}", headline, run.loot_value, run.get_score()),
             &mut self.ui_wrapper.ui,
-            &mut self.ui_wrapper.terminal_manager);
+            &mut self.ui_wrapper.terminal_manager,
+            self.input_handler.get_key_input());
         let result = menu.begin()?;
         if let Some(game_over_choice) = result.view_specific_result {
             return Ok(Some(game_over_choice));
@@ -337,8 +498,44 @@ impl <B : Backend + Send> GameEngine<B> {
         return Ok(None)
     }
 
-    pub(crate) async fn handle_player_movement(&mut self, side: Side) -> Result<Option<GameOverChoice>, ErrorWrapper> {
-        let movement_result : PlayerMovementResult = self.attempt_player_movement(side).await;
+    /*
+     * Ends the game if the player has died, whatever the source of the damage was
+     * This should be checked after anything that can hurt the player
+     */
+    pub(crate) fn check_player_death(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        let cause = self.levels.get_level_mut().characters.get_player().and_then(|p| p.get_cause_of_death());
+        if let Some(cause) = cause {
+            info!("Player killed by: {}", cause);
+            return self.handle_game_over(RunOutcome::Died(cause));
+        }
+        Ok(None)
+    }
+
+    pub(crate) async fn handle_player_movement(&mut self, direction: Direction) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        // Moving into an NPC attacks them
+        let level = self.levels.get_level_mut();
+        let target = level.find_player_position_towards(direction);
+        if let Some(index) = level.characters.get_npcs().iter().position(|npc| Some(npc.get_global_position()) == target) {
+            return self.begin_combat(index);
+        }
+
+        let start_position = self.levels.get_level_mut().get_player_mut().map(|p| p.get_global_position());
+        let movement_result : PlayerMovementResult = self.attempt_player_movement(direction).await;
+
+        // Any travel was plotted on the level being left, so it can't carry on past a level change
+        let changing_level = matches!(movement_result.level_change, Some(LevelChange::UP | LevelChange::DOWN));
+        if movement_result.enter_dungeon.is_some() || changing_level {
+            self.travel = None;
+        }
+        let moved_position = self.levels.get_level_mut().get_player_mut().map(|p| p.get_global_position());
+        if moved_position != start_position || movement_result.enter_dungeon.is_some() || changing_level {
+            self.turn_taken = true;
+        }
+
+        if let Some(index) = movement_result.enter_dungeon {
+            self.enter_dungeon(index).await?;
+            return Ok(None);
+        }
 
         // If the player move results in an up/down level movement, handle this
         let level_change_option = movement_result.level_change.clone();
@@ -349,53 +546,135 @@ impl <B : Backend + Send> GameEngine<B> {
             }
             let levels = &mut self.levels;
 
-            let change_level = levels.change_level(level_change.clone(), map);
-            match change_level {
-                Ok(result) => {
-                    match result {
-                        LevelChangeResult::LevelChanged => {
-                            respawn_player(self, level_change);
-                        },
-                        LevelChangeResult::OutOfDungeon => {
-                           return self.handle_game_over();
+            match levels.change_level(level_change.clone(), map)? {
+                LevelChangeResult::LevelChanged => {
+                    respawn_player(self, level_change);
+                    self.explore();
+                    self.update_deepest_level();
+                    self.play_sound_effect(SoundEffect::LevelChange);
+                },
+                LevelChangeResult::OutOfDungeon => {
+                    // Without an overworld to return to, leaving the dungeon ends the game
+                    if self.world.as_ref().is_some_and(|w| w.is_in_dungeon()) {
+                        let level = self.levels.get_level_mut().clone();
+                        let end_run = self.ui_wrapper.yes_or_no(level, String::from("You've made it out of the dungeon alive. Would you like to end your run here, keeping your loot?"), None, self.input_handler.get_key_input())?;
+                        if end_run {
+                            return self.handle_game_over(RunOutcome::Escaped);
                         }
+                        self.leave_dungeon();
+                        self.play_sound_effect(SoundEffect::LevelChange);
+                    } else {
+                        return self.handle_game_over(RunOutcome::Escaped);
                     }
                 }
-                _ => {}
             }
+            return Ok(None)
+        }
+
+        let end_position = self.levels.get_level_mut().get_player_mut().map(|p| p.get_global_position());
+        if end_position != start_position {
+            self.explore();
+            self.auto_pick_up();
         }
         return Ok(None)
     }
 
-    pub(crate) fn begin_combat(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper>  {
+    // Picks up anything the player has chosen to pick up automatically when walking over it
+    fn auto_pick_up(&mut self) {
+        let categories = self.settings.get_auto_pickup_categories();
+        if let Some(message) = auto_pick_up(self.levels.get_level_mut(), &categories, self.sound_sinks.as_mut()) {
+            self.ui_wrapper.ui.set_console_message(message);
+        }
+    }
+
+    /*
+     * Fights the NPC at the given index of the current level until one side dies or the player flees
+     * The outcome is then applied to the level, leaving any dead to be found at the end of the turn
+     */
+    pub(crate) fn begin_combat(&mut self, npc_index: usize) -> Result<Option<GameOverChoice>, ErrorWrapper>  {
+        // Fighting uses up the turn, even if the player flees straight away
+        self.turn_taken = true;
+        self.set_music_context(MusicContext::Combat);
         let level = self.levels.get_level_mut();
 
         let characters = &level.characters;
         let player = characters.get_player().unwrap().clone();
         let mut npcs = Vec::new();
-        npcs.push(characters.get_npcs().first().unwrap().clone());
+        let npc = characters.get_npcs().get(npc_index).ok_or(ErrorWrapper::new_internal(String::from("There's no one here to fight!")))?;
+        npcs.push(npc.clone());
         let battle_characters = Characters::new(Some(player), npcs);
         let battle = Battle { characters: battle_characters , in_progress: true };
 
         let view_battle = battle.clone();
-        let mut combat = Combat { battle };
+        let mut combat = Combat { battle, sound_sinks: self.sound_sinks.as_mut() };
 
-        let mut combat_view = CombatView::new(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, self.levels.get_level_mut().clone(), view_battle);
+        let mut combat_view = CombatView::new(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, self.input_handler.get_key_input(), self.levels.get_level_mut().clone(), view_battle);
         combat_view.set_callback(Box::new(|data| {
             combat.handle_callback(data)
         }));
-        combat_view.begin()?;
+        let result = combat_view.begin();
+        drop(combat_view);
+
+        // Whatever happened before leaving the fight still counts
+        let battle = combat.battle;
+        self.apply_battle(npc_index, battle);
+        result?;
         Ok(None)
     }
 
-    pub(crate) async fn player_turn(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
-        let key = get_input_key()?;
+    // Writes the characters' state after a battle back to the current level
+    fn apply_battle(&mut self, npc_index: usize, mut battle: Battle) {
+        let characters = &mut self.levels.get_level_mut().characters;
+        if let Some(player) = battle.characters.get_player() {
+            characters.set_player(player.clone());
+        }
+        if let (Some(npc), Some(level_npc)) = (battle.characters.get_npcs_mut().pop(), characters.get_npcs_mut().get_mut(npc_index)) {
+            if npc.is_dead() {
+                self.ui_wrapper.ui.set_console_message(format!("You killed {}!", npc.get_name()));
+            }
+            *level_npc = npc;
+        }
+    }
+
+    pub(crate) async fn player_turn(&mut self) -> Result<PlayerTurn, ErrorWrapper> {
+        self.turn_taken = false;
+        let game_over_choice = self.take_player_action().await?;
+        Ok(PlayerTurn { game_over_choice, turn_taken: self.turn_taken })
+    }
+
+    // Carries on any travel, or else does whatever the player asks for next
+    async fn take_player_action(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        if let Some(step) = self.next_travel_step()? {
+            return self.travel(step).await;
+        }
+
+        let key = loop {
+            match self.input_handler.get_key_input().get_input_event()? {
+                InputEvent::Key(key) => break key,
+                InputEvent::Click(position) => {
+                    if let Some(target) = self.find_clicked_position(position) {
+                        if let Some(step) = self.start_travel(TravelGoal::Position(target))? {
+                            return self.travel(step).await;
+                        }
+                    }
+                },
+                InputEvent::Resize(_, _) => {
+                    self.ui_wrapper.draw_map_view(self.levels.get_level_mut(), self.input_handler.get_key_input())?;
+                },
+                InputEvent::Scroll(_) => {}
+            }
+        };
         let input_handler = &mut self.input_handler;
         
         let action = input_handler.handle_input(key).await;
         
         if let Some(a) = action {
+            // Picking up, taking, dropping or equipping anything uses up the turn, just looking doesn't
+            let belongings = self.get_player_belongings();
             let goc = self.handle_action(a, Some(key)).await?;
+            if self.get_player_belongings() != belongings {
+                self.turn_taken = true;
+            }
             if let Some(goc) = goc {
                 return Ok(Some(goc));
             }
@@ -404,6 +683,175 @@ impl <B : Backend + Send> GameEngine<B> {
         return Ok(None);
     }
     
+    fn get_player_position(&self) -> Option<Position> {
+        self.levels.get_level().characters.get_player().map(|p| p.get_global_position())
+    }
+
+    // Everything the player is carrying and wearing
+    fn get_player_belongings(&mut self) -> Option<(Vec<Item>, HashMap<EquipmentSlot, Item>)> {
+        self.levels.get_level_mut().get_player_mut().map(|p| {
+            let carried = p.get_inventory_mut().to_cloned_nested_item_list();
+            (carried, p.get_equipment().get_slots().clone())
+        })
+    }
+
+    // Where on the map was clicked, if anywhere on it was
+    fn find_clicked_position(&self, screen_position: Position) -> Option<Position> {
+        build_map_view_areas(&self.ui_wrapper.ui, self.levels.get_level()).and_then(|areas| areas.screen_to_global(screen_position))
+    }
+
+    // Plots a path for the player to walk along from where they're standing, empty if there's no way there
+    fn plot_path(&self, target: Position) -> VecDeque<Position> {
+        let level = self.levels.get_level();
+        match (self.get_player_position(), level.map.as_ref()) {
+            (Some(start), Some(map)) => {
+                let mut path = VecDeque::from(Pathfinding::build_walking(start, self.settings.is_diagonal_movement()).a_star_search(map, target));
+                // The path begins where the player's already standing
+                path.pop_front();
+                path
+            },
+            _ => VecDeque::new()
+        }
+    }
+
+    // Sets off towards the given goal, returning the first step to take if there's any way there
+    fn start_travel(&mut self, goal: TravelGoal) -> Result<Option<(Direction, Position)>, ErrorWrapper> {
+        let level = self.levels.get_level_mut();
+        level.explore();
+        // Travel stops for NPCs coming into view, so setting off with one already there could walk right into it
+        if level.is_npc_in_view() {
+            self.ui_wrapper.ui.set_console_message(String::from("Not with someone nearby!"));
+            return Ok(None);
+        }
+        let health = level.characters.get_player().map(|p| p.get_health()).unwrap_or(0);
+        let mut travel = Travel::new(goal.clone(), health, level.is_npc_in_view());
+        if let TravelGoal::Position(target) = goal {
+            let path = self.plot_path(target);
+            if path.is_empty() {
+                self.ui_wrapper.ui.set_console_message(String::from("You can't find a way there."));
+                return Ok(None);
+            }
+            travel.set_path(path);
+        }
+        self.travel = Some(travel);
+        self.next_travel_step()
+    }
+
+    // Stops travelling, letting the player know why
+    fn stop_travel(&mut self, message: String) -> Result<(), ErrorWrapper> {
+        self.travel = None;
+        self.ui_wrapper.ui.set_console_message(message);
+        self.ui_wrapper.draw_map_view(self.levels.get_level_mut(), self.input_handler.get_key_input())
+    }
+
+    // The direction to move in for the next step of travel, and where that step ends up
+    fn next_travel_step(&mut self) -> Result<Option<(Direction, Position)>, ErrorWrapper> {
+        let level = self.levels.get_level();
+        let (travel, start) = match (self.travel.as_mut(), level.characters.get_player()) {
+            (Some(travel), Some(player)) => (travel, player.get_global_position()),
+            _ => return Ok(None)
+        };
+
+        let interruption = if self.input_handler.get_key_input().poll_interrupt()? {
+            Some(TravelInterruption::KeyPressed)
+        } else {
+            travel.check_interruption(level.characters.get_player().map(|p| p.get_health()).unwrap_or(0), level.is_npc_in_view())
+        };
+        if let Some(interruption) = interruption {
+            self.stop_travel(interruption.get_message())?;
+            return Ok(None);
+        }
+
+        match travel.get_goal().clone() {
+            // Exploring heads for wherever is nearest each step, as each step can reveal more
+            TravelGoal::Explore => {
+                match level.find_nearest_unexplored(self.settings.is_diagonal_movement()) {
+                    Some(target) => {
+                        let path = self.plot_path(target);
+                        if let Some(travel) = self.travel.as_mut() {
+                            travel.set_path(path);
+                        }
+                    },
+                    None => {
+                        self.stop_travel(String::from("There's nowhere left to explore."))?;
+                        return Ok(None);
+                    }
+                }
+            },
+            // Running always takes the first step, then keeps going until there's reason to stop
+            TravelGoal::Run(direction) => {
+                if travel.get_steps_taken() > 0 && level.is_run_interrupted(direction) {
+                    self.travel = None;
+                    return Ok(None);
+                }
+                travel.set_path(start.get_adjacent_position(direction).into_iter().collect());
+            },
+            TravelGoal::Position(_) => {}
+        }
+
+        let step = match self.travel.as_mut().and_then(|t| t.next_step()) {
+            Some(step) => step,
+            None => {
+                // Arrived
+                self.travel = None;
+                return Ok(None);
+            }
+        };
+        match Direction::from_offset(step.x as i32 - start.x as i32, step.y as i32 - start.y as i32) {
+            Some(direction) => Ok(Some((direction, step))),
+            None => {
+                self.travel = None;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn travel(&mut self, (direction, step): (Direction, Position)) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        // Moving into an NPC attacks them, which travel should never do
+        if self.levels.get_level().characters.get_npcs().iter().any(|npc| npc.get_global_position() == step) {
+            self.stop_travel(TravelInterruption::Blocked.get_message())?;
+            return Ok(None);
+        }
+        let result = self.handle_player_movement(direction).await;
+        // Anything getting in the way stops the travel
+        if self.get_player_position() != Some(step) {
+            self.travel = None;
+        }
+        result
+    }
+
+    // Marks whatever the player can now see as explored, letting any travel know about items it's not heading for
+    fn explore(&mut self) {
+        let found_items = self.levels.get_level_mut().explore();
+        if let Some(travel) = self.travel.as_mut() {
+            let found_item = found_items.iter().any(|p| !travel.is_heading_for(*p));
+            travel.set_found_item(found_item);
+        }
+    }
+
+    // Sets off towards the given place, or lets the player know there's nowhere to go
+    async fn travel_to(&mut self, target: Option<Position>, missing_message: &str) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        match target {
+            Some(target) => {
+                if let Some(step) = self.start_travel(TravelGoal::Position(target))? {
+                    return self.travel(step).await;
+                }
+            },
+            None => self.ui_wrapper.ui.set_console_message(String::from(missing_message))
+        }
+        Ok(None)
+    }
+
+    // Where the stairs in the given direction are on the current level, if it has any
+    fn find_stairs(&self, level_change: LevelChange) -> Option<Position> {
+        let map = self.levels.get_level().map.as_ref()?;
+        map.rooms.iter().find_map(|r| match level_change {
+            LevelChange::UP => r.get_entry(),
+            LevelChange::DOWN => r.get_exit(),
+            LevelChange::NONE => None
+        })
+    }
+
     async fn handle_action(&mut self, action: Action, input: Option<Key>) -> Result<Option<GameOverChoice>, ErrorWrapper> {
         let level = self.levels.get_level_mut();
         let ui_wrapper = &mut self.ui_wrapper;
@@ -417,13 +865,21 @@ impl <B : Backend + Send> GameEngine<B> {
                 }
             },
             Action::DevBeginCombat => {
-                Ok(self.begin_combat()?)
+                Ok(self.begin_combat(0)?)
+            },
+            Action::DevExportLevel => {
+                let export_name = format!("level-{}-{}", self.levels.get_seed(), self.levels.get_current_level());
+                let paths = write_level_export(self.levels.get_level_mut(), export_name)?;
+                let mut dialog = DialogView::new(&mut self.ui_wrapper.ui, &mut self.ui_wrapper.terminal_manager, self.input_handler.get_key_input(), format!("Level exported to: {}", paths.join(", ")));
+                dialog.begin()?;
+                Ok(None)
             },
             Action::ShowInventory => {
                 let mut command = InventoryCommand {
                     level,
                     ui: &mut self.ui_wrapper.ui,
-                    terminal_manager: &mut self.ui_wrapper.terminal_manager
+                    terminal_manager: &mut self.ui_wrapper.terminal_manager,
+                    key_input: self.input_handler.get_key_input()
                 };
                 command.start()?;
                 
@@ -441,6 +897,7 @@ impl <B : Backend + Send> GameEngine<B> {
                     level,
                     ui: &mut self.ui_wrapper.ui,
                     terminal_manager: &mut self.ui_wrapper.terminal_manager,
+                    key_input: self.input_handler.get_key_input(),
                     bindings: key_bindings.clone()
                 };
                 command.start()?;
@@ -458,8 +915,9 @@ impl <B : Backend + Send> GameEngine<B> {
                     level,
                     ui: &mut self.ui_wrapper.ui,
                     terminal_manager: &mut self.ui_wrapper.terminal_manager,
-                    input_resolver: Box::new(IoKeyInputResolver {}),
-                    key_bindings: key_bindings.clone()
+                    key_input: self.input_handler.get_key_input(),
+                    key_bindings: key_bindings.clone(),
+                    sound_sinks: self.sound_sinks.as_mut()
                 };
                 command.start()?;
 
@@ -472,8 +930,50 @@ impl <B : Backend + Send> GameEngine<B> {
                 
                 Ok(None)
             },
-            Action::MovePlayer(side) => {
-                if let Some(game_over_choice) = self.handle_player_movement(side.clone()).await? {
+            Action::PickUp => {
+                let mut command = PickUpCommand {
+                    level,
+                    ui: &mut self.ui_wrapper.ui,
+                    terminal_manager: &mut self.ui_wrapper.terminal_manager,
+                    key_input: self.input_handler.get_key_input(),
+                    sound_sinks: self.sound_sinks.as_mut()
+                };
+                command.start()?;
+                command.handle_input(None)?;
+                Ok(None)
+            },
+            Action::AutoExplore => {
+                if let Some(step) = self.start_travel(TravelGoal::Explore)? {
+                    return self.travel(step).await;
+                }
+                Ok(None)
+            },
+            Action::TravelToExit => {
+                let exit = self.find_stairs(LevelChange::DOWN);
+                self.travel_to(exit, "There's no way down on this level.").await
+            },
+            Action::TravelToEntry => {
+                let entry = self.find_stairs(LevelChange::UP);
+                self.travel_to(entry, "There's no way up on this level.").await
+            },
+            Action::TravelToItem => {
+                let item = self.levels.get_level().get_last_seen_item();
+                self.travel_to(item, "You've not seen anything worth going back for.").await
+            },
+            Action::RunPlayer(direction) => {
+                if direction.is_diagonal() && !self.settings.is_diagonal_movement() {
+                    return Ok(None);
+                }
+                if let Some(step) = self.start_travel(TravelGoal::Run(direction))? {
+                    return self.travel(step).await;
+                }
+                Ok(None)
+            },
+            Action::MovePlayer(direction) => {
+                if direction.is_diagonal() && !self.settings.is_diagonal_movement() {
+                    return Ok(None);
+                }
+                if let Some(game_over_choice) = self.handle_player_movement(direction).await? {
                     return Ok(Some(game_over_choice));
                 } else {
                     return Ok(None);
@@ -487,34 +987,41 @@ impl <B : Backend + Send> GameEngine<B> {
 
 }
 
-pub fn build_game_engine<'a, B: Backend>(terminal_manager : TerminalManager<B>) -> Result<GameEngine<B>, ErrorWrapper> {
+pub fn build_game_engine<B: Backend>(terminal_manager : TerminalManager<B>, key_input: Box<dyn KeyInputResolver>) -> Result<GameEngine<B>, ErrorWrapper> {
     let ui = build_ui();
     let settings = build_settings();
     let key_bindings = settings.key_bindings.clone();
     let rng_seed = settings.get_rng_seed().ok_or(Error::new(ErrorKind::NotFound, "Failed to retrieve the RNG seed value!"))?;
     let seed_copy = rng_seed.clone();
     let rng = Seeder::from(rng_seed).into_rng();
-    Ok(GameEngine { levels: init_level_manager(seed_copy, rng), settings, ui_wrapper : UIWrapper { ui, terminal_manager }, sound_sinks: None, game_running: false, input_handler: InputHandler::new(key_bindings) })
+    Ok(GameEngine { levels: init_level_manager(seed_copy, rng), settings, ui_wrapper : UIWrapper { ui, terminal_manager }, world: None, deepest_level: 0, run_history_path: String::from(RUN_HISTORY_FILE), sound_sinks: None, game_running: false, input_handler: InputHandler::new(key_bindings, key_input), travel: None, turn_taken: false })
 }
 
-pub fn build_test_game_engine<'a, B: Backend>(levels: Levels, terminal_manager : TerminalManager<B>) -> Result<GameEngine<B>, ErrorWrapper> {
+pub fn build_test_game_engine<B: Backend>(levels: Levels, terminal_manager : TerminalManager<B>) -> Result<GameEngine<B>, ErrorWrapper> {
     let ui = build_ui();
     let settings = build_settings();
     let key_bindings = settings.key_bindings.clone();
     Ok(
         GameEngine { 
             levels,
+            world: None,
+            deepest_level: 0,
+            run_history_path: String::from(RUN_HISTORY_FILE),
             settings, 
             ui_wrapper: UIWrapper { ui, terminal_manager },
             sound_sinks: None, 
             game_running: false,
-            input_handler: InputHandler::new(key_bindings)
+            // The TestBackend has no terminal to read from, so any input is scripted
+            input_handler: InputHandler::new(key_bindings, Box::new(IoKeyInputResolver::new(Box::new(ScriptedEventSource::new(Vec::new()))))),
+            travel: None,
+            turn_taken: false
         })
 }
 
 struct PlayerMovementResult {
     must_generate_map: bool,
-    level_change: Option<LevelChange>
+    level_change: Option<LevelChange>,
+    enter_dungeon: Option<usize>
 }
 
 
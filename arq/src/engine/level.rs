@@ -1,30 +1,73 @@
-use std::io;
+use std::collections::{HashSet, VecDeque};
 
+use rand::Rng;
 use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+use uuid::Uuid;
 
 use crate::character::characters::Characters;
 use crate::character::Character;
 
-use crate::map::map_generator::{build_generator, MapGenerator};
-use crate::map::position::{build_rectangular_area, Position, Side};
+use crate::engine::pathfinding::Pathfinding;
+use crate::engine::spawn_table::{build_spawn_table, SpawnTable};
+use crate::error::errors::ErrorWrapper;
+use crate::map::objects::identification::Identification;
+use crate::map::map_generator::{build_floor_container, build_generator, MapGenerator};
+use crate::map::map_loader::LoadedMap;
+use crate::map::position::{build_rectangular_area, Direction, Position, Side};
+use crate::map::room::Room;
 use crate::map::Map;
 use crate::ui::bindings::look_bindings::{map_look_input_to_side, LookInput};
 
 const MAP_SIZE_X: u16 = 80;
 const MAP_SIZE_Y: u16 = 30;
 
+// How long a dead NPC's body stays around before rotting away (dropping anything it carried)
+const CORPSE_DECAY_TURNS: u64 = 100;
+// Caps how far NPCs wander while the player is away, so returning after a long absence stays cheap
+const MAX_CATCH_UP_STEPS: u64 = 50;
+const SPAWN_POSITION_ATTEMPTS: usize = 20;
+// How close (in steps) the player needs to be before NPCs give chase
+const CHASE_RANGE: u16 = 8;
+
+/*
+ * The remains of an NPC, held as a container on the Floor at the given position
+ */
+#[derive(Debug, Clone)]
+pub struct Corpse {
+    pub position: Position,
+    pub container_id: Uuid,
+    pub decays_at_turn: u64
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct Level {
     pub map : Option<Map>,
-    pub characters : Characters
+    pub characters : Characters,
+    pub spawn_table: SpawnTable,
+    pub corpses: Vec<Corpse>,
+    depth: usize,
+    // The turn the player last left this level, used to catch it up when they return
+    last_visited_turn: u64,
+    // Everywhere the player has seen on this level
+    explored: HashSet<Position>,
+    // Where the player last saw anything lying around
+    last_seen_item: Option<Position>
 }
 
 pub struct Levels {
     seed: String,
     pub rng : Pcg64,
+    /*
+     * For simulating levels as time passes i.e NPCs wandering while the player is away
+     * Kept apart from the map generation RNG so that how long is spent on a level never changes the levels generated after it
+     */
+    simulation_rng: Pcg64,
     // Implied to always reflect updates to levels
     _current_level: usize,
-    levels : Vec<Level>
+    levels : Vec<Level>,
+    // Total turns taken by the player across all levels
+    turn: u64
 }
 
 #[derive(Clone)]
@@ -35,7 +78,8 @@ pub enum LevelChange {
 }
 
 pub fn init_level_manager(seed: String, rng : Pcg64) -> Levels {
-    Levels { seed, rng, levels: vec![], _current_level: 0}
+    let simulation_rng = Seeder::from(format!("{}-simulation", seed)).into_rng();
+    Levels { seed, rng, simulation_rng, levels: vec![], _current_level: 0, turn: 0 }
 }
 
 pub enum LevelChangeResult {
@@ -53,6 +97,10 @@ impl Levels {
         self.levels.push(level);
     }
 
+    pub(crate) fn get_level(&self) -> &Level {
+        self.levels.get(self._current_level).unwrap()
+    }
+
     pub(crate) fn get_level_mut(&mut self) -> &mut Level {
         return self.levels.get_mut(self._current_level).unwrap();
     }
@@ -63,23 +111,101 @@ impl Levels {
 
     pub fn build_map_generator(&mut self) -> MapGenerator {
         let map_area = build_rectangular_area(Position { x: 0, y: 0 }, MAP_SIZE_X, MAP_SIZE_Y);
+        // The level being generated will be added after the existing ones
+        let depth = self.levels.len();
         let rng = &mut self.rng;
-        build_generator(rng, map_area)
+        build_generator(rng, map_area, depth)
+    }
+
+    /*
+     * Adds a newly generated level, populating it with NPCs from the spawn table for it's depth
+     * The player (if any) is moved over from the current level
+     */
+    pub(crate) fn add_level(&mut self, map: Map) -> Result<(), ErrorWrapper> {
+        self.add_loaded_level(LoadedMap { map, npc_spawns: Vec::new(), player_spawn: None })
     }
 
-    pub(crate) fn add_level(&mut self, map: Map) {
-        let new_level;
-        let map = Some(map);
+    /*
+     * Adds a level from a loaded map (see map_loader::load_map_file)
+     * NPCs from the spawn table for it's depth are placed on the map's NPC spawns, or at random if it has none
+     * The player (if any) is moved over from the current level, onto the map's player spawn if it has one
+     */
+    pub(crate) fn add_loaded_level(&mut self, loaded: LoadedMap) -> Result<(), ErrorWrapper> {
         let mut player = None;
         if !self.levels.is_empty() {
             // Move the player to the next level
             player = Some(self.get_level_mut().characters.remove_player());
         }
-        new_level = Level {
-            map,
-            characters: Characters::new(player, Vec::new() )
+        if let (Some(player), Some(position)) = (player.as_mut(), loaded.player_spawn) {
+            player.set_position(position);
+        }
+        let depth = self.levels.len();
+        let mut new_level = Level {
+            map: Some(loaded.map),
+            characters: Characters::new(player, Vec::new()),
+            spawn_table: build_spawn_table(depth),
+            depth,
+            last_visited_turn: self.turn,
+            ..Default::default()
         };
+        if loaded.npc_spawns.is_empty() {
+            new_level.populate(&mut self.rng)?;
+        } else {
+            for position in loaded.npc_spawns {
+                if let Some(mut npc) = new_level.spawn_table.spawn_npc(&mut self.rng, depth)? {
+                    npc.set_position(position);
+                    new_level.characters.get_npcs_mut().push(npc);
+                }
+            }
+        }
         self.levels.push(new_level);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    pub fn get_turn(&self) -> u64 {
+        self.turn
+    }
+
+    // Keeps the turn count in line with another set of levels, so time passes everywhere at once
+    pub(crate) fn sync_turn(&mut self, turn: u64) {
+        self.turn = self.turn.max(turn);
+    }
+
+    // Takes the player out of the current level, remembering when they left it
+    pub(crate) fn leave_current_level(&mut self) -> Character {
+        let turn = self.turn;
+        let level = self.get_level_mut();
+        level.last_visited_turn = turn;
+        level.characters.remove_player()
+    }
+
+    // Puts the player (back) into the current level, catching it up for the time they were away
+    pub(crate) fn enter_current_level(&mut self, player: Character) {
+        let turn = self.turn;
+        let level = self.levels.get_mut(self._current_level).unwrap();
+        level.catch_up(turn, &mut self.simulation_rng);
+        level.characters.set_player(player);
+    }
+
+    // Advances the turn counter, letting the current level's dead lie and rot
+    pub fn end_turn(&mut self) {
+        self.turn += 1;
+        let turn = self.turn;
+        if let Some(level) = self.levels.get_mut(self._current_level) {
+            level.bury_dead_npcs(turn);
+            level.decay_corpses(turn);
+        }
+    }
+
+    // Moves the player from the current level to the given one, catching it up for the time the player was away
+    fn move_player_to_level(&mut self, level_index: usize) {
+        let player = self.leave_current_level();
+        self._current_level = level_index;
+        self.enter_current_level(player);
     }
 
     pub fn must_build_level(&self, level_change: LevelChange) -> bool {
@@ -102,13 +228,11 @@ impl Levels {
         }
     }
 
-    pub(crate) fn change_level(&mut self, level_change: LevelChange, new_map: Option<Map>) -> Result<LevelChangeResult, io::Error>  {
+    pub(crate) fn change_level(&mut self, level_change: LevelChange, new_map: Option<Map>) -> Result<LevelChangeResult, ErrorWrapper>  {
         match level_change {
             LevelChange::UP => {
                 if self._current_level > 0 {
-                    let player = self.get_level_mut().characters.remove_player();
-                    self._current_level -= 1;
-                    self.get_level_mut().characters.set_player(player);
+                    self.move_player_to_level(self._current_level - 1);
                     return Ok(LevelChangeResult::LevelChanged);
                 } else {
                     return Ok(LevelChangeResult::OutOfDungeon);
@@ -116,12 +240,11 @@ impl Levels {
             },
             LevelChange::DOWN => {
                 if self._current_level < self.levels.len() - 1 {
-                    let player = self.get_level_mut().characters.remove_player();
-                    self._current_level += 1;
-                    self.get_level_mut().characters.set_player(player);
+                    self.move_player_to_level(self._current_level + 1);
                 } else {
                     // TODO find a nicer way than assuming the provided new map
-                    self.add_level(new_map.unwrap());
+                    self.get_level_mut().last_visited_turn = self.turn;
+                    self.add_level(new_map.unwrap())?;
                     self._current_level += 1;
                 }
                 return Ok(LevelChangeResult::LevelChanged);
@@ -134,47 +257,648 @@ impl Levels {
 }
 
 impl Level {
+    // Builds a level without a spawn table, i.e one that will never spawn NPCs itself
+    pub fn new(map: Option<Map>, characters: Characters) -> Level {
+        Level { map, characters, ..Default::default() }
+    }
+
     pub(crate) fn find_adjacent_player_position(&mut self, side: Option<Side>) -> Option<Position> {
         return if let Some(s) = side {
-            match s {
-                Side::TOP | Side::BOTTOM | Side::LEFT | Side::RIGHT => {
-                    self.find_player_side_position(s)
-                }
-            }
+            self.find_player_position_towards(Direction::from(s))
         } else {
             Some(self.characters.get_player_mut().unwrap().get_global_position().clone())
         }
     }
 
-    pub fn find_player_side_position(&mut self, side: Side) -> Option<Position> {
-        let position = self.characters.get_player_mut().unwrap().get_global_position().clone();
-        let mut side_position = None;
-        match side {
-            Side::TOP => {
-                if position.y > 0 {
-                    side_position = Some(Position { x: position.x, y: position.y - 1 });
-                }
-            },
-            Side::BOTTOM => {
-                side_position = Some(Position { x: position.x, y: position.y + 1 });
-            },
-            Side::LEFT => {
-                if position.x > 0 {
-                    side_position = Some(Position { x: position.x - 1, y: position.y });
-                }
-            },
-            Side::RIGHT => {
-                side_position = Some(Position { x: position.x + 1, y: position.y });
-            }
-        }
-        side_position
+    pub fn find_player_position_towards(&mut self, direction: Direction) -> Option<Position> {
+        let position = self.characters.get_player_mut().unwrap().get_global_position();
+        position.get_adjacent_position(direction)
     }
 
     pub fn get_player_mut(&mut self) -> Option<&mut Character> {
         self.characters.get_player_mut()
     }
 
+    // What the player knows of magic items, to name them by
+    pub fn get_player_identification(&self) -> Identification {
+        self.characters.get_player().map_or_else(|| Identification::new(String::new()), |p| p.get_identification().clone())
+    }
+
     pub fn get_map_mut(&mut self) -> Option<&mut Map> {
         self.map.as_mut()
     }
-}
\ No newline at end of file
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /*
+     * Places the player on the stairs matching the direction they arrived from
+     * i.e coming up puts them on the exit (stairs down), going down puts them on the entry (stairs up)
+     * Returns the room the player has been placed in
+     */
+    pub fn place_player_at_stairs(&mut self, change: LevelChange) -> Option<Room> {
+        let map = self.map.as_ref()?;
+        let stairs_room = match change {
+            LevelChange::UP => map.rooms.iter().find(|room| room.get_exit().is_some()),
+            LevelChange::DOWN => map.rooms.iter().find(|room| room.get_entry().is_some()),
+            LevelChange::NONE => None
+        }?.clone();
+
+        let stairs = match change {
+            LevelChange::UP => stairs_room.get_exit(),
+            _ => stairs_room.get_entry()
+        }?;
+        self.characters.get_player_mut()?.set_position(stairs);
+        Some(stairs_room)
+    }
+
+    /*
+     * What the player can see from where they're standing
+     * This is the whole of any room they're in (or the doorway of), otherwise only what's right beside them
+     */
+    pub fn get_visible_positions(&self) -> Vec<Position> {
+        let position = match self.characters.get_player() {
+            Some(p) => p.get_global_position(),
+            None => return Vec::new()
+        };
+        let map = match self.map.as_ref() {
+            Some(m) => m,
+            None => return Vec::new()
+        };
+
+        if let Some(room) = map.rooms.iter().find(|r| r.get_area().contains_position(position)) {
+            return room.get_area().get_positions();
+        }
+        let mut visible = vec![position];
+        visible.extend(position.get_neighbors());
+        visible.extend(position.get_diagonal_neighbors());
+        visible.retain(|p| map.area.contains_position(*p));
+        visible
+    }
+
+    fn has_items_at(&self, position: Position) -> bool {
+        self.map.as_ref().and_then(|m| m.get_container(position)).is_some_and(|c| !c.get_contents().is_empty())
+    }
+
+    /*
+     * Marks everything the player can see as explored, keeping track of any items seen along the way
+     * Returns the positions of any items that hadn't been seen before
+     */
+    pub fn explore(&mut self) -> Vec<Position> {
+        let mut found_items = Vec::new();
+        for position in self.get_visible_positions() {
+            if self.has_items_at(position) {
+                if !self.explored.contains(&position) {
+                    found_items.push(position);
+                }
+                self.last_seen_item = Some(position);
+            } else if self.last_seen_item == Some(position) {
+                // Someone's taken it
+                self.last_seen_item = None;
+            }
+            self.explored.insert(position);
+        }
+        found_items
+    }
+
+    pub fn is_explored(&self, position: Position) -> bool {
+        self.explored.contains(&position)
+    }
+
+    pub fn get_last_seen_item(&self) -> Option<Position> {
+        self.last_seen_item
+    }
+
+    pub fn is_npc_in_view(&self) -> bool {
+        let visible = self.get_visible_positions();
+        self.characters.get_npcs().iter().any(|npc| visible.contains(&npc.get_global_position()))
+    }
+
+    // The closest place the player can walk to that they've not explored yet
+    pub fn find_nearest_unexplored(&self, diagonal: bool) -> Option<Position> {
+        let start = self.characters.get_player()?.get_global_position();
+        let map = self.map.as_ref()?;
+
+        let mut visited = HashSet::from([start]);
+        let mut to_visit = VecDeque::from([start]);
+        while let Some(position) = to_visit.pop_front() {
+            if !self.explored.contains(&position) {
+                return Some(position);
+            }
+            for neighbor in map.get_walkable_neighbors(position, diagonal) {
+                if visited.insert(neighbor) {
+                    to_visit.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    // Anything worth stopping for at or right beside the given position
+    fn has_anything_beside(&self, position: Position) -> bool {
+        let mut nearby = vec![position];
+        nearby.extend(position.get_neighbors());
+        nearby.extend(position.get_diagonal_neighbors());
+        nearby.iter().any(|p| self.has_items_at(*p) || self.characters.get_npcs().iter().any(|npc| npc.get_global_position() == *p))
+    }
+
+    /*
+     * Whether the player running in the given direction should stop where they've got to
+     * They stop on running into a wall, or on reaching anywhere worth a look, i.e a doorway, a corridor junction, into a room or beside something new
+     */
+    pub fn is_run_interrupted(&self, direction: Direction) -> bool {
+        let (position, map) = match (self.characters.get_player(), self.map.as_ref()) {
+            (Some(player), Some(map)) => (player.get_global_position(), map),
+            _ => return true
+        };
+        let previous = position.get_adjacent_position(direction.get_opposite()).unwrap_or(position);
+
+        let at_wall = !position.get_adjacent_position(direction).is_some_and(|ahead| map.can_step_between(position, ahead));
+        let in_doorway = map.rooms.iter().any(|r| r.get_doors().iter().any(|d| d.position == position));
+        let in_room = |p: Position| map.rooms.iter().any(|r| r.get_inside_area().contains_position(p));
+        let entered_room = in_room(position) && !in_room(previous);
+        // Corridors only ever lead two ways, unless they branch off
+        let at_junction = !in_room(position) && !in_doorway && map.get_walkable_neighbors(position, false).len() > 2;
+        let beside_something_new = self.has_anything_beside(position) && !self.has_anything_beside(previous);
+        at_wall || in_doorway || entered_room || at_junction || beside_something_new
+    }
+
+    fn is_occupied(&self, position: Position) -> bool {
+        let player_here = self.characters.get_player().is_some_and(|p| p.get_global_position() == position);
+        player_here || self.characters.get_npcs().iter().any(|npc| npc.get_global_position() == position)
+    }
+
+    // Rooms NPCs can spawn in, avoiding the stairs so nothing is waiting on top of the player as they arrive
+    fn find_spawn_rooms(&self) -> Vec<Room> {
+        let rooms = self.map.as_ref().map(|m| m.rooms.clone()).unwrap_or_default();
+        let spawn_rooms: Vec<Room> = rooms.iter()
+            .filter(|r| r.get_entry().is_none() && r.get_exit().is_none())
+            .cloned()
+            .collect();
+        if spawn_rooms.is_empty() { rooms } else { spawn_rooms }
+    }
+
+    fn find_spawn_position(&self, rng: &mut Pcg64) -> Option<Position> {
+        let spawn_rooms = self.find_spawn_rooms();
+        let map = self.map.as_ref()?;
+        if spawn_rooms.is_empty() {
+            return None;
+        }
+        for _ in 0..SPAWN_POSITION_ATTEMPTS {
+            let room = &spawn_rooms[rng.random_range(0..spawn_rooms.len())];
+            let position = room.random_inside_pos(rng);
+            if map.is_traversable(position) && !self.is_occupied(position) {
+                return Some(position);
+            }
+        }
+        None
+    }
+
+    fn spawn_npc(&mut self, rng: &mut Pcg64) -> Result<bool, ErrorWrapper> {
+        if let Some(position) = self.find_spawn_position(rng) {
+            if let Some(mut npc) = self.spawn_table.spawn_npc(rng, self.depth)? {
+                npc.set_position(position);
+                self.characters.get_npcs_mut().push(npc);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Adds the initial NPCs for this level using it's spawn table
+    pub fn populate(&mut self, rng: &mut Pcg64) -> Result<(), ErrorWrapper> {
+        let npc_count = self.spawn_table.roll_npc_count(rng);
+        for _ in 0..npc_count {
+            if !self.spawn_npc(rng)? {
+                log::error!("Failed to find a spawn position for an NPC on level {}", self.depth);
+            }
+        }
+        Ok(())
+    }
+
+    /*
+     * Replaces any dead NPCs with a corpse on the Floor where they fell
+     * The corpse is the NPC's inventory, so it can be looted until it decays
+     */
+    pub fn bury_dead_npcs(&mut self, turn: u64) {
+        let npcs = self.characters.get_npcs_mut();
+        let dead: Vec<Character> = npcs.iter().filter(|npc| npc.is_dead()).cloned().collect();
+        npcs.retain(|npc| !npc.is_dead());
+
+        if let Some(map) = self.map.as_mut() {
+            for mut npc in dead {
+                let position = npc.get_global_position();
+                let corpse = npc.get_inventory_mut().clone();
+                let container_id = corpse.get_self_item().get_id();
+                let floor = map.get_containers_mut().entry(position).or_insert_with(build_floor_container);
+                match floor.add(corpse) {
+                    Ok(_) => self.corpses.push(Corpse { position, container_id, decays_at_turn: turn + CORPSE_DECAY_TURNS }),
+                    Err(e) => log::error!("Failed to leave a corpse for {}: {}", npc.get_name(), e)
+                }
+            }
+        }
+    }
+
+    // Removes any corpses that have rotted away by this turn, leaving their contents on the Floor
+    pub fn decay_corpses(&mut self, turn: u64) {
+        let (decayed, remaining): (Vec<Corpse>, Vec<Corpse>) = self.corpses.drain(..).partition(|c| c.decays_at_turn <= turn);
+        self.corpses = remaining;
+
+        if let Some(map) = self.map.as_mut() {
+            for corpse in decayed {
+                if let Some(floor) = map.find_container_mut(corpse.position) {
+                    let index = floor.get_contents().iter().position(|c| c.get_self_item().get_id() == corpse.container_id);
+                    if let Some(i) = index {
+                        let remains = floor.get_contents_mut().remove(i);
+                        floor.push(remains.get_contents().clone());
+                    }
+                }
+            }
+        }
+    }
+
+    // Lets each NPC take a number of random steps
+    fn wander_npcs(&mut self, steps: u64, rng: &mut Pcg64) {
+        let map = match self.map.as_ref() {
+            Some(m) => m.clone(),
+            None => return
+        };
+        for _ in 0..steps {
+            for i in 0..self.characters.get_npcs().len() {
+                let position = self.characters.get_npcs()[i].get_global_position();
+                let options: Vec<Position> = map.get_neighbors(position).into_iter()
+                    .filter(|n| map.is_traversable(*n) && !self.is_occupied(*n))
+                    .collect();
+                // Staying put is always an option
+                let choice = rng.random_range(0..=options.len());
+                if let Some(next) = options.get(choice) {
+                    self.characters.get_npcs_mut()[i].set_position(*next);
+                }
+            }
+        }
+    }
+
+    /*
+     * Moves any NPCs close enough to the player a step towards them
+     * NPCs stop once they're next to the player, and never step onto another character
+     */
+    pub fn chase_player(&mut self, diagonal: bool) {
+        let player_position = match self.characters.get_player() {
+            Some(p) => p.get_global_position(),
+            None => return
+        };
+        let map = match self.map.as_ref() {
+            Some(m) => m,
+            None => return
+        };
+
+        let mut occupied: Vec<Position> = self.characters.get_npcs().iter().map(|npc| npc.get_global_position()).collect();
+        for (i, npc) in self.characters.get_npcs_mut().iter_mut().enumerate() {
+            if npc.is_dead() {
+                continue;
+            }
+            let position = npc.get_global_position();
+            if position.chebyshev_distance(player_position) > CHASE_RANGE {
+                continue;
+            }
+
+            let mut pathfinding = Pathfinding::build_walking(position, diagonal);
+            let path = pathfinding.a_star_search(map, player_position);
+            if let Some(next) = path.get(1) {
+                if *next != player_position && !occupied.contains(next) {
+                    npc.set_position(*next);
+                    occupied[i] = *next;
+                }
+            }
+        }
+    }
+
+    /*
+     * Simulates the turns that have passed since the player last left this level
+     * NPCs move around, the dead are left to rot and some NPCs respawn (up to the spawn table's limit)
+     */
+    pub fn catch_up(&mut self, turn: u64, rng: &mut Pcg64) {
+        let elapsed = turn.saturating_sub(self.last_visited_turn);
+        if elapsed == 0 {
+            return;
+        }
+
+        self.bury_dead_npcs(self.last_visited_turn);
+        self.wander_npcs(elapsed.min(MAX_CATCH_UP_STEPS), rng);
+        self.decay_corpses(turn);
+
+        // No respawn turns means nothing ever respawns
+        if let Some(due) = elapsed.checked_div(self.spawn_table.get_respawn_turns()) {
+            let missing = self.spawn_table.get_max_npcs().saturating_sub(self.characters.get_npcs().len()) as u64;
+            let respawns = due.min(missing);
+            for _ in 0..respawns {
+                match self.spawn_npc(rng) {
+                    Ok(true) => {},
+                    Ok(false) => break,
+                    Err(e) => {
+                        log::error!("Failed to respawn an NPC on level {}: {}", self.depth, e);
+                        break;
+                    }
+                }
+            }
+        }
+        self.last_visited_turn = turn;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use rand_seeder::Seeder;
+    use uuid::Uuid;
+
+    use crate::character::builder::character_builder::{CharacterBuilder, CharacterPattern};
+    use crate::character::characters::Characters;
+    use crate::engine::level::{init_level_manager, Level, LevelChange, LevelChangeResult, Levels, CORPSE_DECAY_TURNS};
+    use crate::map::map_generator::build_floor_container;
+    use crate::map::map_loader::parse_map;
+    use crate::map::objects::items::{Item, MaterialType};
+    use crate::map::position::{Direction, Position};
+    use crate::map::Map;
+
+    // Stairs in the first room, with a second room for NPCs to spawn in
+    fn build_stairs_map() -> Map {
+        let input = "\
+[map]
+######  #######
+#<..>=--=.....#
+#....#  #.....#
+######  #######
+";
+        parse_map(input).unwrap().map
+    }
+
+    fn build_levels() -> Levels {
+        let mut levels = init_level_manager("test".to_string(), Seeder::from("test").into_rng());
+        levels.add_level(build_stairs_map()).unwrap();
+        let player = CharacterBuilder::new(CharacterPattern::new_player().unwrap()).build(String::from("Player"));
+        levels.get_level_mut().characters.set_player(player);
+        levels
+    }
+
+    // The player in the stairs room, with an item lying in the other room
+    fn build_explorable_level(player_position: Position) -> Level {
+        let mut map = build_stairs_map();
+        let mut floor = build_floor_container();
+        floor.add_item(Item::new(Uuid::new_v4(), String::from("Gold Bar"), MaterialType::GOLD, 'X', 1.0, 100)).unwrap();
+        map.containers.insert(Position::new(10, 2), floor);
+        let player = CharacterBuilder::new(CharacterPattern::new_player().unwrap()).position(player_position).build(String::from("Player"));
+        Level::new(Some(map), Characters::new(Some(player), Vec::new()))
+    }
+
+    #[test]
+    fn test_explore() {
+        // GIVEN the player in the stairs room
+        let mut level = build_explorable_level(Position::new(1, 1));
+
+        // WHEN they look around
+        // THEN the whole of the room is explored, walls and doors included, but nothing beyond it
+        assert!(level.explore().is_empty());
+        assert!(level.is_explored(Position::new(4, 2)));
+        assert!(level.is_explored(Position::new(5, 1)));
+        assert!(!level.is_explored(Position::new(6, 1)));
+        assert_eq!(None, level.get_last_seen_item());
+
+        // WHEN they walk into the other room
+        level.get_player_mut().unwrap().set_position(Position::new(9, 1));
+
+        // THEN they find the item lying there
+        assert_eq!(vec![Position::new(10, 2)], level.explore());
+        assert_eq!(Some(Position::new(10, 2)), level.get_last_seen_item());
+        // AND it's not found a second time
+        assert!(level.explore().is_empty());
+    }
+
+    #[test]
+    fn test_find_nearest_unexplored() {
+        // GIVEN the player has explored the stairs room
+        let mut level = build_explorable_level(Position::new(1, 1));
+        level.explore();
+
+        // WHEN we look for the nearest place left to explore
+        // THEN we expect the start of the corridor, just outside the door
+        assert_eq!(Some(Position::new(6, 1)), level.find_nearest_unexplored(false));
+    }
+
+    #[test]
+    fn test_is_npc_in_view() {
+        // GIVEN an NPC in the room beside the player's
+        let mut level = build_explorable_level(Position::new(1, 1));
+        let npc = CharacterBuilder::new(CharacterPattern::new_player().unwrap()).position(Position::new(12, 2)).build(String::from("Goblin"));
+        level.characters.get_npcs_mut().push(npc);
+
+        // WHEN the player is in their own room
+        // THEN the NPC is out of view
+        assert!(!level.is_npc_in_view());
+
+        // WHEN the player walks into the NPC's room
+        level.get_player_mut().unwrap().set_position(Position::new(9, 1));
+        // THEN the NPC is in view
+        assert!(level.is_npc_in_view());
+    }
+
+    #[test]
+    fn test_is_run_interrupted() {
+        // GIVEN the player in the corridor between the two rooms, running right
+        let mut level = build_explorable_level(Position::new(6, 1));
+        let mut run_to = |position: Position, direction: Direction| {
+            level.get_player_mut().unwrap().set_position(position);
+            level.is_run_interrupted(direction)
+        };
+
+        // THEN they keep going along the corridor
+        assert!(!run_to(Position::new(6, 1), Direction::RIGHT));
+        // AND stop in the doorway, and again on entering the room
+        assert!(run_to(Position::new(8, 1), Direction::RIGHT));
+        assert!(run_to(Position::new(9, 1), Direction::RIGHT));
+        // AND keep going across the room until they reach the far wall
+        assert!(!run_to(Position::new(12, 1), Direction::RIGHT));
+        assert!(run_to(Position::new(13, 1), Direction::RIGHT));
+
+        // WHEN they run left along the bottom of the room, towards the item
+        // THEN they stop as soon as they're beside it
+        assert!(!run_to(Position::new(12, 2), Direction::LEFT));
+        assert!(run_to(Position::new(11, 2), Direction::LEFT));
+    }
+
+    #[test]
+    fn test_add_level_populates_npcs() {
+        // GIVEN a fresh set of levels
+        // WHEN we add a level
+        let mut levels = build_levels();
+
+        // THEN we expect it to be populated from it's spawn table, away from the stairs
+        let level = levels.get_level_mut();
+        let npcs = level.characters.get_npcs();
+        assert!(!npcs.is_empty());
+        for npc in npcs {
+            assert!(npc.get_global_position().x > 8, "NPC spawned in the stairs room: {:?}", npc.get_global_position());
+        }
+    }
+
+    #[test]
+    fn test_change_level_down_generates_populated_level() {
+        // GIVEN a single level
+        let mut levels = build_levels();
+
+        // WHEN we go down with a newly generated map
+        let result = levels.change_level(LevelChange::DOWN, Some(build_stairs_map())).unwrap();
+
+        // THEN the player is moved to the new, populated level
+        assert!(matches!(result, LevelChangeResult::LevelChanged));
+        assert_eq!(1, levels.get_current_level());
+        let level = levels.get_level_mut();
+        assert_eq!(1, level.get_depth());
+        assert!(level.characters.get_player().is_some());
+        assert!(!level.characters.get_npcs().is_empty());
+    }
+
+    #[test]
+    fn test_add_loaded_level() {
+        // GIVEN a single level
+        let mut levels = build_levels();
+        // AND a loaded map with a player spawn and two NPC spawns
+        let input = "\
+[map]
+#######
+#<.@.&#
+#&...>#
+#######
+";
+        let loaded = parse_map(input).unwrap();
+
+        // WHEN we add it as the next level
+        levels.add_loaded_level(loaded).unwrap();
+
+        // THEN the player is moved to the player spawn
+        let level = levels.levels.last().unwrap();
+        assert_eq!(1, level.get_depth());
+        assert_eq!(Position::new(3, 1), level.characters.get_player().unwrap().get_global_position());
+        // AND NPCs are placed on each of the NPC spawns
+        let npc_positions: Vec<Position> = level.characters.get_npcs().iter().map(|npc| npc.get_global_position()).collect();
+        assert_eq!(vec![Position::new(5, 1), Position::new(1, 2)], npc_positions);
+    }
+
+    #[test]
+    fn test_place_player_at_stairs() {
+        // GIVEN a level with an entry and exit
+        let mut levels = build_levels();
+        let level = levels.get_level_mut();
+
+        // WHEN the player arrives from below
+        level.place_player_at_stairs(LevelChange::UP);
+        // THEN they're placed on the exit
+        assert_eq!(Position::new(4, 1), level.get_player_mut().unwrap().get_global_position());
+
+        // WHEN the player arrives from above
+        level.place_player_at_stairs(LevelChange::DOWN);
+        // THEN they're placed on the entry
+        assert_eq!(Position::new(1, 1), level.get_player_mut().unwrap().get_global_position());
+    }
+
+    #[test]
+    fn test_revisit_level_catches_up() {
+        // GIVEN a level with a dead NPC, that the player leaves for a long while
+        let mut levels = build_levels();
+        let npc_count = levels.get_level_mut().characters.get_npcs().len();
+        let dead_position = levels.get_level_mut().characters.get_npcs()[0].get_global_position();
+        levels.get_level_mut().characters.get_npcs_mut()[0].set_health(0);
+        levels.change_level(LevelChange::DOWN, Some(build_stairs_map())).unwrap();
+        for _ in 0..CORPSE_DECAY_TURNS + 1 {
+            levels.end_turn();
+        }
+
+        // WHEN the player returns
+        levels.change_level(LevelChange::UP, None).unwrap();
+
+        // THEN the corpse has rotted away, leaving the dagger it carried on the Floor
+        let level = levels.get_level_mut();
+        assert!(level.corpses.is_empty());
+        let floor = level.map.as_ref().unwrap().get_container(dead_position).unwrap();
+        assert_eq!(1, floor.get_contents().len());
+        assert_eq!("Iron Dagger", floor.get_contents()[0].get_self_item().get_name());
+
+        // AND the player is still on the level, with NPCs within the spawn table limit
+        assert!(level.characters.get_player().is_some());
+        let respawned = level.characters.get_npcs().len();
+        assert!(respawned >= npc_count - 1 && respawned <= level.spawn_table.get_max_npcs());
+    }
+
+    #[test]
+    fn test_catch_up_leaves_map_generation_alone() {
+        // GIVEN two sets of levels from the same seed, where the player leaves the first level
+        let mut levels = build_levels();
+        let mut other_levels = build_levels();
+        levels.change_level(LevelChange::DOWN, Some(build_stairs_map())).unwrap();
+        other_levels.change_level(LevelChange::DOWN, Some(build_stairs_map())).unwrap();
+
+        // WHEN the player only spends time away from the first level in one of them before returning
+        for _ in 0..50 {
+            levels.end_turn();
+        }
+        levels.change_level(LevelChange::UP, None).unwrap();
+        other_levels.change_level(LevelChange::UP, None).unwrap();
+
+        // THEN the map generation RNG is left as it was, so later levels are generated the same
+        assert!(levels.rng == other_levels.rng);
+    }
+
+    #[test]
+    fn test_dead_npc_leaves_corpse() {
+        // GIVEN a level with a dead NPC
+        let mut levels = build_levels();
+        let dead_position = levels.get_level_mut().characters.get_npcs()[0].get_global_position();
+        levels.get_level_mut().characters.get_npcs_mut()[0].set_health(0);
+
+        // WHEN the turn ends
+        levels.end_turn();
+
+        // THEN the NPC is replaced by it's corpse on the Floor
+        let level = levels.get_level_mut();
+        assert!(level.characters.get_npcs().iter().all(|npc| npc.get_global_position() != dead_position));
+        assert_eq!(1, level.corpses.len());
+        let floor = level.map.as_ref().unwrap().get_container(dead_position).unwrap();
+        assert_eq!("A Goblin's dead body", floor.get_contents()[0].get_self_item().get_name());
+    }
+
+    #[test]
+    fn test_chase_player() {
+        // GIVEN a level with a single NPC 2 steps diagonally away from the player
+        let input = "\
+[map]
+#######
+#.....#
+#.....#
+#.....#
+#######
+";
+        let mut levels = build_levels();
+        let level = levels.get_level_mut();
+        level.map = Some(parse_map(input).unwrap().map);
+        let mut npc = level.characters.get_npcs()[0].clone();
+        npc.set_position(Position::new(1, 1));
+        level.characters.set_npcs(vec![npc]);
+        level.characters.get_player_mut().unwrap().set_position(Position::new(3, 3));
+
+        // WHEN the NPC chases the player, allowing diagonal movement
+        level.chase_player(true);
+        // THEN it moves diagonally towards them
+        assert_eq!(Position::new(2, 2), level.characters.get_npcs()[0].get_global_position());
+
+        // AND WHEN it chases them again
+        level.chase_player(true);
+        // THEN it stays next to them
+        assert_eq!(Position::new(2, 2), level.characters.get_npcs()[0].get_global_position());
+
+        // AND WHEN the player moves away from it after it's died
+        level.characters.get_npcs_mut()[0].set_health(0);
+        level.characters.get_player_mut().unwrap().set_position(Position::new(5, 3));
+        level.chase_player(true);
+        // THEN it's left where it fell
+        assert_eq!(Position::new(2, 2), level.characters.get_npcs()[0].get_global_position());
+    }
+}
@@ -0,0 +1,357 @@
+use rand::Rng;
+use rand_pcg::Pcg64;
+use uuid::Uuid;
+
+use crate::map::objects::container::{Container, ContainerType};
+use crate::map::objects::identification::{PotionType, ScrollType};
+use crate::map::objects::items::{Item, ItemForm, ItemType, MaterialType};
+use crate::map::objects::weapon_builder::{BladedWeaponType, WeaponBlueprint, WeaponBuilder};
+
+// Rarer finds stop getting more likely past this depth
+const MAX_RARITY_DEPTH: usize = 6;
+const COIN_WEIGHT: f32 = 0.01;
+const BAR_WEIGHT: f32 = 1.0;
+
+/*
+ * How special a find is, deciding the materials it's made from and how much it's worth
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare
+}
+
+impl Rarity {
+    // Rarer finds become more likely the deeper the level
+    fn weight(&self, depth: usize) -> u32 {
+        let depth = depth.min(MAX_RARITY_DEPTH) as u32;
+        match self {
+            Rarity::Common => 70,
+            Rarity::Uncommon => 20 + 5 * depth,
+            Rarity::Rare => 5 + 5 * depth
+        }
+    }
+
+    fn value_multiplier(&self) -> i32 {
+        match self {
+            Rarity::Common => 1,
+            Rarity::Uncommon => 2,
+            Rarity::Rare => 4
+        }
+    }
+
+    pub fn roll(rng: &mut Pcg64, depth: usize) -> Rarity {
+        let tiers = [Rarity::Common, Rarity::Uncommon, Rarity::Rare];
+        let total_weight: u32 = tiers.iter().map(|r| r.weight(depth)).sum();
+        let mut roll = rng.random_range(0..total_weight);
+        for tier in tiers {
+            if roll < tier.weight(depth) {
+                return tier;
+            }
+            roll -= tier.weight(depth);
+        }
+        Rarity::Common
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LootKind {
+    Weapon,
+    Armour,
+    Potion,
+    Scroll,
+    Coins,
+    Bar
+}
+
+/*
+ * The kinds of container placed in rooms, each holding different sorts of loot
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LootContainerType {
+    Chest,
+    Crate,
+    Urn
+}
+
+impl LootContainerType {
+    pub fn name(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    // Chests are the most common, holding a bit of everything
+    pub fn roll(rng: &mut Pcg64) -> LootContainerType {
+        match rng.random_range(0..7) {
+            0..=2 => LootContainerType::Chest,
+            3..=4 => LootContainerType::Crate,
+            _ => LootContainerType::Urn
+        }
+    }
+
+    pub fn build_empty(&self) -> Container {
+        let (weight, weight_limit) = match self {
+            LootContainerType::Chest => (50.0, 100),
+            LootContainerType::Crate => (20.0, 80),
+            LootContainerType::Urn => (10.0, 20)
+        };
+        Container::new(Uuid::new_v4(), self.name(), '$', weight, 1, ContainerType::AREA, weight_limit)
+    }
+}
+
+/*
+ * A single kind of loot that can be found, weighted against the other entries of the table
+ */
+#[derive(Clone, Debug)]
+pub struct LootEntry {
+    pub kind: LootKind,
+    pub weight: u32,
+    // The first dungeon depth (0 being the first level) this entry can be found at
+    pub min_depth: usize
+}
+
+/*
+ * Describes what's found in a type of container, and how much of it
+ */
+#[derive(Clone, Debug)]
+pub struct LootTable {
+    container_type: LootContainerType,
+    entries: Vec<LootEntry>,
+    min_items: usize,
+    max_items: usize
+}
+
+pub fn build_loot_table(container_type: LootContainerType, depth: usize) -> LootTable {
+    let entry = |kind, weight, min_depth| LootEntry { kind, weight, min_depth };
+    let (entries, min_items, max_items) = match container_type {
+        LootContainerType::Chest => (vec![
+            entry(LootKind::Weapon, 3, 0),
+            entry(LootKind::Armour, 3, 0),
+            entry(LootKind::Coins, 4, 0),
+            entry(LootKind::Bar, 2, 0),
+            entry(LootKind::Potion, 2, 0),
+            entry(LootKind::Scroll, 1, 1)
+        ], 2, 4),
+        // Heavier goods
+        LootContainerType::Crate => (vec![
+            entry(LootKind::Weapon, 3, 0),
+            entry(LootKind::Armour, 3, 0),
+            entry(LootKind::Bar, 3, 0),
+            entry(LootKind::Coins, 1, 0)
+        ], 1, 3),
+        // Small and precious things
+        LootContainerType::Urn => (vec![
+            entry(LootKind::Coins, 4, 0),
+            entry(LootKind::Potion, 3, 0),
+            entry(LootKind::Scroll, 2, 1)
+        ], 1, 2)
+    };
+
+    // Deeper levels hold more
+    LootTable::new(container_type, entries, min_items + depth / 3, max_items + depth / 2)
+}
+
+fn choose<T: Clone>(rng: &mut Pcg64, options: &[T]) -> T {
+    options[rng.random_range(0..options.len())].clone()
+}
+
+fn build_weapon(rng: &mut Pcg64, rarity: Rarity) -> Item {
+    let material = match rarity {
+        Rarity::Common => MaterialType::IRON,
+        Rarity::Uncommon => MaterialType::STEEL,
+        Rarity::Rare => choose(rng, &[MaterialType::SILVER, MaterialType::GOLD])
+    };
+    let form = ItemForm::BLADED(choose(rng, &[BladedWeaponType::DAGGER, BladedWeaponType::ARMING, BladedWeaponType::LONG]));
+    // Every material chosen above can be made into a weapon
+    let blueprint = WeaponBlueprint::new(material.clone(), form).expect("Loot weapons should use weapon materials");
+    let mut weapon = WeaponBuilder::new(blueprint).build();
+    weapon.value = ((weapon.weight * material.value_per_kg() as f32) as i32).max(1) * rarity.value_multiplier();
+    weapon
+}
+
+fn build_armour(rng: &mut Pcg64, rarity: Rarity) -> Item {
+    let material = match rarity {
+        Rarity::Common => MaterialType::LEATHER,
+        Rarity::Uncommon => choose(rng, &[MaterialType::BRONZE, MaterialType::IRON]),
+        Rarity::Rare => MaterialType::STEEL
+    };
+    // Weights for iron, with other materials scaled by their density
+    let (item_type, piece, iron_weight) = choose(rng, &[
+        (ItemType::HEADGEAR, "Helm", 1.5),
+        (ItemType::TORSO, "Cuirass", 8.0),
+        (ItemType::LEGS, "Greaves", 4.0)
+    ]);
+    let weight = iron_weight * material.density_grams_cm3() as f32 / MaterialType::IRON.density_grams_cm3() as f32;
+    let value = ((weight * material.value_per_kg() as f32) as i32).max(1) * rarity.value_multiplier();
+    Item::armour(Uuid::new_v4(), format!("{} {}", material.name(), piece), item_type, material, '[', weight, value)
+}
+
+fn build_coins(rng: &mut Pcg64, rarity: Rarity, depth: usize) -> Item {
+    let material = match rarity {
+        Rarity::Common => MaterialType::BRONZE,
+        Rarity::Uncommon => MaterialType::SILVER,
+        Rarity::Rare => MaterialType::GOLD
+    };
+    let value = ((COIN_WEIGHT * material.value_per_kg() as f32) as i32).max(1);
+    let mut coins = Item::new_with_form(Uuid::new_v4(), String::new(), material, ItemForm::COIN, '$', COIN_WEIGHT, value);
+    coins.set_quantity(rng.random_range(5..=20) * (depth as u32 + 1));
+    coins
+}
+
+fn build_bar(rng: &mut Pcg64, rarity: Rarity) -> Item {
+    let material = match rarity {
+        Rarity::Common => choose(rng, &[MaterialType::TIN, MaterialType::BRONZE]),
+        Rarity::Uncommon => choose(rng, &[MaterialType::IRON, MaterialType::STEEL]),
+        Rarity::Rare => choose(rng, &[MaterialType::SILVER, MaterialType::GOLD])
+    };
+    let value = (BAR_WEIGHT * material.value_per_kg() as f32) as i32;
+    Item::new_with_form(Uuid::new_v4(), String::new(), material, ItemForm::BAR, '=', BAR_WEIGHT, value)
+}
+
+// Each item keeps the rarity it was rolled with
+pub fn build_loot_item(rng: &mut Pcg64, kind: LootKind, rarity: Rarity, depth: usize) -> Item {
+    let mut item = match kind {
+        LootKind::Weapon => build_weapon(rng, rarity),
+        LootKind::Armour => build_armour(rng, rarity),
+        LootKind::Coins => build_coins(rng, rarity, depth),
+        LootKind::Bar => build_bar(rng, rarity),
+        LootKind::Potion => Item::magic(Uuid::new_v4(), ItemForm::POTION(choose(rng, &PotionType::all())), '!', 0.5, 30 * rarity.value_multiplier()),
+        LootKind::Scroll => Item::magic(Uuid::new_v4(), ItemForm::SCROLL(choose(rng, &ScrollType::all())), '?', 0.1, 40 * rarity.value_multiplier()),
+    };
+    item.set_rarity(rarity);
+    item
+}
+
+impl LootTable {
+    pub fn new(container_type: LootContainerType, entries: Vec<LootEntry>, min_items: usize, max_items: usize) -> LootTable {
+        LootTable { container_type, entries, min_items, max_items }
+    }
+
+    #[cfg(test)]
+    pub fn get_max_items(&self) -> usize {
+        self.max_items
+    }
+
+    pub fn roll_item_count(&self, rng: &mut Pcg64) -> usize {
+        if self.entries.is_empty() || self.max_items == 0 {
+            return 0;
+        }
+        rng.random_range(self.min_items.min(self.max_items)..=self.max_items)
+    }
+
+    /*
+     * Picks a weighted random kind of loot available at the given depth
+     * Returns None if nothing can be found at this depth
+     */
+    pub fn roll_kind(&self, rng: &mut Pcg64, depth: usize) -> Option<LootKind> {
+        let available: Vec<&LootEntry> = self.entries.iter().filter(|e| e.min_depth <= depth && e.weight > 0).collect();
+        let total_weight: u32 = available.iter().map(|e| e.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rng.random_range(0..total_weight);
+        for entry in available {
+            if roll < entry.weight {
+                return Some(entry.kind);
+            }
+            roll -= entry.weight;
+        }
+        None
+    }
+
+    // Builds this table's type of container, filled with loot for the given depth
+    pub fn fill_container(&self, rng: &mut Pcg64, depth: usize) -> Container {
+        let mut container = self.container_type.build_empty();
+        for _ in 0..self.roll_item_count(rng) {
+            if let Some(kind) = self.roll_kind(rng, depth) {
+                let rarity = Rarity::roll(rng, depth);
+                let item = build_loot_item(rng, kind, rarity, depth);
+                if let Err(e) = container.add_item(item) {
+                    log::debug!("Skipping loot that doesn't fit in the {}: {}", self.container_type.name(), e);
+                }
+            }
+        }
+        container
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_pcg::Pcg64;
+    use rand_seeder::Seeder;
+
+    use crate::engine::loot_table::{build_loot_item, build_loot_table, LootContainerType, LootEntry, LootKind, LootTable, Rarity};
+    use crate::map::objects::items::{ItemCategory, ItemType, MaterialType};
+
+    #[test]
+    fn test_build_loot_table_depth_scaling() {
+        // GIVEN loot tables for the first and a deeper level
+        let first = build_loot_table(LootContainerType::Chest, 0);
+        let deeper = build_loot_table(LootContainerType::Chest, 4);
+
+        // THEN the deeper level holds more loot
+        assert!(deeper.get_max_items() > first.get_max_items());
+    }
+
+    #[test]
+    fn test_roll_kind_below_min_depth() {
+        // GIVEN a loot table whose only entry requires depth 2
+        let table = LootTable::new(LootContainerType::Urn, vec![LootEntry { kind: LootKind::Scroll, weight: 1, min_depth: 2 }], 1, 1);
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we roll for loot above and at that depth
+        // THEN it's only found once deep enough
+        assert_eq!(None, table.roll_kind(&mut rng, 1));
+        assert_eq!(Some(LootKind::Scroll), table.roll_kind(&mut rng, 2));
+    }
+
+    #[test]
+    fn test_fill_container() {
+        // GIVEN the loot table for a chest on the first level
+        let table = build_loot_table(LootContainerType::Chest, 0);
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we fill several chests
+        // THEN each is a chest holding up to 4 items (fewer if any stacked) within it's weight limit
+        for _ in 0..20 {
+            let chest = table.fill_container(&mut rng, 0);
+            assert_eq!("Chest", chest.get_self_item().get_name());
+            assert!((1..=4).contains(&chest.get_contents().len()));
+            assert!(chest.get_contents_weight_total() <= chest.get_weight_limit() as f32);
+        }
+
+        // AND the same seed always gives the same loot
+        let names = |seed: &str| {
+            let mut rng: Pcg64 = Seeder::from(seed).into_rng();
+            table.fill_container(&mut rng, 0).to_cloned_item_list().iter().map(|i| i.get_name()).collect::<Vec<String>>()
+        };
+        assert_eq!(names("seed"), names("seed"));
+    }
+
+    #[test]
+    fn test_build_loot_item() {
+        // GIVEN a seeded rng
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we build loot of each kind
+        let weapon = build_loot_item(&mut rng, LootKind::Weapon, Rarity::Uncommon, 0);
+        let armour = build_loot_item(&mut rng, LootKind::Armour, Rarity::Common, 0);
+        let coins = build_loot_item(&mut rng, LootKind::Coins, Rarity::Rare, 2);
+        let bar = build_loot_item(&mut rng, LootKind::Bar, Rarity::Common, 0);
+
+        // THEN we expect real items of that kind and rarity
+        assert_eq!(ItemCategory::Weapons, weapon.get_category());
+        assert_eq!(MaterialType::STEEL, weapon.get_material_type());
+        assert_eq!(ItemCategory::Armour, armour.get_category());
+        assert_eq!(MaterialType::LEATHER, armour.get_material_type());
+        assert_eq!("Gold Coin", coins.get_name());
+        // 5 to 20 coins per depth level
+        assert!((15..=60).contains(&coins.get_quantity()));
+        assert_eq!(ItemCategory::Valuables, bar.get_category());
+        assert_eq!(ItemType::ITEM, bar.item_type);
+        // AND each keeps the rarity it was built with
+        assert_eq!(Rarity::Uncommon, weapon.get_rarity());
+        assert_eq!(Rarity::Rare, coins.get_rarity());
+    }
+}
@@ -4,10 +4,26 @@ use std::collections::{BinaryHeap, HashMap};
 use crate::map::position::Position;
 use crate::map::Map;
 
+// Costs are scaled up so a diagonal step (~1.4) can be represented as an integer
+const STRAIGHT_STEP_COST: i32 = 10;
+const DIAGONAL_STEP_COST: i32 = 14;
+
 trait ManhattanPathCosting {
     fn manhattan_path_cost(&self, a: Position, b: Position) -> i32;
 }
 
+trait OctilePathCosting {
+    fn octile_path_cost(&self, a: Position, b: Position) -> i32;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathfindingMode {
+    // 4 directions over anything paveable (including empty tiles), for laying out corridors
+    Paving,
+    // Over traversable tiles only, as a character would move, optionally including diagonal steps
+    Walking { diagonal: bool }
+}
+
 #[derive(Clone, Hash, Debug)]
 struct Node {
     position: Position,
@@ -36,6 +52,7 @@ impl Eq for Node {}
 
 pub struct Pathfinding {
     start_position: Position,
+    mode: PathfindingMode,
     unvisited : BinaryHeap<Reverse<Node>>,
     came_from: HashMap<Position, Position>,
     g_scores:   HashMap<Position, i32>,
@@ -50,9 +67,27 @@ impl ManhattanPathCosting for Pathfinding {
     }
 }
 
+impl OctilePathCosting for Pathfinding {
+    fn octile_path_cost(&self, a: Position, b: Position) -> i32 {
+        let x_abs = a.x.abs_diff(b.x) as i32;
+        let y_abs = a.y.abs_diff(b.y) as i32;
+        let diagonal_steps = x_abs.min(y_abs);
+        let straight_steps = x_abs.max(y_abs) - diagonal_steps;
+        diagonal_steps * DIAGONAL_STEP_COST + straight_steps * STRAIGHT_STEP_COST
+    }
+}
+
 impl Pathfinding {
     pub fn build(start_position: Position) -> Pathfinding {
-        let mut pathfinding = Pathfinding { start_position, unvisited: BinaryHeap::new(), came_from: HashMap::new(), g_scores: HashMap::new(), f_scores: HashMap::new() };
+        Pathfinding::build_for_mode(start_position, PathfindingMode::Paving)
+    }
+
+    pub fn build_walking(start_position: Position, diagonal: bool) -> Pathfinding {
+        Pathfinding::build_for_mode(start_position, PathfindingMode::Walking { diagonal })
+    }
+
+    fn build_for_mode(start_position: Position, mode: PathfindingMode) -> Pathfinding {
+        let mut pathfinding = Pathfinding { start_position, mode, unvisited: BinaryHeap::new(), came_from: HashMap::new(), g_scores: HashMap::new(), f_scores: HashMap::new() };
         let node = Node { position: start_position, score: 0 };
         let reversed = Reverse(node);
         pathfinding.unvisited.push(reversed);
@@ -98,8 +133,30 @@ impl Pathfinding {
         }
     }
 
+    fn path_cost(&self, a: Position, b: Position) -> i32 {
+        match self.mode {
+            PathfindingMode::Walking { diagonal: true } => self.octile_path_cost(a, b),
+            _ => self.manhattan_path_cost(a, b)
+        }
+    }
+
+    fn can_search_from(&self, map: &Map, position: Position) -> bool {
+        match self.mode {
+            PathfindingMode::Paving => map.is_paveable(position),
+            // Characters can always leave where they're standing
+            PathfindingMode::Walking { .. } => position == self.start_position || map.is_traversable(position)
+        }
+    }
+
+    fn find_neighbors(&self, map: &Map, position: Position) -> Vec<Position> {
+        match self.mode {
+            PathfindingMode::Paving => position.get_neighbors(),
+            PathfindingMode::Walking { diagonal } => map.get_walkable_neighbors(position, diagonal)
+        }
+    }
+
     pub fn a_star_search(&mut self, map : &Map, end_position: Position) -> Vec<Position> {
-        let score_estimate = self.manhattan_path_cost(self.start_position, end_position);
+        let score_estimate = self.path_cost(self.start_position, end_position);
         self.f_scores.insert(self.start_position, score_estimate);
 
         while !self.unvisited.is_empty() {
@@ -109,8 +166,8 @@ impl Pathfinding {
                 return self.build_path(end_position);
             }
 
-            if map.is_paveable(current_lowest_score_node.position) {
-                let neighbors = current_lowest_score_node.position.get_neighbors();
+            if self.can_search_from(map, current_lowest_score_node.position) {
+                let neighbors = self.find_neighbors(map, current_lowest_score_node.position);
                 log::debug!("Found {} neighbors for current_lowest_score_node: {:?}", neighbors.len(), current_lowest_score_node.position);
 
                 let current_position = current_lowest_score_node.position.clone();
@@ -120,13 +177,13 @@ impl Pathfinding {
                     log::debug!("Evaluating neighbor {:?}", n);
                     let neighbor_g_score = self.g_scores.get(&n).unwrap_or(&(i16::MAX as i32));
                     log::debug!("Current neighbor gScore {}", current_g_score);
-                    let distance_through = self.manhattan_path_cost(current_position, n);
+                    let distance_through = self.path_cost(current_position, n);
                     log::debug!("Distance through neighbor {}", distance_through);
                     let potential_g_score = current_g_score + distance_through;
                     if potential_g_score < *neighbor_g_score {
                         self.came_from.insert(n, current_position);
                         self.g_scores.insert(n, potential_g_score);
-                        let through_neighbor_score = self.manhattan_path_cost(n, end_position);
+                        let through_neighbor_score = self.path_cost(n, end_position);
 
                         let neighbor_fscore = potential_g_score + through_neighbor_score;
                         self.f_scores.insert(n, neighbor_fscore);
@@ -150,7 +207,7 @@ impl Pathfinding {
 mod tests {
     use std::collections::HashMap;
 
-    use crate::engine::pathfinding::{ManhattanPathCosting, Node, Pathfinding};
+    use crate::engine::pathfinding::{ManhattanPathCosting, Node, OctilePathCosting, Pathfinding};
     use crate::map;
     use crate::map::position::{build_square_area, Position};
     use crate::map::room::build_room;
@@ -159,7 +216,7 @@ mod tests {
 
     fn build_test_map() -> map::Map {
         let tile_library = crate::map::tile::build_library();
-        assert_eq!(9, tile_library.len());
+        assert_eq!(14, tile_library.len());
 
         let non = tile_library[&TileType::NoTile].clone();
         let rom = tile_library[&TileType::Room].clone();
@@ -322,4 +379,67 @@ mod tests {
         assert_eq!(Position{x:1, y:2}, *path.get(3).unwrap());
         assert_eq!(Position{x:2, y:2}, *path.get(4).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_octile_path_cost() {
+        // GIVEN 2 positions 2 apart horizontally and 3 vertically
+        let start_pos = Position { x: 1, y: 3 };
+        let end_pos =  Position { x: 3, y: 6 };
+
+        // WHEN we call to get the octile cost between them
+        let pathfinding = Pathfinding::build_walking(start_pos, true);
+        let result = pathfinding.octile_path_cost(start_pos, end_pos);
+
+        // THEN we expect 2 diagonal steps and 1 straight step
+        assert_eq!(38, result);
+    }
+
+    #[test]
+    fn test_a_star_search_walking_diagonal() {
+        // GIVEN a pathfinding instance allowed to walk diagonally from the top left
+        let start_pos = Position { x: 0, y: 0 };
+        let end_pos = Position { x: 3, y: 3 };
+        let mut pathfinding = Pathfinding::build_walking(start_pos, true);
+
+        // WHEN we search across an open map
+        let map = build_open_map();
+        let path = pathfinding.a_star_search(&map, end_pos);
+
+        // THEN we expect a straight diagonal line
+        assert_eq!(vec![Position::new(0, 0), Position::new(1, 1), Position::new(2, 2), Position::new(3, 3)], path);
+    }
+
+    #[test]
+    fn test_a_star_search_walking_no_corner_cutting() {
+        // GIVEN a pathfinding instance allowed to walk diagonally, outside of a room
+        let start_pos = Position { x: 0, y: 1 };
+        // AND an end position inside the room, through the door
+        let end_pos = Position { x: 2, y: 2 };
+        let mut pathfinding = Pathfinding::build_walking(start_pos, true);
+
+        // WHEN we search (with empty tiles being untraversable)
+        let mut map = build_test_map();
+        let tile_library = crate::map::tile::build_library();
+        for x in 0..4 {
+            map.tiles.set_tile(Position::new(x, 0), tile_library[&TileType::Room].clone());
+        }
+        for y in 0..4 {
+            map.tiles.set_tile(Position::new(0, y), tile_library[&TileType::Room].clone());
+        }
+        let path = pathfinding.a_star_search(&map, end_pos);
+
+        // THEN we expect the path to go straight through the door, rather than cutting the door frame's corners
+        assert_eq!(vec![Position::new(0, 1), Position::new(0, 2), Position::new(1, 2), Position::new(2, 2)], path);
+    }
+
+    fn build_open_map() -> map::Map {
+        let tile_library = crate::map::tile::build_library();
+        let rom = tile_library[&TileType::Room].clone();
+        map::Map {
+            area: build_square_area(Position::zero(), 4),
+            tiles: Tiles { tiles: vec![vec![rom.clone(); 4]; 4] },
+            rooms: Vec::new(),
+            containers: HashMap::new()
+        }
+    }
+}
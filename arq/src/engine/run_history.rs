@@ -0,0 +1,306 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::character::Character;
+use crate::error::errors::ErrorWrapper;
+
+pub const RUN_HISTORY_FILE: &str = "runs/history.json";
+
+// Every dungeon level reached is worth this much on top of the loot carried out
+const DEPTH_SCORE: i32 = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RunOutcome {
+    Escaped,
+    // The player gave up part way through the run
+    Quit,
+    // Holds whatever killed the player
+    Died(String)
+}
+
+impl RunOutcome {
+    pub fn describe(&self) -> String {
+        match self {
+            RunOutcome::Escaped => String::from("Escaped"),
+            RunOutcome::Quit => String::from("Quit"),
+            RunOutcome::Died(cause) => format!("Killed by {}", cause)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunSortOrder {
+    Score,
+    Depth
+}
+
+impl RunSortOrder {
+    pub fn next(&self) -> RunSortOrder {
+        match self {
+            RunSortOrder::Score => RunSortOrder::Depth,
+            RunSortOrder::Depth => RunSortOrder::Score
+        }
+    }
+}
+
+/*
+ * A summary of a single finished run, as shown on the leaderboard and it's morgue screen
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    pub character_name: String,
+    pub race: String,
+    pub class: String,
+    pub seed: String,
+    pub deepest_level: usize,
+    pub loot_value: i32,
+    pub turns: u64,
+    pub outcome: RunOutcome,
+    pub inventory: Vec<String>
+}
+
+impl RunRecord {
+    pub fn from_player(player: &mut Character, seed: String, deepest_level: usize, turns: u64, outcome: RunOutcome) -> RunRecord {
+        // True names, even for anything never identified during the run
+        let inventory = player.get_inventory_mut().get_contents().iter()
+            .map(|c| c.get_self_item().get_name())
+            .collect();
+        RunRecord {
+            character_name: player.get_name(),
+            race: format!("{:?}", player.get_race()),
+            class: player.get_class().to_string(),
+            seed,
+            deepest_level,
+            loot_value: player.get_inventory_mut().get_loot_value(),
+            turns,
+            outcome,
+            inventory
+        }
+    }
+
+    pub fn get_score(&self) -> i32 {
+        self.loot_value + self.deepest_level as i32 * DEPTH_SCORE
+    }
+
+    fn to_json(&self) -> Value {
+        let (outcome, cause) = match &self.outcome {
+            RunOutcome::Escaped => ("Escaped", None),
+            RunOutcome::Quit => ("Quit", None),
+            RunOutcome::Died(cause) => ("Died", Some(cause.clone()))
+        };
+        json!({
+            "character_name": self.character_name,
+            "race": self.race,
+            "class": self.class,
+            "seed": self.seed,
+            "deepest_level": self.deepest_level,
+            "loot_value": self.loot_value,
+            "turns": self.turns,
+            "outcome": outcome,
+            "cause_of_death": cause,
+            "inventory": self.inventory
+        })
+    }
+
+    fn from_json(value: &Value) -> Result<RunRecord, ErrorWrapper> {
+        let outcome = match json_str(value, "outcome")?.as_str() {
+            "Escaped" => RunOutcome::Escaped,
+            "Quit" => RunOutcome::Quit,
+            "Died" => RunOutcome::Died(json_str(value, "cause_of_death")?),
+            other => return ErrorWrapper::internal_result(format!("Unknown run outcome: {}", other))
+        };
+        let inventory = value.get("inventory").and_then(|i| i.as_array())
+            .map(|items| items.iter().filter_map(|i| i.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        Ok(RunRecord {
+            character_name: json_str(value, "character_name")?,
+            race: json_str(value, "race")?,
+            class: json_str(value, "class")?,
+            seed: json_str(value, "seed")?,
+            deepest_level: json_u64(value, "deepest_level")? as usize,
+            loot_value: value.get("loot_value").and_then(|v| v.as_i64()).unwrap_or(0) as i32,
+            turns: json_u64(value, "turns")?,
+            outcome,
+            inventory
+        })
+    }
+}
+
+/*
+ * Every run recorded so far, persisted between games as JSON
+ */
+#[derive(Clone, Debug, Default)]
+pub struct RunHistory {
+    runs: Vec<RunRecord>
+}
+
+impl RunHistory {
+    pub fn get_runs(&self) -> &Vec<RunRecord> {
+        &self.runs
+    }
+
+    pub fn add_run(&mut self, run: RunRecord) {
+        self.runs.push(run);
+    }
+
+    // Best runs first, ties go to the faster run
+    pub fn sorted_by(&self, order: RunSortOrder) -> Vec<RunRecord> {
+        let mut runs = self.runs.clone();
+        match order {
+            RunSortOrder::Score => runs.sort_by(|a, b| b.get_score().cmp(&a.get_score()).then(a.turns.cmp(&b.turns))),
+            RunSortOrder::Depth => runs.sort_by(|a, b| b.deepest_level.cmp(&a.deepest_level).then(b.get_score().cmp(&a.get_score())))
+        }
+        runs
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({ "runs": self.runs.iter().map(|r| r.to_json()).collect::<Vec<Value>>() })
+    }
+
+    pub fn from_json(value: &Value) -> Result<RunHistory, ErrorWrapper> {
+        let runs = value.get("runs").and_then(|r| r.as_array())
+            .ok_or(ErrorWrapper::new_internal(String::from("Run history is missing it's runs")))?;
+        let runs = runs.iter().map(RunRecord::from_json).collect::<Result<Vec<RunRecord>, ErrorWrapper>>()?;
+        Ok(RunHistory { runs })
+    }
+}
+
+/*
+ * A missing history file just means no runs have finished yet
+ * An unreadable one is treated the same, rather than stopping the leaderboard or the end of a run
+ */
+pub fn load_run_history(path: &str) -> RunHistory {
+    if !Path::new(path).exists() {
+        return RunHistory::default();
+    }
+    match read_run_history(path) {
+        Ok(history) => history,
+        Err(e) => {
+            log::warn!("Failed to load run history from: {}, {}. Treating it as empty.", path, e);
+            RunHistory::default()
+        }
+    }
+}
+
+fn read_run_history(path: &str) -> Result<RunHistory, ErrorWrapper> {
+    let raw = fs::read_to_string(path)?;
+    let value: Value = serde_json::from_str(&raw)
+        .map_err(|e| ErrorWrapper::new_internal(format!("Failed to parse run history: {}", e)))?;
+    RunHistory::from_json(&value)
+}
+
+pub fn save_run_history(path: &str, history: &RunHistory) -> Result<(), ErrorWrapper> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json_string = serde_json::to_string_pretty(&history.to_json())
+        .map_err(|e| ErrorWrapper::new_internal(format!("Failed to serialise run history: {}", e)))?;
+    fs::write(path, json_string)?;
+    Ok(())
+}
+
+// Adds the run to the history on disk, returning the updated history
+pub fn record_run(path: &str, run: RunRecord) -> Result<RunHistory, ErrorWrapper> {
+    let mut history = load_run_history(path);
+    log::info!("Recording run for {}: {}", run.character_name, run.outcome.describe());
+    history.add_run(run);
+    save_run_history(path, &history)?;
+    Ok(history)
+}
+
+fn json_str(value: &Value, key: &str) -> Result<String, ErrorWrapper> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+        .ok_or(ErrorWrapper::new_internal(format!("Run is missing the '{}' field", key)))
+}
+
+fn json_u64(value: &Value, key: &str) -> Result<u64, ErrorWrapper> {
+    value.get(key).and_then(|v| v.as_u64())
+        .ok_or(ErrorWrapper::new_internal(format!("Run is missing the '{}' field", key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::engine::run_history::{load_run_history, record_run, RunHistory, RunOutcome, RunRecord, RunSortOrder};
+
+    fn build_run(name: &str, deepest_level: usize, loot_value: i32, outcome: RunOutcome) -> RunRecord {
+        RunRecord {
+            character_name: name.to_string(),
+            race: String::from("Human"),
+            class: String::from("Warrior"),
+            seed: String::from("seed"),
+            deepest_level,
+            loot_value,
+            turns: 100,
+            outcome,
+            inventory: vec![String::from("Steel Sword")]
+        }
+    }
+
+    #[test]
+    fn test_sorted_by() {
+        // GIVEN a history with a deep run and a rich run
+        let mut history = RunHistory::default();
+        history.add_run(build_run("Deep", 5, 0, RunOutcome::Died(String::from("Ruggo"))));
+        history.add_run(build_run("Rich", 1, 1000, RunOutcome::Escaped));
+
+        // WHEN we sort by score
+        // THEN the rich run comes first
+        assert_eq!("Rich", history.sorted_by(RunSortOrder::Score)[0].character_name);
+
+        // AND when sorting by depth the deep run comes first
+        assert_eq!("Deep", history.sorted_by(RunSortOrder::Depth)[0].character_name);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        // GIVEN a history holding an escaped, a quit and a fatal run
+        let mut history = RunHistory::default();
+        history.add_run(build_run("Escapee", 2, 50, RunOutcome::Escaped));
+        history.add_run(build_run("Quitter", 0, 5, RunOutcome::Quit));
+        history.add_run(build_run("Victim", 3, 10, RunOutcome::Died(String::from("Snikk"))));
+
+        // WHEN we convert it to and back from JSON
+        let parsed = RunHistory::from_json(&history.to_json()).unwrap();
+
+        // THEN we expect the same runs back
+        assert_eq!(history.get_runs(), parsed.get_runs());
+    }
+
+    #[test]
+    fn test_record_run() {
+        // GIVEN no existing history file
+        let path = std::env::temp_dir().join(format!("arq-run-history-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        assert!(load_run_history(path).get_runs().is_empty());
+
+        // WHEN we record two runs
+        record_run(path, build_run("First", 1, 0, RunOutcome::Escaped)).unwrap();
+        record_run(path, build_run("Second", 2, 0, RunOutcome::Escaped)).unwrap();
+
+        // THEN both are persisted
+        let history = load_run_history(path);
+        assert_eq!(2, history.get_runs().len());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_record_run_over_corrupt_history() {
+        // GIVEN a history file that isn't valid JSON
+        let path = std::env::temp_dir().join(format!("arq-run-history-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "{ not json").unwrap();
+
+        // WHEN we load it
+        // THEN it's treated as empty
+        assert!(load_run_history(path).get_runs().is_empty());
+
+        // AND recording a run replaces it with a valid history
+        record_run(path, build_run("First", 1, 0, RunOutcome::Escaped)).unwrap();
+        assert_eq!(1, load_run_history(path).get_runs().len());
+        fs::remove_file(path).unwrap();
+    }
+}
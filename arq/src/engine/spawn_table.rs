@@ -0,0 +1,160 @@
+use rand::Rng;
+use rand_pcg::Pcg64;
+
+use crate::character::builder::character_builder::{CharacterBuilder, CharacterPattern, CharacterType};
+use crate::character::Character;
+use crate::error::errors::ErrorWrapper;
+
+const GOBLIN_NAMES: [&str; 8] = ["Ruggo", "Snikk", "Grub", "Nagg", "Skab", "Urzo", "Brak", "Mott"];
+
+// How many turns a left behind level needs to be away for a single NPC to respawn
+const BASE_RESPAWN_TURNS: u64 = 200;
+
+/*
+ * A single kind of NPC that can be spawned, weighted against the other entries of the table
+ */
+#[derive(Clone, Debug)]
+pub struct SpawnEntry {
+    pub character_type: CharacterType,
+    pub names: Vec<String>,
+    pub weight: u32,
+    // The first dungeon depth (0 being the first level) this entry can spawn at
+    pub min_depth: usize
+}
+
+/*
+ * Describes which NPCs populate a level and how quickly they come back once killed
+ */
+#[derive(Clone, Debug, Default)]
+pub struct SpawnTable {
+    entries: Vec<SpawnEntry>,
+    min_npcs: usize,
+    max_npcs: usize,
+    respawn_turns: u64
+}
+
+pub fn build_spawn_table(depth: usize) -> SpawnTable {
+    let goblin_names: Vec<String> = GOBLIN_NAMES.iter().map(|n| n.to_string()).collect();
+    let entries = vec![
+        SpawnEntry { character_type: CharacterType::GoblinWarrior, names: goblin_names, weight: 1, min_depth: 0 }
+    ];
+
+    // Deeper levels are busier and recover from losses more quickly
+    let respawn_turns = BASE_RESPAWN_TURNS / (depth as u64 + 1);
+    SpawnTable::new(entries, 1 + depth / 2, 2 + depth, respawn_turns.max(1))
+}
+
+impl SpawnTable {
+    pub fn new(entries: Vec<SpawnEntry>, min_npcs: usize, max_npcs: usize, respawn_turns: u64) -> SpawnTable {
+        SpawnTable { entries, min_npcs, max_npcs, respawn_turns }
+    }
+
+    pub fn get_max_npcs(&self) -> usize {
+        self.max_npcs
+    }
+
+    pub fn get_respawn_turns(&self) -> u64 {
+        self.respawn_turns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn roll_npc_count(&self, rng: &mut Pcg64) -> usize {
+        if self.is_empty() || self.max_npcs == 0 {
+            return 0;
+        }
+        rng.random_range(self.min_npcs.min(self.max_npcs)..=self.max_npcs)
+    }
+
+    /*
+     * Picks a weighted random entry available at the given depth and builds an NPC from it
+     * Returns None if nothing can spawn at this depth
+     */
+    pub fn spawn_npc(&self, rng: &mut Pcg64, depth: usize) -> Result<Option<Character>, ErrorWrapper> {
+        let available: Vec<&SpawnEntry> = self.entries.iter().filter(|e| e.min_depth <= depth && e.weight > 0).collect();
+        let total_weight: u32 = available.iter().map(|e| e.weight).sum();
+        if total_weight == 0 {
+            return Ok(None);
+        }
+
+        let mut roll = rng.random_range(0..total_weight);
+        for entry in available {
+            if roll < entry.weight {
+                let pattern = CharacterPattern::for_type(entry.character_type.clone())?;
+                let name = if entry.names.is_empty() {
+                    format!("{:?}", entry.character_type)
+                } else {
+                    entry.names[rng.random_range(0..entry.names.len())].clone()
+                };
+                return Ok(Some(CharacterBuilder::new(pattern).build(name)));
+            }
+            roll -= entry.weight;
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_seeder::Seeder;
+    use rand_pcg::Pcg64;
+
+    use crate::character::builder::character_builder::CharacterType;
+    use crate::engine::spawn_table::{build_spawn_table, SpawnEntry, SpawnTable};
+
+    #[test]
+    fn test_build_spawn_table_depth_scaling() {
+        // GIVEN spawn tables for the first and a deeper level
+        let first = build_spawn_table(0);
+        let deeper = build_spawn_table(4);
+
+        // THEN the deeper level allows more NPCs and respawns them sooner
+        assert!(deeper.get_max_npcs() > first.get_max_npcs());
+        assert!(deeper.get_respawn_turns() < first.get_respawn_turns());
+    }
+
+    #[test]
+    fn test_roll_npc_count_in_range() {
+        // GIVEN a spawn table allowing 2 to 4 NPCs
+        let entry = SpawnEntry { character_type: CharacterType::GoblinWarrior, names: vec!["Ruggo".to_string()], weight: 1, min_depth: 0 };
+        let table = SpawnTable::new(vec![entry], 2, 4, 10);
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we roll the NPC count several times
+        // THEN it always falls within the range
+        for _ in 0..20 {
+            let count = table.roll_npc_count(&mut rng);
+            assert!((2..=4).contains(&count));
+        }
+    }
+
+    #[test]
+    fn test_spawn_npc() {
+        // GIVEN the spawn table for the first level
+        let table = build_spawn_table(0);
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we spawn an NPC
+        let npc = table.spawn_npc(&mut rng, 0).unwrap();
+
+        // THEN we expect a goblin
+        assert!(npc.is_some());
+        assert_eq!('g', npc.unwrap().get_symbol());
+    }
+
+    #[test]
+    fn test_spawn_npc_below_min_depth() {
+        // GIVEN a spawn table whose only entry requires depth 3
+        let entry = SpawnEntry { character_type: CharacterType::GoblinWarrior, names: Vec::new(), weight: 1, min_depth: 3 };
+        let table = SpawnTable::new(vec![entry], 1, 1, 10);
+        let mut rng: Pcg64 = Seeder::from("test").into_rng();
+
+        // WHEN we try to spawn an NPC on the first level
+        let npc = table.spawn_npc(&mut rng, 0).unwrap();
+
+        // THEN nothing is spawned
+        assert!(npc.is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+
+use crate::map::position::{Direction, Position};
+
+/*
+ * Where the player is travelling towards over several turns
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum TravelGoal {
+    // Keep heading for the nearest place the player has yet to see
+    Explore,
+    // Keep stepping in a direction until there's something worth stopping for
+    Run(Direction),
+    Position(Position)
+}
+
+/*
+ * Anything that should stop the player travelling any further
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum TravelInterruption {
+    KeyPressed,
+    NpcInView,
+    ItemFound,
+    HealthDropped,
+    // An NPC standing on the next step, which travel never walks into
+    Blocked
+}
+
+impl TravelInterruption {
+    pub fn get_message(&self) -> String {
+        String::from(match self {
+            TravelInterruption::KeyPressed => "You stop.",
+            TravelInterruption::NpcInView => "You stop, there's someone nearby!",
+            TravelInterruption::ItemFound => "You stop, you've spotted something.",
+            TravelInterruption::HealthDropped => "You stop, you're hurt!",
+            TravelInterruption::Blocked => "You stop, there's someone in the way."
+        })
+    }
+}
+
+/*
+ * Travel in progress, taken one step per turn until it either arrives or is interrupted
+ */
+#[derive(Debug, Clone)]
+pub struct Travel {
+    goal: TravelGoal,
+    path: VecDeque<Position>,
+    steps_taken: usize,
+    // The player's health when they last moved, so any drop in it can be noticed
+    health: u16,
+    npc_in_view: bool,
+    found_item: bool
+}
+
+impl Travel {
+    pub fn new(goal: TravelGoal, health: u16, npc_in_view: bool) -> Travel {
+        Travel { goal, path: VecDeque::new(), steps_taken: 0, health, npc_in_view, found_item: false }
+    }
+
+    pub fn get_goal(&self) -> &TravelGoal {
+        &self.goal
+    }
+
+    pub fn set_path(&mut self, path: VecDeque<Position>) {
+        self.path = path;
+    }
+
+    pub fn next_step(&mut self) -> Option<Position> {
+        let step = self.path.pop_front();
+        if step.is_some() {
+            self.steps_taken += 1;
+        }
+        step
+    }
+
+    pub fn get_steps_taken(&self) -> usize {
+        self.steps_taken
+    }
+
+    // Whether the given position is where this travel is headed, or right beside it
+    pub fn is_heading_for(&self, position: Position) -> bool {
+        match self.goal {
+            TravelGoal::Position(target) => target.chebyshev_distance(position) <= 1,
+            _ => false
+        }
+    }
+
+    pub fn set_found_item(&mut self, found_item: bool) {
+        self.found_item = found_item;
+    }
+
+    // Checks what's happened since the last step, remembering the latest state for the next check
+    pub fn check_interruption(&mut self, health: u16, npc_in_view: bool) -> Option<TravelInterruption> {
+        let interruption = if health < self.health {
+            Some(TravelInterruption::HealthDropped)
+        } else if npc_in_view && !self.npc_in_view {
+            Some(TravelInterruption::NpcInView)
+        } else if self.found_item {
+            Some(TravelInterruption::ItemFound)
+        } else {
+            None
+        };
+        self.health = health;
+        self.npc_in_view = npc_in_view;
+        self.found_item = false;
+        interruption
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::travel::{Travel, TravelGoal, TravelInterruption};
+    use crate::map::position::{Direction, Position};
+
+    #[test]
+    fn test_is_heading_for() {
+        // GIVEN travel to a position
+        let travel = Travel::new(TravelGoal::Position(Position::new(5, 5)), 10, false);
+
+        // WHEN we check positions at, beside and away from it
+        // THEN only those at or beside it are being headed for
+        assert!(travel.is_heading_for(Position::new(5, 5)));
+        assert!(travel.is_heading_for(Position::new(6, 4)));
+        assert!(!travel.is_heading_for(Position::new(7, 5)));
+
+        // AND nowhere is being headed for when running or exploring
+        assert!(!Travel::new(TravelGoal::Run(Direction::LEFT), 10, false).is_heading_for(Position::new(5, 5)));
+        assert!(!Travel::new(TravelGoal::Explore, 10, false).is_heading_for(Position::new(5, 5)));
+    }
+
+    #[test]
+    fn test_check_interruption() {
+        // GIVEN travel started at full health with no one in view
+        let mut travel = Travel::new(TravelGoal::Explore, 10, false);
+
+        // WHEN nothing has changed
+        // THEN there's no interruption
+        assert_eq!(None, travel.check_interruption(10, false));
+
+        // WHEN an NPC comes into view
+        // THEN travel is interrupted, but only when they first appear
+        assert_eq!(Some(TravelInterruption::NpcInView), travel.check_interruption(10, true));
+        assert_eq!(None, travel.check_interruption(10, true));
+
+        // WHEN an item is found
+        travel.set_found_item(true);
+        // THEN travel is interrupted
+        assert_eq!(Some(TravelInterruption::ItemFound), travel.check_interruption(10, true));
+
+        // WHEN the player is hurt
+        // THEN travel is interrupted
+        assert_eq!(Some(TravelInterruption::HealthDropped), travel.check_interruption(8, true));
+        assert_eq!(None, travel.check_interruption(8, true));
+    }
+}
@@ -0,0 +1,152 @@
+use rand_seeder::Seeder;
+
+use crate::engine::level::{init_level_manager, Levels};
+use crate::map::overworld_generator::Overworld;
+use crate::map::position::Position;
+
+const DUNGEON_NAMES: [&str; 5] = ["the Goblin Warrens", "the Old Barrow", "the Sunken Crypt", "the Deep Mines", "the Forgotten Keep"];
+
+/*
+ * A dungeon found on the overworld, with it's own stack of levels
+ * The levels are only built once the player first goes in, and are held by the GameEngine while the player is inside
+ */
+pub struct Dungeon {
+    name: String,
+    entrance: Position,
+    levels: Option<Levels>
+}
+
+impl Dungeon {
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/*
+ * The campaign, made up of the overworld and every dungeon on it
+ * Only one set of Levels is in play at a time (the GameEngine's), the others are kept here until the player returns
+ */
+pub struct World {
+    seed: String,
+    dungeons: Vec<Dungeon>,
+    // Held here while the player is inside a dungeon
+    overworld: Option<Levels>,
+    current_dungeon: Option<usize>
+}
+
+pub fn build_world(seed: String, overworld: &Overworld) -> World {
+    let dungeons = overworld.dungeon_entrances.iter().enumerate()
+        .map(|(i, entrance)| {
+            let name = DUNGEON_NAMES.get(i).map(|n| n.to_string()).unwrap_or(format!("Dungeon {}", i + 1));
+            Dungeon { name, entrance: *entrance, levels: None }
+        })
+        .collect();
+    World { seed, dungeons, overworld: None, current_dungeon: None }
+}
+
+impl World {
+    pub fn get_seed(&self) -> String {
+        self.seed.clone()
+    }
+
+    pub fn get_dungeons(&self) -> &Vec<Dungeon> {
+        &self.dungeons
+    }
+
+    pub fn is_in_dungeon(&self) -> bool {
+        self.current_dungeon.is_some()
+    }
+
+    pub fn find_dungeon_at(&self, position: Position) -> Option<usize> {
+        self.dungeons.iter().position(|d| d.entrance == position)
+    }
+
+    /*
+     * Swaps the overworld levels out for those of the given dungeon
+     * A dungeon being entered for the first time has no levels yet, it's first level needs adding by the caller
+     */
+    pub fn enter_dungeon(&mut self, index: usize, levels: &mut Levels) {
+        let dungeon = &mut self.dungeons[index];
+        let mut dungeon_levels = dungeon.levels.take().unwrap_or_else(|| {
+            // Each dungeon has it's own seed derived from the world's, so they generate independently of visiting order
+            let dungeon_seed = format!("{}-{}", self.seed, index);
+            init_level_manager(dungeon_seed.clone(), Seeder::from(dungeon_seed).into_rng())
+        });
+        dungeon_levels.sync_turn(levels.get_turn());
+        self.overworld = Some(std::mem::replace(levels, dungeon_levels));
+        self.current_dungeon = Some(index);
+    }
+
+    /*
+     * Swaps the current dungeon's levels back out for the overworld
+     * Returns the position of the dungeon's entrance to place the player at, or None if not in a dungeon
+     */
+    pub fn leave_dungeon(&mut self, levels: &mut Levels) -> Option<Position> {
+        let index = self.current_dungeon?;
+        let mut overworld = self.overworld.take()?;
+        overworld.sync_turn(levels.get_turn());
+        self.dungeons[index].levels = Some(std::mem::replace(levels, overworld));
+        self.current_dungeon = None;
+        Some(self.dungeons[index].entrance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_seeder::Seeder;
+
+    use crate::character::characters::Characters;
+    use crate::engine::level::{init_level_manager, Level, Levels};
+    use crate::engine::world::build_world;
+    use crate::map::overworld_generator::{build_overworld_generator, Overworld};
+    use crate::map::position::{build_rectangular_area, Position};
+
+    fn build_overworld_levels() -> (Levels, Overworld) {
+        let mut levels = init_level_manager("test".to_string(), Seeder::from("test").into_rng());
+        let map_area = build_rectangular_area(Position::zero(), 60, 30);
+        let overworld = build_overworld_generator(&mut levels.rng, map_area, 2).generate();
+        levels.add_level_directly(Level::new(Some(overworld.map.clone()), Characters::default()));
+        (levels, overworld)
+    }
+
+    #[test]
+    fn test_build_world() {
+        // GIVEN a generated overworld
+        let (_levels, overworld) = build_overworld_levels();
+
+        // WHEN we build the world for it
+        let world = build_world("test".to_string(), &overworld);
+
+        // THEN there's a named dungeon at each entrance
+        assert_eq!(overworld.dungeon_entrances.len(), world.get_dungeons().len());
+        let first_entrance = overworld.dungeon_entrances[0];
+        assert_eq!(Some(0), world.find_dungeon_at(first_entrance));
+        assert_eq!("the Goblin Warrens", world.get_dungeons()[0].get_name());
+        assert_eq!(None, world.find_dungeon_at(overworld.town_centre));
+        assert!(!world.is_in_dungeon());
+    }
+
+    #[test]
+    fn test_enter_and_leave_dungeon() {
+        // GIVEN a world, with the overworld in play
+        let (mut levels, overworld) = build_overworld_levels();
+        let mut world = build_world("test".to_string(), &overworld);
+
+        // WHEN we enter the first dungeon
+        world.enter_dungeon(0, &mut levels);
+
+        // THEN we're given it's (not yet built) levels, with their own seed
+        assert!(world.is_in_dungeon());
+        assert!(levels.is_empty());
+        assert_eq!("test-0", levels.get_seed());
+
+        // WHEN we leave the dungeon again
+        let entrance = world.leave_dungeon(&mut levels);
+
+        // THEN the overworld is back in play and we're pointed at the dungeon entrance
+        assert!(!world.is_in_dungeon());
+        assert!(!levels.is_empty());
+        assert_eq!("test", levels.get_seed());
+        assert_eq!(Some(overworld.dungeon_entrances[0]), entrance);
+    }
+}
@@ -1,28 +1,124 @@
 use std::any::Any;
 use std::collections::VecDeque;
 use std::io;
+
 use termion::event::Key;
-use termion::input::TermRead;
+
+use crate::input::event_source::EventSource;
+use crate::map::position::Position;
+
+pub mod event_source;
+pub mod session;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollDirection {
+    Up,
+    Down
+}
+
+/*
+ * Anything the player can do to the game through the terminal
+ * Clicks are terminal positions, it's up to whatever's drawn there to work out what was clicked
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    Key(Key),
+    // A left click
+    Click(Position),
+    Scroll(ScrollDirection),
+    // The terminal's new width and height
+    Resize(u16, u16)
+}
+
+impl InputEvent {
+    // The key this event stands for, for anywhere that only reads keys
+    pub fn as_key(&self) -> Option<Key> {
+        match self {
+            InputEvent::Key(key) => Some(*key),
+            _ => None
+        }
+    }
+
+    /*
+     * The same, but with scrolling paging through the list like PageUp/PageDown
+     * Only for views showing a list, elsewhere PageUp/PageDown may mean something else entirely (i.e diagonal moves)
+     */
+    pub fn as_paging_key(&self) -> Option<Key> {
+        match self {
+            InputEvent::Scroll(ScrollDirection::Up) => Some(Key::PageUp),
+            InputEvent::Scroll(ScrollDirection::Down) => Some(Key::PageDown),
+            _ => self.as_key()
+        }
+    }
+}
 
 pub trait KeyInputResolver {
     fn get_input_key(&mut self) -> Result<Key, io::Error>;
     fn get_or_return_input_key(&mut self, input : Option<Key>) -> Result<Key, io::Error>;
 
+    // Only sources that know about the mouse or terminal give anything other than keys
+    fn get_input_event(&mut self) -> Result<InputEvent, io::Error> {
+        self.get_input_key().map(InputEvent::Key)
+    }
+
+    // Whether anything's been pressed or clicked to interrupt the game i.e while travelling, without waiting for it
+    fn poll_interrupt(&mut self) -> Result<bool, io::Error> {
+        Ok(false)
+    }
+
+    /*
+     * Called with the seed of each new game, returning the seed that should actually be used
+     * Recording sessions remember the seed and replays hand back the recorded one
+     */
+    fn sync_seed(&mut self, seed: String) -> String {
+        seed
+    }
+
     fn as_any(&self) -> &dyn Any;
     
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
-#[derive(Clone)]
-pub struct IoKeyInputResolver {}
+// Skips anything that doesn't stand for a key, see InputEvent::as_key
+pub(crate) fn read_terminal_key(events: &mut dyn EventSource) -> Result<Key, io::Error> {
+    loop {
+        if let Some(key) = events.read_event()?.as_key() {
+            return Ok(key);
+        }
+    }
+}
+
+// Any key press or click waiting counts as an interrupt, it's used up rather than acted on
+pub(crate) fn poll_terminal_interrupt(events: &mut dyn EventSource) -> Result<bool, io::Error> {
+    let event = events.poll_event()?;
+    Ok(matches!(event, Some(InputEvent::Key(_)) | Some(InputEvent::Click(_))))
+}
 
-fn get_input_key() -> Result<Key, io::Error> {
-    io::stdin().keys().next().unwrap()
+/*
+ * Reads straight from the terminal, this is the game's input unless a session is being recorded or replayed
+ * The events should come from whichever terminal the game is drawn to i.e scripted events for the TestBackend
+ */
+pub struct IoKeyInputResolver {
+    events: Box<dyn EventSource>
+}
+
+impl IoKeyInputResolver {
+    pub fn new(events: Box<dyn EventSource>) -> IoKeyInputResolver {
+        IoKeyInputResolver { events }
+    }
 }
 
 impl KeyInputResolver for IoKeyInputResolver {
     fn get_input_key(&mut self) -> Result<Key, io::Error> {
-        Ok(get_input_key()?)
+        read_terminal_key(self.events.as_mut())
+    }
+
+    fn get_input_event(&mut self) -> Result<InputEvent, io::Error> {
+        self.events.read_event()
+    }
+
+    fn poll_interrupt(&mut self) -> Result<bool, io::Error> {
+        poll_terminal_interrupt(self.events.as_mut())
     }
     
     fn get_or_return_input_key(&mut self, input : Option<Key>) -> Result<Key, io::Error> {
@@ -31,7 +127,7 @@ impl KeyInputResolver for IoKeyInputResolver {
                 Ok(input_key)
             },
             _ => {
-                Ok(get_input_key()?)
+                read_terminal_key(self.events.as_mut())
             }
         }
     }
@@ -75,4 +171,35 @@ impl KeyInputResolver for MockKeyInputResolver {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::event::Key;
+
+    use crate::input::{InputEvent, ScrollDirection};
+    use crate::map::position::Position;
+
+    #[test]
+    fn test_as_key() {
+        // GIVEN each kind of event
+        // WHEN we ask for the key it stands for
+        // THEN only key presses have one
+        assert_eq!(Some(Key::Esc), InputEvent::Key(Key::Esc).as_key());
+        assert_eq!(None, InputEvent::Scroll(ScrollDirection::Up).as_key());
+        assert_eq!(None, InputEvent::Click(Position::new(1, 1)).as_key());
+        assert_eq!(None, InputEvent::Resize(80, 24).as_key());
+    }
+
+    #[test]
+    fn test_as_paging_key() {
+        // GIVEN each kind of event
+        // WHEN we ask for the key it stands for in a list
+        // THEN scrolling pages, and clicks and resizes have none
+        assert_eq!(Some(Key::Esc), InputEvent::Key(Key::Esc).as_paging_key());
+        assert_eq!(Some(Key::PageUp), InputEvent::Scroll(ScrollDirection::Up).as_paging_key());
+        assert_eq!(Some(Key::PageDown), InputEvent::Scroll(ScrollDirection::Down).as_paging_key());
+        assert_eq!(None, InputEvent::Click(Position::new(1, 1)).as_paging_key());
+        assert_eq!(None, InputEvent::Resize(80, 24).as_paging_key());
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use termion::event::Key;
+
+use crate::input::{InputEvent, ScrollDirection};
+use crate::map::position::Position;
+
+/*
+ * Where the terminal's input comes from
+ * This is crossterm when playing, or a script of events when testing
+ */
+pub trait EventSource {
+    // Blocks until there's an event the game understands
+    fn read_event(&mut self) -> Result<InputEvent, io::Error>;
+
+    // Any event the game understands that's already waiting, without blocking
+    fn poll_event(&mut self) -> Result<Option<InputEvent>, io::Error>;
+}
+
+pub struct CrosstermEventSource {}
+
+impl EventSource for CrosstermEventSource {
+    fn read_event(&mut self) -> Result<InputEvent, io::Error> {
+        loop {
+            if let Some(event) = to_input_event(crossterm::event::read()?) {
+                return Ok(event);
+            }
+        }
+    }
+
+    fn poll_event(&mut self) -> Result<Option<InputEvent>, io::Error> {
+        while crossterm::event::poll(Duration::ZERO)? {
+            if let Some(event) = to_input_event(crossterm::event::read()?) {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// Plays out the given events in order, failing with UnexpectedEof once they run out
+pub struct ScriptedEventSource {
+    events: VecDeque<InputEvent>
+}
+
+impl ScriptedEventSource {
+    pub fn new(events: Vec<InputEvent>) -> ScriptedEventSource {
+        ScriptedEventSource { events: VecDeque::from(events) }
+    }
+}
+
+impl EventSource for ScriptedEventSource {
+    fn read_event(&mut self) -> Result<InputEvent, io::Error> {
+        self.events.pop_front().ok_or(io::Error::new(io::ErrorKind::UnexpectedEof, "The scripted events have run out"))
+    }
+
+    // Scripted events only ever come when they're read, so there's never anything waiting
+    fn poll_event(&mut self) -> Result<Option<InputEvent>, io::Error> {
+        Ok(None)
+    }
+}
+
+pub(crate) fn to_input_event(event: Event) -> Option<InputEvent> {
+    match event {
+        Event::Key(key_event) if key_event.kind != KeyEventKind::Release => to_key(key_event).map(InputEvent::Key),
+        Event::Mouse(mouse_event) => match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(InputEvent::Click(Position::new(mouse_event.column, mouse_event.row))),
+            MouseEventKind::ScrollUp => Some(InputEvent::Scroll(ScrollDirection::Up)),
+            MouseEventKind::ScrollDown => Some(InputEvent::Scroll(ScrollDirection::Down)),
+            _ => None
+        },
+        Event::Resize(width, height) => Some(InputEvent::Resize(width, height)),
+        _ => None
+    }
+}
+
+// The rest of the game still speaks in termion keys
+fn to_key(key_event: KeyEvent) -> Option<Key> {
+    let key = match key_event.code {
+        KeyCode::Char(c) if key_event.modifiers.contains(KeyModifiers::CONTROL) => Key::Ctrl(c),
+        KeyCode::Char(c) if key_event.modifiers.contains(KeyModifiers::ALT) => Key::Alt(c),
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Char('\n'),
+        KeyCode::Tab => Key::Char('\t'),
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Up if key_event.modifiers.contains(KeyModifiers::SHIFT) => Key::ShiftUp,
+        KeyCode::Down if key_event.modifiers.contains(KeyModifiers::SHIFT) => Key::ShiftDown,
+        KeyCode::Left if key_event.modifiers.contains(KeyModifiers::SHIFT) => Key::ShiftLeft,
+        KeyCode::Right if key_event.modifiers.contains(KeyModifiers::SHIFT) => Key::ShiftRight,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n) => Key::F(n),
+        _ => return None
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use termion::event::Key;
+
+    use std::io;
+
+    use crate::input::event_source::{to_input_event, EventSource, ScriptedEventSource};
+    use crate::input::{InputEvent, ScrollDirection};
+    use crate::map::position::Position;
+
+    fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE })
+    }
+
+    #[test]
+    fn test_to_input_event_keys() {
+        // GIVEN some key presses from the terminal
+        // WHEN we convert them
+        // THEN we expect the matching keys
+        assert_eq!(Some(InputEvent::Key(Key::Char('w'))), to_input_event(Event::Key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE))));
+        assert_eq!(Some(InputEvent::Key(Key::Char('\n'))), to_input_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))));
+        assert_eq!(Some(InputEvent::Key(Key::Ctrl('c'))), to_input_event(Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))));
+        assert_eq!(Some(InputEvent::Key(Key::ShiftLeft)), to_input_event(Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT))));
+
+        // AND key releases are ignored
+        let release = KeyEvent::new_with_kind(KeyCode::Char('w'), KeyModifiers::NONE, KeyEventKind::Release);
+        assert_eq!(None, to_input_event(Event::Key(release)));
+    }
+
+    #[test]
+    fn test_to_input_event_mouse() {
+        // GIVEN a left click, a scroll and a right click
+        // WHEN we convert them
+        // THEN we expect the click position and scroll direction
+        assert_eq!(Some(InputEvent::Click(Position::new(10, 5))), to_input_event(mouse_event(MouseEventKind::Down(MouseButton::Left), 10, 5)));
+        assert_eq!(Some(InputEvent::Scroll(ScrollDirection::Up)), to_input_event(mouse_event(MouseEventKind::ScrollUp, 0, 0)));
+        // AND anything else is ignored
+        assert_eq!(None, to_input_event(mouse_event(MouseEventKind::Down(MouseButton::Right), 10, 5)));
+    }
+
+    #[test]
+    fn test_scripted_events() {
+        // GIVEN a script of a click then a key
+        let mut source = ScriptedEventSource::new(vec![InputEvent::Click(Position::new(1, 2)), InputEvent::Key(Key::Esc)]);
+
+        // WHEN we read the events
+        // THEN we get each in order
+        assert_eq!(InputEvent::Click(Position::new(1, 2)), source.read_event().unwrap());
+        assert_eq!(InputEvent::Key(Key::Esc), source.read_event().unwrap());
+        // AND then an error once the script runs out
+        assert_eq!(io::ErrorKind::UnexpectedEof, source.read_event().unwrap_err().kind());
+    }
+}
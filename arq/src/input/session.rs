@@ -0,0 +1,551 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use termion::event::Key;
+
+use crate::input::event_source::EventSource;
+use crate::input::{poll_terminal_interrupt, read_terminal_key, InputEvent, IoKeyInputResolver, KeyInputResolver, ScrollDirection};
+use crate::map::position::Position;
+
+const SESSION_HEADER: &str = "# ARQ session v1";
+
+/*
+ * A recorded session is the seed of each game played, interleaved with every key pressed
+ * Seeded generation means replaying these in order reproduces the session exactly
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionEntry {
+    Seed(String),
+    Key(Key),
+    Click(Position),
+    Scroll(ScrollDirection),
+    // Something pressed while travelling, after the given number of checks that found nothing
+    Interrupt(u32)
+}
+
+impl SessionEntry {
+    // Resizes aren't recorded, a replay draws at whatever size it's given
+    pub fn from_event(event: InputEvent) -> Option<SessionEntry> {
+        match event {
+            InputEvent::Key(key) => Some(SessionEntry::Key(key)),
+            InputEvent::Click(position) => Some(SessionEntry::Click(position)),
+            InputEvent::Scroll(direction) => Some(SessionEntry::Scroll(direction)),
+            InputEvent::Resize(_, _) => None
+        }
+    }
+}
+
+pub fn key_to_string(key: Key) -> Option<String> {
+    match key {
+        Key::Char('\n') => Some(String::from("Enter")),
+        Key::Char('\t') => Some(String::from("Tab")),
+        Key::Char(c) => Some(format!("Char:{}", c)),
+        Key::Ctrl(c) => Some(format!("Ctrl:{}", c)),
+        Key::Alt(c) => Some(format!("Alt:{}", c)),
+        Key::F(n) => Some(format!("F:{}", n)),
+        Key::Up => Some(String::from("Up")),
+        Key::Down => Some(String::from("Down")),
+        Key::Left => Some(String::from("Left")),
+        Key::Right => Some(String::from("Right")),
+        Key::ShiftUp => Some(String::from("ShiftUp")),
+        Key::ShiftDown => Some(String::from("ShiftDown")),
+        Key::ShiftLeft => Some(String::from("ShiftLeft")),
+        Key::ShiftRight => Some(String::from("ShiftRight")),
+        Key::Home => Some(String::from("Home")),
+        Key::End => Some(String::from("End")),
+        Key::PageUp => Some(String::from("PageUp")),
+        Key::PageDown => Some(String::from("PageDown")),
+        Key::BackTab => Some(String::from("BackTab")),
+        Key::Backspace => Some(String::from("Backspace")),
+        Key::Delete => Some(String::from("Delete")),
+        Key::Insert => Some(String::from("Insert")),
+        Key::Esc => Some(String::from("Esc")),
+        _ => None
+    }
+}
+
+pub fn parse_key(text: &str) -> Option<Key> {
+    let single_char = |c: &str| {
+        let mut chars = c.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None
+        }
+    };
+
+    match text.split_once(':') {
+        Some(("Char", c)) => single_char(c).map(Key::Char),
+        Some(("Ctrl", c)) => single_char(c).map(Key::Ctrl),
+        Some(("Alt", c)) => single_char(c).map(Key::Alt),
+        Some(("F", n)) => n.parse().ok().map(Key::F),
+        _ => match text {
+            "Enter" => Some(Key::Char('\n')),
+            "Tab" => Some(Key::Char('\t')),
+            "Up" => Some(Key::Up),
+            "Down" => Some(Key::Down),
+            "Left" => Some(Key::Left),
+            "Right" => Some(Key::Right),
+            "ShiftUp" => Some(Key::ShiftUp),
+            "ShiftDown" => Some(Key::ShiftDown),
+            "ShiftLeft" => Some(Key::ShiftLeft),
+            "ShiftRight" => Some(Key::ShiftRight),
+            "Home" => Some(Key::Home),
+            "End" => Some(Key::End),
+            "PageUp" => Some(Key::PageUp),
+            "PageDown" => Some(Key::PageDown),
+            "BackTab" => Some(Key::BackTab),
+            "Backspace" => Some(Key::Backspace),
+            "Delete" => Some(Key::Delete),
+            "Insert" => Some(Key::Insert),
+            "Esc" => Some(Key::Esc),
+            _ => None
+        }
+    }
+}
+
+fn entry_to_line(entry: &SessionEntry) -> Option<String> {
+    match entry {
+        SessionEntry::Seed(seed) => Some(format!("seed {}", seed)),
+        SessionEntry::Key(key) => key_to_string(*key).map(|k| format!("key {}", k)),
+        SessionEntry::Click(position) => Some(format!("click {},{}", position.x, position.y)),
+        SessionEntry::Scroll(ScrollDirection::Up) => Some(String::from("scroll up")),
+        SessionEntry::Scroll(ScrollDirection::Down) => Some(String::from("scroll down")),
+        SessionEntry::Interrupt(quiet_polls) => Some(format!("interrupt {}", quiet_polls))
+    }
+}
+
+fn parse_click(text: &str) -> Option<Position> {
+    let (x, y) = text.split_once(',')?;
+    Some(Position::new(x.parse().ok()?, y.parse().ok()?))
+}
+
+pub fn parse_session(text: &str) -> Result<Vec<SessionEntry>, io::Error> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = match line.split_once(' ') {
+            Some(("seed", seed)) => Some(SessionEntry::Seed(seed.to_string())),
+            Some(("key", key)) => parse_key(key).map(SessionEntry::Key),
+            Some(("click", position)) => parse_click(position).map(SessionEntry::Click),
+            Some(("scroll", "up")) => Some(SessionEntry::Scroll(ScrollDirection::Up)),
+            Some(("scroll", "down")) => Some(SessionEntry::Scroll(ScrollDirection::Down)),
+            Some(("interrupt", quiet_polls)) => quiet_polls.parse().ok().map(SessionEntry::Interrupt),
+            _ => None
+        };
+        match entry {
+            Some(e) => entries.push(e),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid session entry on line {}: {}", number + 1, line)))
+        }
+    }
+    Ok(entries)
+}
+
+pub fn load_session(path: &str) -> Result<Vec<SessionEntry>, io::Error> {
+    parse_session(&fs::read_to_string(path)?)
+}
+
+/*
+ * Reads input from the terminal, writing each of it (and the seed of each game) to the session file
+ * Every entry is flushed as it's written so a crash still leaves a usable recording
+ */
+pub struct RecordingKeyInputResolver {
+    file: File,
+    events: Box<dyn EventSource>,
+    // Checks for an interrupt that found nothing since the last entry, so a replay interrupts at the same point
+    quiet_polls: u32
+}
+
+impl RecordingKeyInputResolver {
+    pub fn new(path: &str, events: Box<dyn EventSource>) -> Result<RecordingKeyInputResolver, io::Error> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", SESSION_HEADER)?;
+        log::info!("Recording session to: {}", path);
+        Ok(RecordingKeyInputResolver { file, events, quiet_polls: 0 })
+    }
+
+    fn record(&mut self, entry: SessionEntry) {
+        self.quiet_polls = 0;
+        if let Some(line) = entry_to_line(&entry) {
+            let result = writeln!(self.file, "{}", line).and_then(|_| self.file.flush());
+            if let Err(e) = result {
+                log::error!("Failed to record session entry {:?}: {}", entry, e);
+            }
+        } else {
+            log::warn!("Key can't be recorded, replays of this session will be missing it: {:?}", entry);
+        }
+    }
+}
+
+impl KeyInputResolver for RecordingKeyInputResolver {
+    fn get_input_key(&mut self) -> Result<Key, io::Error> {
+        let key = read_terminal_key(self.events.as_mut())?;
+        self.record(SessionEntry::Key(key));
+        Ok(key)
+    }
+
+    fn get_input_event(&mut self) -> Result<InputEvent, io::Error> {
+        let event = self.events.read_event()?;
+        if let Some(entry) = SessionEntry::from_event(event) {
+            self.record(entry);
+        }
+        Ok(event)
+    }
+
+    fn poll_interrupt(&mut self) -> Result<bool, io::Error> {
+        if poll_terminal_interrupt(self.events.as_mut())? {
+            self.record(SessionEntry::Interrupt(self.quiet_polls));
+            return Ok(true);
+        }
+        self.quiet_polls += 1;
+        Ok(false)
+    }
+
+    fn get_or_return_input_key(&mut self, input: Option<Key>) -> Result<Key, io::Error> {
+        match input {
+            Some(input_key) => Ok(input_key),
+            _ => self.get_input_key()
+        }
+    }
+
+    fn sync_seed(&mut self, seed: String) -> String {
+        self.record(SessionEntry::Seed(seed.clone()));
+        seed
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/*
+ * Plays back a recorded session, optionally waiting between keys so it can be watched
+ * Once the keys run out any further read fails with UnexpectedEof
+ */
+pub struct ReplayKeyInputResolver {
+    entries: VecDeque<SessionEntry>,
+    key_delay: Option<Duration>
+}
+
+impl ReplayKeyInputResolver {
+    pub fn new(entries: Vec<SessionEntry>, key_delay: Option<Duration>) -> ReplayKeyInputResolver {
+        ReplayKeyInputResolver { entries: VecDeque::from(entries), key_delay }
+    }
+
+    fn wait(&self) {
+        if let Some(delay) = self.key_delay {
+            thread::sleep(delay);
+        }
+    }
+}
+
+pub fn is_replay_finished(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::UnexpectedEof
+}
+
+impl KeyInputResolver for ReplayKeyInputResolver {
+    fn get_input_key(&mut self) -> Result<Key, io::Error> {
+        loop {
+            match self.get_input_event()? {
+                InputEvent::Click(position) => {
+                    log::error!("Replay expected a key but found a click at {:?}, the replay may be out of sync", position);
+                },
+                event => if let Some(key) = event.as_key() {
+                    return Ok(key);
+                }
+            }
+        }
+    }
+
+    fn get_input_event(&mut self) -> Result<InputEvent, io::Error> {
+        while let Some(entry) = self.entries.pop_front() {
+            let event = match entry {
+                SessionEntry::Key(key) => InputEvent::Key(key),
+                SessionEntry::Click(position) => InputEvent::Click(position),
+                SessionEntry::Scroll(direction) => InputEvent::Scroll(direction),
+                SessionEntry::Seed(seed) => {
+                    log::error!("Replay expected input but found seed {}, the replay may be out of sync", seed);
+                    continue;
+                },
+                SessionEntry::Interrupt(_) => {
+                    log::error!("Replay expected input but found an interrupt, the replay may be out of sync");
+                    continue;
+                }
+            };
+            self.wait();
+            return Ok(event);
+        }
+        Err(io::Error::new(io::ErrorKind::UnexpectedEof, "The replay has finished"))
+    }
+
+    fn poll_interrupt(&mut self) -> Result<bool, io::Error> {
+        match self.entries.front_mut() {
+            Some(SessionEntry::Interrupt(0)) => {
+                self.entries.pop_front();
+                Ok(true)
+            },
+            Some(SessionEntry::Interrupt(quiet_polls)) => {
+                *quiet_polls -= 1;
+                Ok(false)
+            },
+            _ => Ok(false)
+        }
+    }
+
+    fn get_or_return_input_key(&mut self, input: Option<Key>) -> Result<Key, io::Error> {
+        match input {
+            Some(input_key) => Ok(input_key),
+            _ => self.get_input_key()
+        }
+    }
+
+    fn sync_seed(&mut self, seed: String) -> String {
+        if let Some(SessionEntry::Seed(_)) = self.entries.front() {
+            if let Some(SessionEntry::Seed(recorded)) = self.entries.pop_front() {
+                return recorded;
+            }
+        }
+        seed
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SessionOptions {
+    pub record_path: Option<String>,
+    pub replay_path: Option<String>,
+    // Replays without drawing to the terminal
+    pub headless: bool,
+    // Milliseconds to wait between replayed keys
+    pub replay_delay_ms: Option<u64>
+}
+
+/*
+ * Reads the session options from the command line arguments (excluding the program name)
+ * i.e --record <file>, or --replay <file> [--headless] [--replay-delay <ms>]
+ */
+pub fn parse_session_options(args: &[String]) -> Result<SessionOptions, String> {
+    let mut options = SessionOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => options.record_path = Some(args.next().ok_or("--record needs a file path")?.clone()),
+            "--replay" => options.replay_path = Some(args.next().ok_or("--replay needs a file path")?.clone()),
+            "--headless" => options.headless = true,
+            "--replay-delay" => {
+                let delay = args.next().ok_or("--replay-delay needs a number of milliseconds")?;
+                options.replay_delay_ms = Some(delay.parse().map_err(|_| format!("Invalid replay delay: {}", delay))?);
+            },
+            other => return Err(format!("Unknown argument: {}", other))
+        }
+    }
+
+    if options.record_path.is_some() && options.replay_path.is_some() {
+        return Err(String::from("A session can't be recorded and replayed at the same time"));
+    }
+    if options.headless && options.replay_path.is_none() {
+        return Err(String::from("--headless is only supported when replaying a session"));
+    }
+    Ok(options)
+}
+
+// Builds the game's input from the terminal's events, recording or replaying the session as the options ask for
+pub fn start_session(options: &SessionOptions, events: Box<dyn EventSource>) -> Result<Box<dyn KeyInputResolver>, io::Error> {
+    if let Some(path) = &options.record_path {
+        return Ok(Box::new(RecordingKeyInputResolver::new(path, events)?));
+    } else if let Some(path) = &options.replay_path {
+        let entries = load_session(path)?;
+        log::info!("Replaying {} session entries from: {}", entries.len(), path);
+        let key_delay = options.replay_delay_ms.map(Duration::from_millis);
+        return Ok(Box::new(ReplayKeyInputResolver::new(entries, key_delay)));
+    }
+    Ok(Box::new(IoKeyInputResolver::new(events)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use termion::event::Key;
+
+    use crate::input::session::{entry_to_line, is_replay_finished, key_to_string, load_session, parse_key, parse_session, parse_session_options, RecordingKeyInputResolver, ReplayKeyInputResolver, SessionEntry};
+    use crate::input::event_source::ScriptedEventSource;
+    use crate::input::{InputEvent, KeyInputResolver, ScrollDirection};
+    use crate::map::position::Position;
+
+    #[test]
+    fn test_key_round_trip() {
+        // GIVEN a selection of keys
+        let keys = vec![Key::Char('w'), Key::Char(' '), Key::Char(':'), Key::Char('\n'), Key::Ctrl('c'), Key::F(5), Key::Up, Key::ShiftLeft, Key::PageDown, Key::Esc];
+
+        // WHEN we convert them to text and back
+        // THEN we expect the same keys
+        for key in keys {
+            assert_eq!(Some(key), parse_key(&key_to_string(key).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_parse_session() {
+        // GIVEN a session with a seed and some keys
+        let text = "# ARQ session v1\nseed abc123\nkey Char:w\nkey Enter\n";
+
+        // WHEN we parse it
+        let entries = parse_session(text).unwrap();
+
+        // THEN we expect each entry in order
+        assert_eq!(vec![SessionEntry::Seed(String::from("abc123")), SessionEntry::Key(Key::Char('w')), SessionEntry::Key(Key::Char('\n'))], entries);
+    }
+
+    #[test]
+    fn test_parse_session_invalid_entry() {
+        // GIVEN a session with an unknown key
+        // WHEN we parse it
+        let result = parse_session("key Bogus");
+
+        // THEN we expect an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replay() {
+        // GIVEN a replay of a seed and 2 keys
+        let entries = vec![SessionEntry::Seed(String::from("recorded")), SessionEntry::Key(Key::Char('w')), SessionEntry::Key(Key::Esc)];
+        let mut replay = ReplayKeyInputResolver::new(entries, None);
+
+        // WHEN we sync the seed and read the keys back
+        // THEN we get the recorded seed and keys in order
+        assert_eq!("recorded", replay.sync_seed(String::from("random")));
+        assert_eq!(Key::Char('w'), replay.get_input_key().unwrap());
+        assert_eq!(Key::Esc, replay.get_input_key().unwrap());
+
+        // AND the replay finishes once the keys run out
+        assert!(is_replay_finished(&replay.get_input_key().unwrap_err()));
+        // AND later seeds are left as they are
+        assert_eq!("random", replay.sync_seed(String::from("random")));
+    }
+
+    #[test]
+    fn test_mouse_entry_round_trip() {
+        // GIVEN a click, scrolls in each direction and an interrupt
+        let entries = vec![SessionEntry::Click(Position::new(12, 7)), SessionEntry::Scroll(ScrollDirection::Up), SessionEntry::Scroll(ScrollDirection::Down), SessionEntry::Interrupt(3)];
+
+        // WHEN we write them out and parse them back
+        let text = entries.iter().map(|e| entry_to_line(e).unwrap()).collect::<Vec<String>>().join("\n");
+
+        // THEN we expect the same entries
+        assert_eq!(entries, parse_session(&text).unwrap());
+    }
+
+    #[test]
+    fn test_replay_mouse_events() {
+        // GIVEN a replay of a click, a scroll and a key
+        let entries = vec![SessionEntry::Click(Position::new(3, 4)), SessionEntry::Scroll(ScrollDirection::Down), SessionEntry::Key(Key::Esc)];
+        let mut replay = ReplayKeyInputResolver::new(entries.clone(), None);
+
+        // WHEN we read them back as events
+        // THEN we get each of them in order
+        assert_eq!(InputEvent::Click(Position::new(3, 4)), replay.get_input_event().unwrap());
+        assert_eq!(InputEvent::Scroll(ScrollDirection::Down), replay.get_input_event().unwrap());
+        assert_eq!(InputEvent::Key(Key::Esc), replay.get_input_event().unwrap());
+
+        // AND reading keys alone skips both the click and the scroll
+        let mut replay = ReplayKeyInputResolver::new(entries, None);
+        assert_eq!(Key::Esc, replay.get_input_key().unwrap());
+    }
+
+    #[test]
+    fn test_replay_interrupt() {
+        // GIVEN a replay of an interrupt after 2 checks that found nothing, then a key
+        let mut replay = ReplayKeyInputResolver::new(vec![SessionEntry::Interrupt(2), SessionEntry::Key(Key::Esc)], None);
+
+        // WHEN we check for an interrupt 3 times
+        // THEN only the last check finds it
+        assert!(!replay.poll_interrupt().unwrap());
+        assert!(!replay.poll_interrupt().unwrap());
+        assert!(replay.poll_interrupt().unwrap());
+        // AND the key after it is left to be read
+        assert!(!replay.poll_interrupt().unwrap());
+        assert_eq!(Key::Esc, replay.get_input_key().unwrap());
+    }
+
+    #[test]
+    fn test_recording_seeds() {
+        // GIVEN a recording session
+        let path = std::env::temp_dir().join(format!("arq-session-{}.txt", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let mut recording = RecordingKeyInputResolver::new(path, Box::new(ScriptedEventSource::new(Vec::new()))).unwrap();
+
+        // WHEN the seeds of two games are synced
+        assert_eq!("first", recording.sync_seed(String::from("first")));
+        assert_eq!("second", recording.sync_seed(String::from("second")));
+
+        // THEN both are saved to the session file
+        let entries = load_session(path).unwrap();
+        assert_eq!(vec![SessionEntry::Seed(String::from("first")), SessionEntry::Seed(String::from("second"))], entries);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_recording_events() {
+        // GIVEN a recording session
+        let path = std::env::temp_dir().join(format!("arq-session-{}.txt", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        // AND the terminal gives a click, a resize and a key
+        let events = vec![InputEvent::Click(Position::new(3, 4)), InputEvent::Resize(100, 30), InputEvent::Key(Key::Char('w'))];
+        let mut recording = RecordingKeyInputResolver::new(path, Box::new(ScriptedEventSource::new(events.clone()))).unwrap();
+
+        // WHEN the game reads them
+        for event in events {
+            assert_eq!(event, recording.get_input_event().unwrap());
+        }
+
+        // THEN the click and key are saved to the session file, but not the resize
+        let entries = load_session(path).unwrap();
+        assert_eq!(vec![SessionEntry::Click(Position::new(3, 4)), SessionEntry::Key(Key::Char('w'))], entries);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_session_options() {
+        // GIVEN arguments for a headless replay
+        let args: Vec<String> = ["--replay", "bug.session", "--headless", "--replay-delay", "50"].iter().map(|a| a.to_string()).collect();
+
+        // WHEN we parse them
+        let options = parse_session_options(&args).unwrap();
+
+        // THEN we expect a headless replay of the file with the delay set
+        assert_eq!(Some(String::from("bug.session")), options.replay_path);
+        assert!(options.headless);
+        assert_eq!(Some(50), options.replay_delay_ms);
+    }
+
+    #[test]
+    fn test_parse_session_options_conflicting() {
+        // GIVEN arguments to both record and replay
+        let args: Vec<String> = ["--record", "a", "--replay", "b"].iter().map(|a| a.to_string()).collect();
+
+        // WHEN we parse them
+        // THEN we expect an error
+        assert!(parse_session_options(&args).is_err());
+    }
+}
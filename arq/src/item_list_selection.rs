@@ -70,6 +70,24 @@ impl ItemListSelection {
         ItemListSelection { selection_mode, start_index, pivot_index, previous_container_index, current_index, true_index, container_index, selecting_items, selected_indices, selected_items, items, page_line_count: item_view_line_count }
     }
 
+    /*
+     * Replaces the items listed (i.e after sorting / filtering), starting back at the top of the list
+     * Any selected items that are still listed stay selected
+     */
+    pub fn rebuild_keeping_selection(&mut self, items : Vec<Item>) {
+        let previously_selected = self.selected_items.clone();
+        *self = ItemListSelection::new(items, self.page_line_count);
+        let mut kept_indices: Vec<i32> = previously_selected.iter()
+            .filter_map(|selected| self.items.iter().position(|i| i.id_equals(selected)))
+            .map(|index| index as i32)
+            .collect();
+        kept_indices.sort();
+        for index in kept_indices {
+            self.selected_indices.push_back(index);
+            self.selected_items.push_back(self.items[index as usize].clone());
+        }
+    }
+
     fn reset_selection(&mut self) {
         self.start_index = 0;
         self.current_index = 0;
@@ -564,6 +582,26 @@ mod tests {
         assert_eq!(0, selected_items.len());
     }
 
+    #[test]
+    fn test_rebuild_keeping_selection() {
+        // GIVEN a list of 4 items, with the 2nd and 4th selected
+        let items = build_item_series_4();
+        let mut list_selection = ItemListSelection::new(items.clone(), 4);
+        list_selection.select(1);
+        list_selection.select(3);
+
+        // WHEN we rebuild it with the list reversed and the 2nd item dropped
+        let rebuilt_items = vec![items[3].clone(), items[2].clone(), items[0].clone()];
+        list_selection.rebuild_keeping_selection(rebuilt_items);
+
+        // THEN only the 4th item is still selected, at it's new position
+        assert_eq!(3, list_selection.get_items().len());
+        assert!(list_selection.is_selected(0));
+        assert_eq!(vec![items[3].clone()], Vec::from(list_selection.get_selected_items()));
+        // AND the page line count is kept
+        assert_eq!(4, list_selection.page_line_count);
+    }
+
     #[test]
     fn test_multi_page_count() {
         // GIVEN a series of items to select from
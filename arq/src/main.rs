@@ -1,15 +1,16 @@
 extern crate core;
 
-use std::io;
-
 use futures::executor::block_on;
-use termion::input::TermRead;
-use termion::raw::RawTerminal;
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::Backend;
 
 use crate::engine::engine_helpers::menu::start_menu;
-use crate::engine::game_engine::{build_game_engine, GameEngine};
+use crate::engine::game_engine::build_game_engine;
 use crate::error::errors::ErrorWrapper;
+use crate::input::event_source::{CrosstermEventSource, EventSource, ScriptedEventSource};
+use crate::input::KeyInputResolver;
+use crate::input::session::{is_replay_finished, parse_session_options, start_session};
+use crate::terminal::terminal_manager::TerminalManager;
+use crate::ui::resolution::MIN_RESOLUTION;
 use crate::ui::ui::StartMenuChoice::Play;
 use crate::view::game_over_view::GameOverChoice;
 
@@ -33,11 +34,8 @@ pub mod map;
 
 pub mod input;
 
-async fn begin() -> Result<(), ErrorWrapper> {
-    let game_engine: Result<GameEngine<CrosstermBackend<RawTerminal<std::io::Stdout>>>, ErrorWrapper>;
-    let terminal_manager = terminal::terminal_manager::init().unwrap();
-    game_engine = build_game_engine(terminal_manager);
-    let mut engine = game_engine.unwrap();
+async fn begin<B: Backend + Send + 'static>(terminal_manager: TerminalManager<B>, key_input: Box<dyn KeyInputResolver>) -> Result<(), ErrorWrapper> {
+    let mut engine = build_game_engine(terminal_manager, key_input)?;
 
     log::info!("Displaying start menu..");
     let mut choice = None;
@@ -59,8 +57,12 @@ async fn begin() -> Result<(), ErrorWrapper> {
                 }
             },
             Err(e) => {
+                if e.io_error.as_ref().is_some_and(is_replay_finished) {
+                    log::info!("Replay finished.");
+                    return Ok(())
+                }
                 println!("Fatal error: {}", e);
-                io::stdin().keys().next().unwrap()?;
+                engine.input_handler.get_key_input().get_input_key()?;
                 return Ok(())
             },
             Ok(None) => {}
@@ -72,5 +74,38 @@ async fn begin() -> Result<(), ErrorWrapper> {
 #[tokio::main(worker_threads = 2)]
 async fn main<>() {
     log4rs::init_file("log4rs.yaml", Default::default()).unwrap();
-    block_on(begin()).expect("Failure in main thread!");
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let session_options = match parse_session_options(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    // Headless replays render to an in-memory terminal instead, which has no events of it's own
+    let events: Box<dyn EventSource> = if session_options.headless {
+        Box::new(ScriptedEventSource::new(Vec::new()))
+    } else {
+        Box::new(CrosstermEventSource {})
+    };
+    let key_input = match start_session(&session_options, events) {
+        Ok(key_input) => key_input,
+        Err(e) => {
+            eprintln!("Failed to start the session: {}", e);
+            return;
+        }
+    };
+
+    if session_options.headless {
+        let terminal_manager = terminal::terminal_manager::init_test(MIN_RESOLUTION).unwrap();
+        block_on(begin(terminal_manager, key_input)).expect("Failure in main thread!");
+    } else {
+        let terminal_manager = terminal::terminal_manager::init().unwrap();
+        block_on(begin(terminal_manager, key_input)).expect("Failure in main thread!");
+        if let Err(e) = terminal::terminal_manager::restore() {
+            log::error!("Failed to restore the terminal: {}", e);
+        }
+    }
 }
@@ -12,6 +12,9 @@ pub mod position;
 pub mod room;
 pub mod tile;
 pub mod map_view_areas;
+pub mod map_loader;
+pub mod map_exporter;
+pub mod overworld_generator;
 
 #[derive(Debug, Clone)]
 pub struct Map {
@@ -156,6 +159,31 @@ impl Map {
         }
         results
     }
+
+    /*
+     * Whether a character can step directly between 2 adjacent positions
+     * Diagonal steps need both of the tiles either side to be traversable, so nothing cuts a corner past a wall
+     */
+    pub fn can_step_between(&self, from: Position, to: Position) -> bool {
+        if !self.is_traversable(to) {
+            return false;
+        }
+        if from.x != to.x && from.y != to.y {
+            self.is_traversable(Position::new(to.x, from.y)) && self.is_traversable(Position::new(from.x, to.y))
+        } else {
+            true
+        }
+    }
+
+    // The neighboring positions a character could step to, optionally including diagonals
+    pub fn get_walkable_neighbors(&self, position: Position, diagonal: bool) -> Vec<Position> {
+        let mut neighbors = self.get_neighbors(position);
+        if diagonal && self.area.contains_position(position) {
+            neighbors.extend(position.get_diagonal_neighbors().into_iter().filter(|n| self.area.contains_position(*n)));
+        }
+        neighbors.retain(|n| self.can_step_between(position, *n));
+        neighbors
+    }
 }
 
 #[cfg(test)]
@@ -170,7 +198,7 @@ mod tests {
     #[test]
     fn test_build_map() {
         let tile_library = crate::map::tile::build_library();
-        assert_eq!(9, tile_library.len());
+        assert_eq!(14, tile_library.len());
 
         let rom = tile_library[&TileType::Room].clone();
         let wall = tile_library[&TileType::Wall].clone();
@@ -207,7 +235,7 @@ mod tests {
     #[test]
     fn test_adjust_map() {
         let tile_library = crate::map::tile::build_library();
-        assert_eq!(9, tile_library.len());
+        assert_eq!(14, tile_library.len());
 
         let wall = tile_library[&TileType::Wall].clone();
 
@@ -246,4 +274,35 @@ mod tests {
         // AND the new tile to be available at 1,0
         assert_eq!(crate::map::tile::TileType::Wall, map.tiles.tiles[1][0].tile_type);
     }
+
+    #[test]
+    fn test_get_walkable_neighbors_no_corner_cutting() {
+        // GIVEN a map with a wall in the middle of the top row
+        let tile_library = crate::map::tile::build_library();
+        let rom = tile_library[&TileType::Room].clone();
+        let wall = tile_library[&TileType::Wall].clone();
+        let map = crate::map::Map {
+            area: build_square_area(Position::zero(), 3),
+            tiles : Tiles {tiles: vec![
+                vec![ rom.clone(), wall.clone(), rom.clone() ],
+                vec![ rom.clone(), rom.clone(), rom.clone() ],
+                vec![ rom.clone(), rom.clone(), rom.clone() ],
+            ]},
+            rooms: Vec::new(),
+            containers: HashMap::new()
+        };
+
+        // WHEN we get the walkable neighbors of the middle-left tile, including diagonals
+        let neighbors = map.get_walkable_neighbors(Position::new(0, 1), true);
+
+        // THEN we can't step diagonally up past the wall, but can step diagonally down
+        assert!(!neighbors.contains(&Position::new(1, 0)));
+        assert!(neighbors.contains(&Position::new(0, 0)));
+        assert!(neighbors.contains(&Position::new(1, 2)));
+        assert!(!map.can_step_between(Position::new(0, 0), Position::new(1, 1)));
+        assert_eq!(4, neighbors.len());
+
+        // AND without diagonals only the orthogonal steps are returned
+        assert_eq!(3, map.get_walkable_neighbors(Position::new(0, 1), false).len());
+    }
 }
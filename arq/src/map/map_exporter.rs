@@ -0,0 +1,362 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::character::Character;
+use crate::engine::level::Level;
+use crate::error::errors::ErrorWrapper;
+use crate::map::map_loader::{tile_symbol, LEVEL_JSON_VERSION};
+use crate::map::objects::container::{Container, ContainerType};
+use crate::map::objects::items::{Item, ItemForm, ItemType};
+use crate::map::position::Position;
+use crate::map::tile::TileType;
+use crate::map::Map;
+
+pub const EXPORT_FOLDER: &str = "exports";
+
+/*
+ * Renders a Level using the same format as the ASCII map loader, with markers for:
+ * '@' The player
+ * '&' NPCs
+ * '$' Containers (i.e Chests) sitting on the floor
+ * Markers are only drawn over Room tiles, as that's the tile the loader puts back beneath them
+ * Anything standing on another tile (i.e the player on an Entry) keeps the tile's glyph, so nothing is lost on reloading
+ * Each character/container is also listed in the header comments for easier reading
+ */
+pub fn export_level_ascii(level: &Level) -> String {
+    let mut lines = vec![String::from("; Exported ARQ level")];
+    if let Some(player) = level.characters.get_player() {
+        lines.push(format!("; Player: {}", describe_character(player)));
+    }
+    for npc in level.characters.get_npcs() {
+        lines.push(format!("; NPC: {}", describe_character(npc)));
+    }
+
+    if let Some(map) = &level.map {
+        for (position, container) in sorted_containers(map) {
+            for c in placed_containers(container) {
+                lines.push(format!("; Container: {} at {}, {} ({} items, value: {})",
+                                   c.get_self_item().get_name(), position.x, position.y, c.get_total_count(), c.get_loot_value()));
+            }
+        }
+
+        lines.push(String::from("[map]"));
+        for (y, row) in map.tiles.tiles.iter().enumerate() {
+            let mut line = String::new();
+            for (x, tile) in row.iter().enumerate() {
+                let position = Position::new(x as u16, y as u16);
+                let marker = if tile.tile_type == TileType::Room { level_symbol(level, map, position) } else { None };
+                line.push(marker.unwrap_or(tile_symbol(tile.tile_type)));
+            }
+            lines.push(line.trim_end().to_string());
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/*
+ * Serialises a Level to JSON, this can be loaded again using map_loader::parse_level_json
+ */
+pub fn export_level_json(level: &Level) -> Value {
+    let mut document = json!({
+        "version": LEVEL_JSON_VERSION,
+        "characters": {
+            "player": level.characters.get_player().map(character_to_json),
+            "npcs": level.characters.get_npcs().iter().map(character_to_json).collect::<Vec<Value>>()
+        }
+    });
+
+    if let Some(map) = &level.map {
+        let tiles: Vec<String> = map.tiles.tiles.iter()
+            .map(|row| row.iter().map(|t| tile_symbol(t.tile_type)).collect())
+            .collect();
+        let rooms: Vec<Value> = map.rooms.iter().map(|room| {
+            let area = room.get_area();
+            json!({
+                "x": area.start_position.x,
+                "y": area.start_position.y,
+                "width": area.width,
+                "height": area.height,
+                "doors": room.get_doors().iter().map(|door| json!({
+                    "x": door.position.x,
+                    "y": door.position.y,
+                    "open": door.open,
+                    "locked": door.locked,
+                    "health": door.health,
+                    "locks": door.locks,
+                    "unlocked_locks": door.unlocked_locks
+                })).collect::<Vec<Value>>(),
+                "entry": room.get_entry().map(position_to_json),
+                "exit": room.get_exit().map(position_to_json)
+            })
+        }).collect();
+        let containers: Vec<Value> = sorted_containers(map).iter().map(|(position, container)| {
+            json!({
+                "x": position.x,
+                "y": position.y,
+                "container": container_to_json(container)
+            })
+        }).collect();
+
+        document["map"] = json!({
+            "width": map.area.width,
+            "height": map.area.height,
+            "tiles": tiles,
+            "rooms": rooms,
+            "containers": containers
+        });
+    }
+    document
+}
+
+/*
+ * Writes both the ASCII and JSON exports of a level into the EXPORT_FOLDER
+ * Returns the paths of the written files
+ */
+pub fn write_level_export(level: &Level, name: String) -> Result<Vec<String>, ErrorWrapper> {
+    fs::create_dir_all(EXPORT_FOLDER)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let base_path = format!("{}/{}-{}", EXPORT_FOLDER, name, timestamp);
+
+    let ascii_path = format!("{}.txt", base_path);
+    fs::write(&ascii_path, export_level_ascii(level))?;
+
+    let json_path = format!("{}.json", base_path);
+    let json_string = serde_json::to_string_pretty(&export_level_json(level))
+        .map_err(|e| ErrorWrapper::new_internal(format!("Failed to serialise level: {}", e)))?;
+    fs::write(&json_path, json_string)?;
+
+    log::info!("Level exported to: {} and {}", ascii_path, json_path);
+    Ok(vec![ascii_path, json_path])
+}
+
+fn level_symbol(level: &Level, map: &Map, position: Position) -> Option<char> {
+    if level.characters.get_player().is_some_and(|p| p.get_global_position() == position) {
+        return Some('@');
+    }
+    if level.characters.get_npcs().iter().any(|npc| npc.get_global_position() == position) {
+        return Some('&');
+    }
+    if let Some(container) = map.get_container(position) {
+        if !placed_containers(container).is_empty() {
+            return Some('$');
+        }
+    }
+    None
+}
+
+// Returns any fixed containers (i.e Chests) placed on top of a Floor container, or the container itself if it's not a Floor
+fn placed_containers(container: &Container) -> Vec<&Container> {
+    if container.get_self_item().get_name() == "Floor" {
+        container.get_contents().iter().filter(|c| c.container_type == ContainerType::AREA).collect()
+    } else {
+        vec![container]
+    }
+}
+
+fn sorted_containers(map: &Map) -> Vec<(Position, &Container)> {
+    let mut containers: Vec<(Position, &Container)> = map.containers.iter().map(|(p, c)| (*p, c)).collect();
+    containers.sort_by_key(|(p, _c)| (p.y, p.x));
+    containers
+}
+
+fn describe_character(character: &Character) -> String {
+    let position = character.get_global_position();
+    format!("{} at {}, {} (health: {})", character.get_name(), position.x, position.y, character.get_health())
+}
+
+fn position_to_json(position: Position) -> Value {
+    json!({ "x": position.x, "y": position.y })
+}
+
+fn character_to_json(character: &Character) -> Value {
+    let position = character.get_global_position();
+    json!({
+        "name": character.get_name(),
+        "x": position.x,
+        "y": position.y,
+        "health": character.get_health(),
+        "max_health": character.get_max_health(),
+        "symbol": character.get_symbol().to_string()
+    })
+}
+
+pub fn container_to_json(container: &Container) -> Value {
+    json!({
+        "container_type": container.container_type.to_string(),
+        "weight_limit": container.get_weight_limit(),
+        "item": item_to_json(container.get_self_item()),
+        "contents": container.get_contents().iter().map(container_to_json).collect::<Vec<Value>>()
+    })
+}
+
+fn item_to_json(item: &Item) -> Value {
+    let (item_type, damage) = match &item.item_type {
+        ItemType::ITEM => ("ITEM", None),
+        ItemType::CONTAINER => ("CONTAINER", None),
+        ItemType::WEAPON(weapon) => ("WEAPON", Some(weapon.damage)),
+        ItemType::HEADGEAR => ("HEADGEAR", None),
+        ItemType::TORSO => ("TORSO", None),
+        ItemType::LEGS => ("LEGS", None)
+    };
+    let (form, form_detail) = match item.get_item_form() {
+        ItemForm::COIN => ("COIN", None),
+        ItemForm::BAR => ("BAR", None),
+        ItemForm::BLADED(bladed_type) => ("BLADED", Some(format!("{:?}", bladed_type))),
+        ItemForm::POTION(potion_type) => ("POTION", Some(format!("{:?}", potion_type))),
+        ItemForm::SCROLL(scroll_type) => ("SCROLL", Some(format!("{:?}", scroll_type))),
+        ItemForm::OTHER(description) => ("OTHER", Some(description))
+    };
+    json!({
+        "id": item.get_id().to_string(),
+        "name": item.get_name(),
+        "item_type": item_type,
+        "damage": damage,
+        "item_form": form,
+        "item_form_detail": form_detail,
+        "material_type": format!("{:?}", item.get_material_type()),
+        "symbol": item.symbol.character.to_string(),
+        "colour": format!("{:?}", item.symbol.colour),
+        "weight": item.get_weight(),
+        "value": item.get_value(),
+        "quantity": item.get_quantity(),
+        "rarity": format!("{:?}", item.get_rarity()),
+        "equipment_slot": item.get_equipment_slot().map(|slot| format!("{:?}", slot))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::engine::level::Level;
+    use crate::engine::loot_table::Rarity;
+    use crate::map::map_exporter::{export_level_ascii, export_level_json};
+    use crate::map::map_loader::{parse_level_json, parse_map};
+    use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::items::{Item, ItemForm, MaterialType};
+    use crate::map::objects::weapon_builder::{BladedWeaponType, WeaponBlueprint, WeaponBuilder};
+    use crate::map::position::Position;
+    use crate::map::tile::TileType;
+    use crate::test::utils::test_utils::build_test_level;
+
+    const TEST_MAP: &str = "\
+[map]
+######
+#<.$&#
+#...>=--
+######
+";
+
+    fn build_loaded_test_level() -> Level {
+        let loaded = parse_map(TEST_MAP).unwrap();
+        let mut level = build_test_level(None, None);
+        level.map = Some(loaded.map);
+        level.characters.get_player_mut().unwrap().set_position(Position::new(2, 2));
+        level
+    }
+
+    #[test]
+    fn test_export_level_ascii() {
+        // GIVEN a level loaded from an ASCII map, with the player at 2,2
+        let level = build_loaded_test_level();
+
+        // WHEN we call to export it as ASCII
+        let ascii = export_level_ascii(&level);
+
+        // THEN we expect the map rows to be rendered using the loader's legend
+        let map_section: Vec<&str> = ascii.lines().skip_while(|l| *l != "[map]").collect();
+        assert_eq!(vec!["[map]", "######", "#<.$.#", "#.@.>=--", "######"], map_section);
+        // AND the chest to be described in the header
+        assert!(ascii.contains("; Container: Chest at 3, 1"));
+        assert!(ascii.contains("; Player: Test Player at 2, 2"));
+    }
+
+    #[test]
+    fn test_export_level_ascii_keeps_tiles_under_characters() {
+        // GIVEN a level loaded from an ASCII map, with the player standing on the entry at 1,1
+        let mut level = build_loaded_test_level();
+        level.characters.get_player_mut().unwrap().set_position(Position::new(1, 1));
+
+        // WHEN we call to export it as ASCII and load it again
+        let ascii = export_level_ascii(&level);
+        let loaded = parse_map(&ascii).unwrap();
+
+        // THEN we expect the entry to be kept, with the player only described in the header
+        let map_section: Vec<&str> = ascii.lines().skip_while(|l| *l != "[map]").collect();
+        assert_eq!(vec!["[map]", "######", "#<.$.#", "#...>=--", "######"], map_section);
+        assert!(ascii.contains("; Player: Test Player at 1, 1"));
+        assert_eq!(TileType::Entry, loaded.map.tiles.get_tile(Position::new(1, 1)).unwrap().tile_type);
+    }
+
+    #[test]
+    fn test_export_level_ascii_can_be_loaded() {
+        // GIVEN a level loaded from an ASCII map
+        let level = build_loaded_test_level();
+
+        // WHEN we export it as ASCII and load it again
+        let loaded = parse_map(&export_level_ascii(&level));
+
+        // THEN we expect the player spawn to match the player's position
+        assert!(loaded.is_ok());
+        let loaded = loaded.unwrap();
+        assert_eq!(Some(Position::new(2, 2)), loaded.player_spawn);
+        assert_eq!(1, loaded.map.rooms.len());
+    }
+
+    #[test]
+    fn test_export_level_json_round_trip() {
+        // GIVEN a level loaded from an ASCII map
+        let mut level = build_loaded_test_level();
+        // AND a bag holding a rare sword and a stack of coins dropped on the floor at 2,1
+        let blueprint = WeaponBlueprint::new(MaterialType::STEEL, ItemForm::BLADED(BladedWeaponType::DAGGER)).unwrap();
+        let mut sword = WeaponBuilder::new(blueprint).build();
+        sword.set_rarity(Rarity::Rare);
+        let mut bag = Container::new(Uuid::new_v4(), "Bag".to_owned(), '$', 5.0, 50, ContainerType::OBJECT, 50);
+        bag.add_item(sword.clone()).unwrap();
+        let mut coins = Item::new_with_form(Uuid::new_v4(), "Gold Coin".to_owned(), MaterialType::GOLD, ItemForm::COIN, '$', 0.01, 1);
+        coins.set_quantity(12);
+        bag.add_item(coins.clone()).unwrap();
+        let map = level.map.as_mut().unwrap();
+        map.find_container_mut(Position::new(2, 1)).unwrap().add(bag.clone()).unwrap();
+
+        // WHEN we export it to JSON and load it again
+        let json = export_level_json(&level);
+        let result = parse_level_json(&json.to_string());
+
+        // THEN we expect the same tiles, rooms and containers
+        assert!(result.is_ok(), "{:?}", result.as_ref().err());
+        let loaded = result.unwrap();
+        let original_map = level.map.as_ref().unwrap();
+        let loaded_map = &loaded.map;
+        assert_eq!(original_map.area, loaded_map.area);
+        for (y, row) in original_map.tiles.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                assert_eq!(tile.tile_type, loaded_map.tiles.tiles[y][x].tile_type);
+            }
+        }
+
+        assert_eq!(1, loaded_map.rooms.len());
+        let room = &loaded_map.rooms[0];
+        assert_eq!(original_map.rooms[0].get_area(), room.get_area());
+        assert_eq!(Some(Position::new(1, 1)), room.get_entry());
+        assert_eq!(Some(Position::new(4, 2)), room.get_exit());
+        assert_eq!(Position::new(5, 2), room.get_doors()[0].position);
+
+        assert_eq!(original_map.containers.len(), loaded_map.containers.len());
+        let floor = loaded_map.get_container(Position::new(2, 1)).unwrap();
+        let loaded_bag = floor.get_contents().get(0).unwrap();
+        assert_eq!(bag.get_self_item().get_id(), loaded_bag.get_self_item().get_id());
+        assert_eq!(ContainerType::OBJECT, loaded_bag.container_type);
+        let loaded_sword = loaded_bag.get_contents().get(0).unwrap().get_self_item();
+        assert_eq!(&sword, loaded_sword);
+        assert_eq!(Rarity::Rare, loaded_sword.get_rarity());
+        let loaded_coins = loaded_bag.get_contents().get(1).unwrap().get_self_item();
+        assert_eq!(&coins, loaded_coins);
+        assert_eq!(12, loaded_coins.get_quantity());
+
+        // AND the player to be returned as a spawn
+        assert_eq!(Some(Position::new(2, 2)), loaded.player_spawn);
+    }
+}
@@ -10,14 +10,14 @@ use rand::Rng;
 use rand_pcg::Pcg64;
 use uuid::Uuid;
 
+use crate::engine::loot_table::{build_loot_table, LootContainerType};
 use crate::engine::pathfinding::Pathfinding;
 use crate::engine::process::Progressible;
 use crate::map::objects::container::{Container, ContainerType};
 use crate::map::objects::door::build_door;
-use crate::map::objects::items::{Item, MaterialType};
 use crate::map::position::{build_square_area, Area, Position, Side};
 use crate::map::room::{build_room, Room};
-use crate::map::tile::TileType::{Door, Entry, Exit, NoTile, Wall};
+use crate::map::tile::TileType::{Door, DungeonEntrance, Entry, Exit, NoTile, Wall, Water};
 use crate::map::tile::{build_library, TileDetails, TileType};
 use crate::map::{Map, Tiles};
 use crate::progress::{MultiStepProgress, Step};
@@ -32,11 +32,13 @@ pub struct MapGenerator<'rng> {
     taken_positions : Vec<Position>,
     possible_room_positions : Vec<Position>,
     rng: &'rng mut Pcg64,
+    // How deep in the dungeon this level is, deciding how much loot it has
+    depth: usize,
     pub progress: MultiStepProgress,
     pub map: Map
 }
 
-pub fn build_generator<'a>(rng : &'a mut Pcg64, map_area : Area) -> MapGenerator<'a> {
+pub fn build_generator<'a>(rng : &'a mut Pcg64, map_area : Area, depth: usize) -> MapGenerator<'a> {
     let map_generation_steps: Vec<Step> = vec![
         Step { id: String::from("mapgen"), description: String::from("Generating map...") },
         Step { id: String::from("entry/exits"),  description: String::from("Adding entry/exit...") },
@@ -53,32 +55,39 @@ pub fn build_generator<'a>(rng : &'a mut Pcg64, map_area : Area) -> MapGenerator
         tile_library: build_library(), map_area, taken_positions: Vec::new(),
         possible_room_positions : Vec::new(),
         rng,
+        depth,
         progress,
         map: Map {area: map_area, tiles: Tiles { tiles: Vec::new() }, rooms: Vec::new(), containers: HashMap::new()}}
 }
 
-pub fn build_dev_chest() -> Container {
-    let mut container = Container::new(Uuid::new_v4(), "Chest".to_owned(), '$', 50.0, 1, ContainerType::AREA, 100);
-
-    // Items totalling 7 weight
-    let bronze_bar = Item::new(Uuid::new_v4(), "Bronze Bar".to_owned(), MaterialType::BRONZE, 'X', 1.0, 50);
-    let mut bag = Container::new(Uuid::new_v4(), "Bag".to_owned(), '$', 5.0, 50, ContainerType::OBJECT, 50);
-    let mut carton = Container::new(Uuid::new_v4(), "Carton".to_owned(), '$', 1.0, 50, ContainerType::OBJECT, 5);
-    let tin_bar = Item::new(Uuid::new_v4(), "Tin Bar".to_owned(), MaterialType::TIN, 'X', 1.0, 50);
-    carton.add_item(tin_bar).expect("Tin bar should have been added to the carton!");
-    bag.add(carton).expect("The carton should have been added to the bag!");
-    bag.add_item(bronze_bar).expect("Bronze bar should have been added to the bag!");
-    container.add(bag).expect("The Bag should have been added to the Chest");
-
-    // 60 extra weight
-    for i in 1..=60 {
-        let test_item = Item::new(Uuid::new_v4(), format!("Test Item {}", i), MaterialType::UNKNOWN, '$', 1.0, 100);
-        container.add_item(test_item).expect(format!("Test Item {} should have been added to the container", i).as_str());
+// Builds an AREA container (The "Floor") for every tile that can hold items
+pub fn build_area_containers(map_area: Area, tiles: &Tiles) ->  HashMap<Position, Container> {
+    let mut area_containers = HashMap::new();
+    for y in map_area.start_position.y..=map_area.end_position.y {
+        for x in map_area.start_position.x..=map_area.end_position.x {
+            let position = Position { x, y };
+            if let Some(td) = tiles.get_tile(position) {
+                if td.tile_type != NoTile && td.tile_type != Wall && td.tile_type != Door && td.tile_type != Entry && td.tile_type != Exit
+                    && td.tile_type != Water && td.tile_type != DungeonEntrance {
+                    log::debug!("New AREA container at: {}, {}", x,y);
+                    area_containers.insert(position, build_floor_container());
+                }
+            }
+        }
     }
-    return container;
+    area_containers
+}
+
+pub fn build_floor_container() -> Container {
+    Container::new(Uuid::new_v4(), "Floor".to_owned(), '$', 0.0, 0, ContainerType::AREA, 999999)
+}
+
+pub fn build_empty_chest() -> Container {
+    Container::new(Uuid::new_v4(), "Chest".to_owned(), '$', 50.0, 1, ContainerType::AREA, 100)
 }
 
-fn generate_room_containers(rng: &mut Pcg64, room: Room) -> HashMap<Position, Container> {
+// Rooms are given a few containers, filled from the loot table for their type and the level's depth
+fn generate_room_containers(rng: &mut Pcg64, room: Room, depth: usize) -> HashMap<Position, Container> {
     let mut container_map = HashMap::new();
     let inside_area = room.get_inside_area();
     let total_area = inside_area.get_total_area();
@@ -90,7 +99,8 @@ fn generate_room_containers(rng: &mut Pcg64, room: Room) -> HashMap<Position, Co
             let random_x: u16 = rng.gen_range(0..size_x) as u16;
             let random_y: u16 = rng.gen_range(0..size_y) as u16;
             let container_position = Position { x: inside_area.start_position.x.clone() + random_x, y: inside_area.start_position.y.clone() + random_y };
-            container_map.insert(container_position, build_dev_chest());
+            let loot_table = build_loot_table(LootContainerType::roll(rng), depth);
+            container_map.insert(container_position, loot_table.fill_container(rng, depth));
         }
     }
 
@@ -113,7 +123,7 @@ impl <'rng> MapGenerator<'rng> {
 
     pub fn add_area_containers(&mut self) {
         let mut area_container_count = 0;
-        let area_containers = self.build_area_containers();
+        let area_containers = build_area_containers(self.map_area, &self.map.tiles);
         for pos_container in area_containers {
             let pos = pos_container.0.clone();
             let container = pos_container.1.clone();
@@ -131,7 +141,7 @@ impl <'rng> MapGenerator<'rng> {
         let mut room_container_count = 0;
         let rooms = &mut self.map.rooms;
         for room in rooms.iter_mut() {
-            let room_containers = generate_room_containers(&mut self.rng, room.clone());
+            let room_containers = generate_room_containers(&mut self.rng, room.clone(), self.depth);
             for pos_container in &room_containers {
                 let mut pos = pos_container.0.clone();
                 let container = pos_container.1.clone();
@@ -466,26 +476,6 @@ impl <'rng> MapGenerator<'rng> {
         map_tiles
     }
 
-    fn build_area_containers(&self) ->  HashMap<Position, Container> {
-        let mut area_containers = HashMap::new();
-        for y in self.map_area.start_position.y..=self.map_area.end_position.y {
-            for x in self.map_area.start_position.x..=self.map_area.end_position.x {
-                let position = Position { x, y };
-                match self.map.tiles.get_tile(position) {
-                    Some(td) => {
-                        if td.tile_type != NoTile && td.tile_type != Wall && td.tile_type != Door && td.tile_type != Entry && td.tile_type != Exit {
-                            log::debug!("New AREA container at: {}, {}", x,y);
-                            let area_container = Container::new(Uuid::new_v4(), "Floor".to_owned(), '$', 0.0, 0, ContainerType::AREA, 999999);
-                            area_containers.insert(position, area_container);
-                        }
-                    },
-                    _ => {}
-                }
-            }
-        }
-        area_containers
-    }
-
     fn add_room_to_map(&mut self, room: &Room) {
         let tile_library = crate::map::tile::build_library();
         let room_tile = &tile_library[&TileType::Room].clone();
@@ -571,7 +561,7 @@ mod tests {
     use crate::map::Map;
 
     fn build_test_map(rng: &mut Pcg64, map_area: Area) -> Map {
-        let mut generator = build_generator(rng, map_area);
+        let mut generator = build_generator(rng, map_area, 0);
 
         let (tx, _rx) = channel();
         block_on(generator.generate(tx))
@@ -582,7 +572,7 @@ mod tests {
         // GIVEN a 12x12 map board
         let map_area = build_square_area(Position { x: 0, y: 0 }, 12);
         let rng = &mut Seeder::from("test".to_string()).into_rng();
-        let generator = build_generator(rng, map_area);
+        let generator = build_generator(rng, map_area, 0);
 
         assert_eq!(3, generator.min_room_size);
         assert_eq!(6, generator.max_room_size);
@@ -598,7 +588,7 @@ mod tests {
     fn test_generate_room() {
         let map_area = build_square_area(Position { x: 0, y: 0 }, 12);
         let rng = &mut Seeder::from("test".to_string()).into_rng();
-        let mut generator = build_generator(rng, map_area);
+        let mut generator = build_generator(rng, map_area, 0);
 
         let room = generator.generate_room(Position { x: 0, y: 0 }, 3);
         let expected_area = build_square_area(Position { x: 0, y: 0 }, 3);
@@ -611,7 +601,7 @@ mod tests {
         let map_size = 12;
         let map_area = build_square_area(Position { x: 0, y: 0 }, map_size);
         let rng = &mut Seeder::from("test".to_string()).into_rng();
-        let mut generator = build_generator(rng, map_area);
+        let mut generator = build_generator(rng, map_area, 0);
         let rooms = generator.generate_rooms();
         assert_ne!(0, rooms.len());
 
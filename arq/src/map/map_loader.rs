@@ -0,0 +1,650 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::character::equipment::{all_equipment_slots, EquipmentSlot};
+use crate::engine::loot_table::Rarity;
+use crate::error::errors::ErrorWrapper;
+use crate::map::map_generator::{build_area_containers, build_empty_chest};
+use crate::map::objects::container::{Container, ContainerType};
+use crate::map::objects::door::{build_door, Door};
+use crate::map::objects::identification::all_unidentified_forms;
+use crate::map::objects::items::{Item, ItemForm, ItemType, MaterialType, Weapon};
+use crate::map::objects::weapon_builder::BladedWeaponType;
+use crate::map::position::{build_rectangular_area, Area, Position};
+use crate::map::room::{build_room, Room};
+use crate::map::tile::{build_library, Colour, Symbol, TileDetails, TileType};
+use crate::map::{Map, Tiles};
+
+pub const RESOURCE_MAPS_FOLDER: &str = "resources/maps";
+
+const LEGEND_HEADER: &str = "[legend]";
+const MAP_HEADER: &str = "[map]";
+const COMMENT_PREFIX: char = ';';
+
+pub const LEVEL_JSON_VERSION: u64 = 1;
+
+/*
+ * What a single character of an ASCII map file represents
+ * Markers (Container/NpcSpawn/PlayerSpawn) are placed on top of a Room tile
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum LegendEntry {
+    Tile(TileType),
+    Container,
+    NpcSpawn,
+    PlayerSpawn
+}
+
+/*
+ * The result of loading an ASCII map
+ * Character spawns are returned separately as a Map does not hold any characters
+ */
+#[derive(Debug, Clone)]
+pub struct LoadedMap {
+    pub map: Map,
+    pub npc_spawns: Vec<Position>,
+    pub player_spawn: Option<Position>
+}
+
+pub fn build_default_legend() -> HashMap<char, LegendEntry> {
+    let mut legend = HashMap::new();
+    legend.insert(' ', LegendEntry::Tile(TileType::NoTile));
+    legend.insert('-', LegendEntry::Tile(TileType::Corridor));
+    legend.insert('.', LegendEntry::Tile(TileType::Room));
+    legend.insert('#', LegendEntry::Tile(TileType::Wall));
+    legend.insert('%', LegendEntry::Tile(TileType::Window));
+    legend.insert('=', LegendEntry::Tile(TileType::Door));
+    legend.insert('<', LegendEntry::Tile(TileType::Entry));
+    legend.insert('>', LegendEntry::Tile(TileType::Exit));
+    legend.insert('!', LegendEntry::Tile(TileType::Deadly));
+    legend.insert(',', LegendEntry::Tile(TileType::Grass));
+    legend.insert('T', LegendEntry::Tile(TileType::Forest));
+    legend.insert('~', LegendEntry::Tile(TileType::Water));
+    legend.insert(':', LegendEntry::Tile(TileType::Road));
+    legend.insert('O', LegendEntry::Tile(TileType::DungeonEntrance));
+    legend.insert('$', LegendEntry::Container);
+    legend.insert('&', LegendEntry::NpcSpawn);
+    legend.insert('@', LegendEntry::PlayerSpawn);
+    legend
+}
+
+// The default legend symbol for a tile type
+pub fn tile_symbol(tile_type: TileType) -> char {
+    build_default_legend().iter()
+        .find(|(_symbol, entry)| **entry == LegendEntry::Tile(tile_type))
+        .map(|(symbol, _entry)| *symbol)
+        .unwrap_or(' ')
+}
+
+fn parse_legend_entry(name: &str) -> Option<LegendEntry> {
+    match name {
+        "NoTile" => Some(LegendEntry::Tile(TileType::NoTile)),
+        "Corridor" => Some(LegendEntry::Tile(TileType::Corridor)),
+        "Room" => Some(LegendEntry::Tile(TileType::Room)),
+        "Wall" => Some(LegendEntry::Tile(TileType::Wall)),
+        "Window" => Some(LegendEntry::Tile(TileType::Window)),
+        "Door" => Some(LegendEntry::Tile(TileType::Door)),
+        "Entry" => Some(LegendEntry::Tile(TileType::Entry)),
+        "Exit" => Some(LegendEntry::Tile(TileType::Exit)),
+        "Deadly" => Some(LegendEntry::Tile(TileType::Deadly)),
+        "Grass" => Some(LegendEntry::Tile(TileType::Grass)),
+        "Forest" => Some(LegendEntry::Tile(TileType::Forest)),
+        "Water" => Some(LegendEntry::Tile(TileType::Water)),
+        "Road" => Some(LegendEntry::Tile(TileType::Road)),
+        "DungeonEntrance" => Some(LegendEntry::Tile(TileType::DungeonEntrance)),
+        "Container" => Some(LegendEntry::Container),
+        "NPC" => Some(LegendEntry::NpcSpawn),
+        "Player" => Some(LegendEntry::PlayerSpawn),
+        _ => None
+    }
+}
+
+/*
+ * Loads an ASCII map file (see parse_map)
+ * The result can be added to the game using Levels::add_loaded_level
+ */
+pub fn load_map_file(path: &str) -> Result<LoadedMap, ErrorWrapper> {
+    let input = fs::read_to_string(path)?;
+    parse_map(&input)
+}
+
+/*
+ * Parses an ASCII map in the format:
+ *
+ * ; Comment lines start with ';', and can be anywhere in the file
+ * [legend]
+ * ,=Corridor
+ * [map]
+ * #####
+ * #.<.#
+ * ##=##
+ *
+ * The legend section is optional and adds to / overrides the default legend (see build_default_legend)
+ * Rooms are found from the walled rectangles around Room/Entry/Exit tiles
+ */
+pub fn parse_map(input: &str) -> Result<LoadedMap, ErrorWrapper> {
+    let mut legend = build_default_legend();
+    let mut map_lines: Vec<&str> = Vec::new();
+    let mut in_legend = false;
+    let mut in_map = false;
+    for line in input.lines() {
+        // Blank lines are only kept between map rows
+        let is_leading_blank = line.trim().is_empty() && (!in_map || map_lines.is_empty());
+        if is_leading_blank || line.starts_with(COMMENT_PREFIX) {
+            continue;
+        }
+
+        if line.trim() == LEGEND_HEADER {
+            in_legend = true;
+            in_map = false;
+        } else if line.trim() == MAP_HEADER {
+            in_legend = false;
+            in_map = true;
+        } else if in_legend {
+            let mut chars = line.chars();
+            let symbol = chars.next();
+            let separator = chars.next();
+            let name: String = chars.collect();
+            match (symbol, separator, parse_legend_entry(name.trim())) {
+                (Some(s), Some('='), Some(entry)) => {
+                    legend.insert(s, entry);
+                },
+                _ => {
+                    return ErrorWrapper::internal_result(format!("Invalid legend entry: '{}'", line));
+                }
+            }
+        } else if in_map {
+            map_lines.push(line);
+        } else {
+            return ErrorWrapper::internal_result(format!("Unexpected line outside of a section: '{}'", line));
+        }
+    }
+
+    // Trailing blank lines aren't part of the map
+    while map_lines.last().is_some_and(|l| l.trim().is_empty()) {
+        map_lines.pop();
+    }
+
+    if map_lines.is_empty() {
+        return ErrorWrapper::internal_result(String::from("No map rows found, is the [map] section missing?"));
+    }
+
+    build_loaded_map(&legend, map_lines)
+}
+
+pub fn load_level_json_file(path: &str) -> Result<LoadedMap, ErrorWrapper> {
+    let input = fs::read_to_string(path)?;
+    parse_level_json(&input)
+}
+
+/*
+ * Parses a level exported by map_exporter::export_level_json
+ * Unlike the ASCII format, rooms, doors and containers (and their contents) are all loaded as they were exported
+ */
+pub fn parse_level_json(input: &str) -> Result<LoadedMap, ErrorWrapper> {
+    let document: Value = serde_json::from_str(input)
+        .map_err(|e| ErrorWrapper::new_internal(format!("Invalid level JSON: {}", e)))?;
+    let version = document.get("version").and_then(|v| v.as_u64());
+    if version != Some(LEVEL_JSON_VERSION) {
+        return ErrorWrapper::internal_result(format!("Unsupported level JSON version: {:?}", version));
+    }
+
+    let map_json = json_field(&document, "map")?;
+    let width = json_u16(map_json, "width")?;
+    let height = json_u16(map_json, "height")?;
+    let map_area = build_rectangular_area(Position::zero(), width, height);
+
+    let tile_library = build_library();
+    let legend = build_default_legend();
+    let mut tiles = Vec::new();
+    for row_json in json_array(map_json, "tiles")? {
+        let mut row = Vec::new();
+        for symbol in row_json.as_str().unwrap_or("").chars() {
+            match legend.get(&symbol) {
+                Some(LegendEntry::Tile(tile_type)) => row.push(tile_library[tile_type].clone()),
+                _ => return ErrorWrapper::internal_result(format!("Unknown tile symbol '{}'", symbol))
+            }
+        }
+        tiles.push(row);
+    }
+
+    let mut rooms = Vec::new();
+    for room_json in json_array(map_json, "rooms")? {
+        let start_position = Position::new(json_u16(room_json, "x")?, json_u16(room_json, "y")?);
+        let room_area = build_rectangular_area(start_position, json_u16(room_json, "width")?, json_u16(room_json, "height")?);
+        let mut doors = Vec::new();
+        for door_json in json_array(room_json, "doors")? {
+            let mut door = build_door(json_position(door_json)?);
+            door.open = json_field(door_json, "open")?.as_bool().unwrap_or(false);
+            door.locked = json_field(door_json, "locked")?.as_bool().unwrap_or(false);
+            door.health = json_u16(door_json, "health")?;
+            door.locks = json_u16(door_json, "locks")?;
+            door.unlocked_locks = json_u16(door_json, "unlocked_locks")?;
+            doors.push(door);
+        }
+        let mut room = build_room(room_area, doors);
+        room.set_entry(json_optional_position(room_json, "entry")?);
+        room.set_exit(json_optional_position(room_json, "exit")?);
+        rooms.push(room);
+    }
+
+    let mut containers = HashMap::new();
+    for container_json in json_array(map_json, "containers")? {
+        let position = json_position(container_json)?;
+        containers.insert(position, parse_container_json(json_field(container_json, "container")?)?);
+    }
+
+    let characters_json = json_field(&document, "characters")?;
+    let player_spawn = match characters_json.get("player") {
+        Some(Value::Null) | None => None,
+        Some(player_json) => Some(json_position(player_json)?)
+    };
+    let mut npc_spawns = Vec::new();
+    for npc_json in json_array(characters_json, "npcs")? {
+        npc_spawns.push(json_position(npc_json)?);
+    }
+
+    let map = Map { area: map_area, tiles: Tiles { tiles }, rooms, containers };
+    Ok(LoadedMap { map, npc_spawns, player_spawn })
+}
+
+pub fn parse_container_json(container_json: &Value) -> Result<Container, ErrorWrapper> {
+    let item = parse_item_json(json_field(container_json, "item")?)?;
+    let container_type = match json_str(container_json, "container_type")? {
+        "ITEM" => ContainerType::ITEM,
+        "OBJECT" => ContainerType::OBJECT,
+        "AREA" => ContainerType::AREA,
+        other => return ErrorWrapper::internal_result(format!("Unknown container type: {}", other))
+    };
+
+    let mut container = match container_type {
+        ContainerType::ITEM => Container::wrap(item),
+        _ => {
+            let weight_limit = json_field(container_json, "weight_limit")?.as_i64().unwrap_or(0) as i32;
+            Container::new(item.get_id(), item.get_name(), item.symbol.character, item.weight, item.value, container_type, weight_limit)
+        }
+    };
+
+    // Contents are pushed directly to keep them exactly as they were exported
+    let mut contents = Vec::new();
+    for content_json in json_array(container_json, "contents")? {
+        contents.push(parse_container_json(content_json)?);
+    }
+    container.push(contents);
+    Ok(container)
+}
+
+fn parse_item_json(item_json: &Value) -> Result<Item, ErrorWrapper> {
+    let id = Uuid::parse_str(json_str(item_json, "id")?)
+        .map_err(|e| ErrorWrapper::new_internal(format!("Invalid item id: {}", e)))?;
+    let name = json_str(item_json, "name")?.to_string();
+    let symbol = json_str(item_json, "symbol")?.chars().next().unwrap_or(' ');
+    let weight = json_field(item_json, "weight")?.as_f64().unwrap_or(0.0) as f32;
+    let value = json_field(item_json, "value")?.as_i64().unwrap_or(0) as i32;
+
+    let form_detail = item_json.get("item_form_detail").and_then(|d| d.as_str()).unwrap_or("").to_string();
+    let form_name = json_str(item_json, "item_form")?;
+    let item_form = match form_name {
+        "COIN" => ItemForm::COIN,
+        "BAR" => ItemForm::BAR,
+        "BLADED" => {
+            let bladed_type = vec![BladedWeaponType::DAGGER, BladedWeaponType::ARMING, BladedWeaponType::LONG].into_iter()
+                .find(|t| format!("{:?}", t) == form_detail)
+                .ok_or(ErrorWrapper::new_internal(format!("Unknown bladed weapon type: {}", form_detail)))?;
+            ItemForm::BLADED(bladed_type)
+        },
+        "POTION" | "SCROLL" => {
+            // i.e POTION(Healing)
+            all_unidentified_forms().into_iter()
+                .find(|f| format!("{:?}", f) == format!("{}({})", form_name, form_detail))
+                .ok_or(ErrorWrapper::new_internal(format!("Unknown magic item type: {}", form_detail)))?
+        },
+        _ => ItemForm::OTHER(form_detail)
+    };
+
+    let material_name = json_str(item_json, "material_type")?;
+    let material_type = vec![MaterialType::CLOTH, MaterialType::LEATHER, MaterialType::WOOD, MaterialType::STONE, MaterialType::BRONZE,
+        MaterialType::TIN, MaterialType::IRON, MaterialType::STEEL, MaterialType::SILVER, MaterialType::GOLD].into_iter()
+        .find(|m| format!("{:?}", m) == material_name)
+        .unwrap_or(MaterialType::UNKNOWN);
+
+    let mut item = match json_str(item_json, "item_type")? {
+        "CONTAINER" => Item::container_item(id, name, symbol, weight, value),
+        "WEAPON" => {
+            let damage = json_field(item_json, "damage")?.as_i64().unwrap_or(0) as i32;
+            Item::weapon(id, name, item_form.clone(), material_type.clone(), symbol, weight, value, Weapon { damage })
+        },
+        _ => Item::new_with_form(id, name, material_type.clone(), item_form.clone(), symbol, weight, value)
+    };
+    item.item_type = match json_str(item_json, "item_type")? {
+        "HEADGEAR" => ItemType::HEADGEAR,
+        "TORSO" => ItemType::TORSO,
+        "LEGS" => ItemType::LEGS,
+        _ => item.item_type
+    };
+
+    let colour_name = json_str(item_json, "colour")?;
+    let colour = vec![Colour::None, Colour::Red, Colour::Green, Colour::Blue, Colour::Cyan, Colour::Brown, Colour::White, Colour::Black].into_iter()
+        .find(|c| format!("{:?}", c) == colour_name)
+        .unwrap_or(Colour::White);
+    item.symbol = Symbol::new(symbol, colour);
+
+    let equipment_slot = item_json.get("equipment_slot").and_then(|s| s.as_str())
+        .and_then(|slot_name| all_equipment_slots().into_iter().find(|slot: &EquipmentSlot| format!("{:?}", slot) == slot_name));
+    item.set_equipment_slot(equipment_slot);
+    // Levels exported before items stacked won't have a quantity
+    item.set_quantity(item_json.get("quantity").and_then(|q| q.as_u64()).unwrap_or(1) as u32);
+    // Or a rarity, anything not rolled as loot is common
+    let rarity = item_json.get("rarity").and_then(|r| r.as_str())
+        .and_then(|rarity_name| vec![Rarity::Common, Rarity::Uncommon, Rarity::Rare].into_iter().find(|r| format!("{:?}", r) == rarity_name));
+    item.set_rarity(rarity.unwrap_or(Rarity::Common));
+    Ok(item)
+}
+
+fn json_field<'a>(json: &'a Value, name: &str) -> Result<&'a Value, ErrorWrapper> {
+    json.get(name).ok_or(ErrorWrapper::new_internal(format!("Missing JSON field: {}", name)))
+}
+
+fn json_str<'a>(json: &'a Value, name: &str) -> Result<&'a str, ErrorWrapper> {
+    json_field(json, name)?.as_str().ok_or(ErrorWrapper::new_internal(format!("Expected a string for JSON field: {}", name)))
+}
+
+fn json_u16(json: &Value, name: &str) -> Result<u16, ErrorWrapper> {
+    json_field(json, name)?.as_u64().map(|v| v as u16).ok_or(ErrorWrapper::new_internal(format!("Expected a number for JSON field: {}", name)))
+}
+
+fn json_array<'a>(json: &'a Value, name: &str) -> Result<&'a Vec<Value>, ErrorWrapper> {
+    json_field(json, name)?.as_array().ok_or(ErrorWrapper::new_internal(format!("Expected an array for JSON field: {}", name)))
+}
+
+fn json_position(json: &Value) -> Result<Position, ErrorWrapper> {
+    Ok(Position::new(json_u16(json, "x")?, json_u16(json, "y")?))
+}
+
+fn json_optional_position(json: &Value, name: &str) -> Result<Option<Position>, ErrorWrapper> {
+    match json.get(name) {
+        Some(Value::Null) | None => Ok(None),
+        Some(position_json) => Ok(Some(json_position(position_json)?))
+    }
+}
+
+fn build_loaded_map(legend: &HashMap<char, LegendEntry>, map_lines: Vec<&str>) -> Result<LoadedMap, ErrorWrapper> {
+    let tile_library = build_library();
+    let width = map_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+    let height = map_lines.len() as u16;
+    let map_area = build_rectangular_area(Position::zero(), width, height);
+
+    let mut tiles: Vec<Vec<TileDetails>> = Vec::new();
+    let mut container_positions = Vec::new();
+    let mut npc_spawns = Vec::new();
+    let mut player_spawn = None;
+    for (y, line) in map_lines.iter().enumerate() {
+        let mut row = Vec::new();
+        let mut chars = line.chars();
+        for x in 0..width {
+            // Pad any short rows with empty tiles
+            let symbol = chars.next().unwrap_or(' ');
+            let position = Position::new(x, y as u16);
+            let tile_type = match legend.get(&symbol) {
+                Some(LegendEntry::Tile(tile_type)) => *tile_type,
+                Some(LegendEntry::Container) => {
+                    container_positions.push(position);
+                    TileType::Room
+                },
+                Some(LegendEntry::NpcSpawn) => {
+                    npc_spawns.push(position);
+                    TileType::Room
+                },
+                Some(LegendEntry::PlayerSpawn) => {
+                    player_spawn = Some(position);
+                    TileType::Room
+                },
+                None => {
+                    return ErrorWrapper::internal_result(format!("Unknown map symbol '{}' at: {}, {}", symbol, x, y));
+                }
+            };
+            row.push(tile_library[&tile_type].clone());
+        }
+        tiles.push(row);
+    }
+
+    let tiles = Tiles { tiles };
+    let rooms = find_rooms(map_area, &tiles)?;
+    let containers = build_area_containers(map_area, &tiles);
+    let mut map = Map { area: map_area, tiles, rooms, containers };
+
+    for position in container_positions {
+        match map.find_container_mut(position) {
+            Some(floor) => {
+                floor.add_area(build_empty_chest())?;
+            },
+            None => {
+                return ErrorWrapper::internal_result(format!("No floor to place a container on at: {:?}", position));
+            }
+        }
+    }
+
+    Ok(LoadedMap { map, npc_spawns, player_spawn })
+}
+
+fn is_room_inside_tile(tiles: &Tiles, position: Position) -> bool {
+    match tiles.get_tile(position) {
+        Some(tile) => {
+            tile.tile_type == TileType::Room || tile.tile_type == TileType::Entry || tile.tile_type == TileType::Exit
+        },
+        None => false
+    }
+}
+
+/*
+ * Finds each connected group of Room/Entry/Exit tiles and builds a Room from the bounding walls around it
+ * Doors are any Door tiles along the sides of the room
+ */
+fn find_rooms(map_area: Area, tiles: &Tiles) -> Result<Vec<Room>, ErrorWrapper> {
+    let mut rooms = Vec::new();
+    let mut visited: HashSet<Position> = HashSet::new();
+    for y in map_area.start_position.y..=map_area.end_position.y {
+        for x in map_area.start_position.x..=map_area.end_position.x {
+            let start = Position::new(x, y);
+            if visited.contains(&start) || !is_room_inside_tile(tiles, start) {
+                continue;
+            }
+
+            // Flood fill to find the extents of the room's inside area
+            let mut inside_positions = Vec::new();
+            let mut unvisited = vec![start];
+            visited.insert(start);
+            while let Some(current) = unvisited.pop() {
+                inside_positions.push(current);
+                for n in current.get_neighbors() {
+                    if !visited.contains(&n) && is_room_inside_tile(tiles, n) {
+                        visited.insert(n);
+                        unvisited.push(n);
+                    }
+                }
+            }
+
+            let min_x = inside_positions.iter().map(|p| p.x).min().unwrap();
+            let min_y = inside_positions.iter().map(|p| p.y).min().unwrap();
+            let max_x = inside_positions.iter().map(|p| p.x).max().unwrap();
+            let max_y = inside_positions.iter().map(|p| p.y).max().unwrap();
+            if min_x == 0 || min_y == 0 || max_x >= map_area.end_position.x || max_y >= map_area.end_position.y {
+                return ErrorWrapper::internal_result(format!("Room containing {:?} has no walls around it.", start));
+            }
+
+            let room_area = build_rectangular_area(Position::new(min_x - 1, min_y - 1), max_x - min_x + 3, max_y - min_y + 3);
+            let mut doors = Vec::new();
+            for side in room_area.get_sides() {
+                for position in side.area.get_positions() {
+                    let tile_type = tiles.get_tile(position).map(|t| t.tile_type);
+                    match tile_type {
+                        Some(TileType::Door) => {
+                            if !doors.iter().any(|d: &Door| d.position == position) {
+                                doors.push(build_door(position));
+                            }
+                        },
+                        Some(TileType::Wall) | Some(TileType::Window) => {},
+                        _ => {
+                            return ErrorWrapper::internal_result(format!("Room containing {:?} has a gap in it's walls at: {:?}", start, position));
+                        }
+                    }
+                }
+            }
+
+            let mut room = build_room(room_area, doors);
+            for position in &inside_positions {
+                match tiles.get_tile(*position).map(|t| t.tile_type) {
+                    Some(TileType::Entry) => room.set_entry(Some(*position)),
+                    Some(TileType::Exit) => room.set_exit(Some(*position)),
+                    _ => {}
+                }
+            }
+            rooms.push(room);
+        }
+    }
+    Ok(rooms)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::map::map_loader::{load_map_file, parse_map, RESOURCE_MAPS_FOLDER};
+    use crate::map::position::Position;
+    use crate::map::tile::TileType;
+
+    #[test]
+    fn test_parse_map() {
+        // GIVEN an ASCII map with a single room, a door, a corridor, entry/exit and markers
+        let input = "\
+; A single room
+[map]
+######
+#<.$&#
+#...>=--
+######
+";
+        // WHEN we call to parse it
+        let result = parse_map(input);
+
+        // THEN we expect a 8x4 map
+        assert!(result.is_ok(), "{:?}", result.as_ref().err());
+        let loaded = result.unwrap();
+        let map = loaded.map;
+        assert_eq!(8, map.area.width);
+        assert_eq!(4, map.area.height);
+
+        // AND short rows to be padded with empty tiles
+        assert_eq!(TileType::NoTile, map.tiles.get_tile(Position::new(7, 0)).unwrap().tile_type);
+        assert_eq!(TileType::Corridor, map.tiles.get_tile(Position::new(7, 2)).unwrap().tile_type);
+
+        // AND a single room with the walls as it's area
+        assert_eq!(1, map.rooms.len());
+        let room = map.rooms.get(0).unwrap();
+        assert_eq!(Position::new(0, 0), room.get_area().start_position);
+        assert_eq!(Position::new(5, 3), room.get_area().end_position);
+
+        // AND the door, entry and exit to be set on that room
+        assert_eq!(1, room.get_doors().len());
+        assert_eq!(Position::new(5, 2), room.get_doors()[0].position);
+        assert_eq!(Some(Position::new(1, 1)), room.get_entry());
+        assert_eq!(Some(Position::new(4, 2)), room.get_exit());
+
+        // AND markers to be placed on top of room tiles
+        assert_eq!(TileType::Room, map.tiles.get_tile(Position::new(3, 1)).unwrap().tile_type);
+        assert_eq!(vec![Position::new(4, 1)], loaded.npc_spawns);
+
+        // AND a chest to be added to the floor container at the container marker
+        let floor = map.get_container(Position::new(3, 1)).unwrap();
+        assert_eq!(1, floor.get_contents().len());
+        assert_eq!("Chest", floor.get_contents()[0].get_self_item().get_name());
+
+        // AND floor containers for each room/corridor tile
+        assert!(map.get_container(Position::new(2, 1)).is_some());
+        assert!(map.get_container(Position::new(6, 2)).is_some());
+        assert!(map.get_container(Position::new(5, 2)).is_none());
+    }
+
+    #[test]
+    fn test_parse_map_blank_lines_and_comments() {
+        // GIVEN an ASCII map with blank lines around the rows, and a comment between them
+        let input = "\
+[map]
+
+####
+#..#
+; The room's bottom wall
+####
+
+";
+        // WHEN we call to parse it
+        let result = parse_map(input);
+
+        // THEN we expect a 4x3 map, with the blank lines and comment left out
+        assert!(result.is_ok(), "{:?}", result.as_ref().err());
+        let map = result.unwrap().map;
+        assert_eq!(4, map.area.width);
+        assert_eq!(3, map.area.height);
+        assert_eq!(TileType::Wall, map.tiles.get_tile(Position::new(0, 0)).unwrap().tile_type);
+        assert_eq!(TileType::Room, map.tiles.get_tile(Position::new(1, 1)).unwrap().tile_type);
+        assert_eq!(1, map.rooms.len());
+    }
+
+    #[test]
+    fn test_parse_map_custom_legend() {
+        // GIVEN an ASCII map overriding the corridor symbol
+        let input = "\
+[legend]
+,=Corridor
+[map]
+,,
+";
+        // WHEN we call to parse it
+        let result = parse_map(input);
+
+        // THEN we expect the custom symbol to be used
+        assert!(result.is_ok());
+        let map = result.unwrap().map;
+        assert_eq!(TileType::Corridor, map.tiles.get_tile(Position::new(0, 0)).unwrap().tile_type);
+        assert_eq!(TileType::Corridor, map.tiles.get_tile(Position::new(1, 0)).unwrap().tile_type);
+    }
+
+    #[test]
+    fn test_parse_map_unknown_symbol() {
+        // GIVEN an ASCII map with a symbol missing from the legend
+        let input = "[map]\n#?#\n";
+        // WHEN we call to parse it
+        let result = parse_map(input);
+        // THEN we expect an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_map_missing_wall() {
+        // GIVEN an ASCII map with a room that has a gap in it's walls
+        let input = "\
+[map]
+#####
+#...
+#####
+";
+        // WHEN we call to parse it
+        let result = parse_map(input);
+        // THEN we expect an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_tutorial_map() {
+        // GIVEN the tutorial map resource
+        let path = format!("{}/tutorial.txt", RESOURCE_MAPS_FOLDER);
+        // WHEN we call to load it
+        let result = load_map_file(&path);
+        // THEN we expect a valid map with an entry and exit
+        assert!(result.is_ok());
+        let map = result.unwrap().map;
+        assert!(map.rooms.iter().any(|r| r.get_entry().is_some()));
+        assert!(map.rooms.iter().any(|r| r.get_exit().is_some()));
+    }
+}
@@ -31,6 +31,20 @@ impl MapViewAreas {
         return Some(global_position);
     }
 
+    // The map position drawn at the given screen position, if there's any of the map drawn there
+    pub(crate) fn screen_to_global(&self, screen_position: Position) -> Option<Position> {
+        if !self.map_view_area.contains_position(screen_position) {
+            return None;
+        }
+        let view_start = self.map_view_area.start_position;
+        let local_position = Position::new(screen_position.x - view_start.x, screen_position.y - view_start.y);
+        let global_position = self.local_to_global(local_position)?;
+        if self.is_position_in_map_display_area(global_position) && self.map_area.contains_position(global_position) {
+            return Some(global_position);
+        }
+        None
+    }
+
     pub(crate) fn global_to_local(&self, global_position: Position) -> Option<Position> {
         let map_display_area = self.map_display_area;
         if map_display_area.contains_position(global_position) && self.map_area.contains_position(global_position) {
@@ -176,6 +190,25 @@ mod tests {
         assert_eq!(map_view_area.height, map_display_area.height);
     }
 
+    #[test]
+    fn test_screen_to_global() {
+        // GIVEN a 12x12 map
+        let map_area = Area::new(Position::new(0, 0), 12, 12);
+        // AND a 6x6 view of it drawn from 1,1 on screen
+        let map_view_area = Area::new(Position::new(1, 1), 6, 6);
+        // AND the view is showing the map from 4,4
+        let map_display_area = Area::new(Position::new(4, 4), 6, 6);
+        let map_view_areas = MapViewAreas { map_area, map_view_area, map_display_area };
+
+        // WHEN we look up screen positions inside the view
+        // THEN we expect the map positions drawn there
+        assert_eq!(Some(Position::new(4, 4)), map_view_areas.screen_to_global(Position::new(1, 1)));
+        assert_eq!(Some(Position::new(6, 8)), map_view_areas.screen_to_global(Position::new(3, 5)));
+        // AND nothing outside of the view
+        assert_eq!(None, map_view_areas.screen_to_global(Position::new(0, 1)));
+        assert_eq!(None, map_view_areas.screen_to_global(Position::new(10, 10)));
+    }
+
     #[test]
     fn test_is_position_in_map_display_area_valid() {
         // GIVEN a 12x12 map at pos 0,0
@@ -1,4 +1,5 @@
 pub mod container;
 pub mod door;
+pub mod identification;
 pub mod items;
 pub mod weapon_builder;
\ No newline at end of file
@@ -211,14 +211,46 @@ impl Container {
         }
     }
 
+    // Removes the matching container, or only the given quantity if it's part of a larger stack
     pub fn remove_item(&mut self, item : &Container) -> bool {
         if let Some(position) = self.position(item) {
-            self.contents.remove(position);
+            let removing_quantity = item.get_self_item().get_quantity();
+            let stack_item = self.contents[position].get_self_item_mut();
+            if stack_item.get_quantity() > removing_quantity {
+                stack_item.set_quantity(stack_item.get_quantity() - removing_quantity);
+            } else {
+                self.contents.remove(position);
+            }
             return true;
         }
         return false;
     }
 
+    /*
+    * Returns a copy of this stack holding only the given quantity
+    * When that's not the whole stack, the copy is a new stack (with it's own id) split from this one
+    */
+    pub fn clone_quantity(&self, quantity: u32) -> Container {
+        let mut copy = self.clone();
+        if self.container_type == ContainerType::ITEM && quantity < self.item.get_quantity() {
+            copy.item = self.item.split(quantity);
+        }
+        copy
+    }
+
+    // Adds to a matching stack if there is one, otherwise adds the container as-is
+    fn push_or_stack(&mut self, container: Container) {
+        let adding = container.get_self_item();
+        let stack = self.contents.iter_mut()
+            .find(|c| c.container_type == ContainerType::ITEM && c.item.can_stack_with(adding));
+        if let Some(s) = stack {
+            let quantity = s.item.get_quantity() + adding.get_quantity();
+            s.item.set_quantity(quantity);
+        } else {
+            self.contents.push(container);
+        }
+    }
+
     pub fn remove_matching_items(&mut self, items : Vec<Container>) -> bool {
         let mut removed = Vec::new();
         for item in items.iter() {
@@ -291,7 +323,7 @@ impl Container {
                 return weight_total;
             },
             _ => {
-                weight_total += self.get_self_item().weight.clone();
+                weight_total += self.get_self_item().get_total_weight();
             }
         }
         weight_total
@@ -306,7 +338,7 @@ impl Container {
         for c in &self.contents {
             match c.container_type {
                 ContainerType::ITEM => {
-                    loot_total += c.get_self_item().value;
+                    loot_total += c.get_self_item().get_total_value();
                 },
                 ContainerType::OBJECT | ContainerType::AREA => {
                     loot_total += c.get_self_item().value;
@@ -362,7 +394,7 @@ impl Container {
                     let within_potential_weight_limit = max_weight_limit <= self.weight_limit as f32;
                     let potential_weight = total_weight.clone() + container.get_weight_total();
                     return if within_potential_weight_limit && potential_weight <= self.weight_limit as f32 {
-                        self.contents.push(container);
+                        self.push_or_stack(container);
                         Ok(())
                     } else {
                         Err(Error::new(ErrorKind::Other, format!("Cannot add the container to this container as above the weight limit. Potential weight: {}, Weight limit: {}", potential_weight, self.weight_limit)))
@@ -392,12 +424,12 @@ impl Container {
                 let weight_total = match self.container_type {
                     ContainerType::OBJECT => {
                         let current_weight_total: f32 = self.get_weight_total();
-                        let item_weight: f32 = item.weight;
+                        let item_weight: f32 = item.get_total_weight();
                         current_weight_total + item_weight
                     },
                     ContainerType::AREA => {
                         // Only consider the weight of the item to be added
-                        item.weight
+                        item.get_total_weight()
                     },
                     ContainerType::ITEM => {
                         return ErrorWrapper::internal_result(String::from("Cannot add item. An item cannot be added to an ITEM ContainerType!"))
@@ -405,14 +437,13 @@ impl Container {
                 };
 
                 if weight_total <= weight_limit {
-                    let container = Container::wrap(item.clone());
-                    self.contents.push(container);
+                    self.push_or_stack(Container::wrap(item.clone()));
                     return Ok(())
                 } else {
                     return Err(ErrorWrapper::new_internal(
                         String::from(
                             format!("Cannot add item. Not enough free weight space in this container. Current weight total: {}, item weight: {}, weight limit: {}",
-                                    self.get_weight_total(), item.weight, weight_limit)
+                                    self.get_weight_total(), item.get_total_weight(), weight_limit)
                         )
                     ))
                 }
@@ -433,6 +464,16 @@ impl Container {
         }
         items
     }
+
+    // Every item held, including those inside any nested containers
+    pub fn to_cloned_nested_item_list(&self) -> Vec<Item> {
+        let mut items = Vec::new();
+        for c in self.get_contents() {
+            items.push(c.get_self_item().clone());
+            items.extend(c.to_cloned_nested_item_list());
+        }
+        items
+    }
 }
 
 #[cfg(test)]
@@ -440,7 +481,7 @@ mod tests {
     use uuid::Uuid;
 
     use crate::map::objects::container::{Container, ContainerType};
-    use crate::map::objects::items::{Item, MaterialType};
+    use crate::map::objects::items::{Item, ItemForm, MaterialType};
     use crate::map::tile::Colour;
 
     #[test]
@@ -553,9 +594,58 @@ mod tests {
         container.add(lockpick_1).expect_err("Lockpick 1 should not have been added to the container (too much weight)!");
         container.add(lockpick_2).expect_err("Lockpick 2 should not have been added to the container (too much weight)!");
 
-        // Along with the bag contents
+        // Along with the bag contents, where the identical gold bars are stacked
+        assert_eq!(2, container.get_contents().len());
+        assert_eq!(1, container.get_contents()[1].get_contents().len());
+        assert_eq!(3, container.get_contents()[1].get_contents()[0].get_self_item().get_quantity());
+    }
+
+    #[test]
+    fn test_container_add_item_stacks() {
+        // GIVEN we have a valid container
+        let mut container = Container::new(Uuid::new_v4(), "Test Container".to_owned(), 'X', 0.0, 1, ContainerType::OBJECT, 100);
+
+        // WHEN we add 3 identical gold bars and a silver bar
+        for _i in 0..3 {
+            container.add_item(Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::BAR, 'X', 2.0, 100)).unwrap();
+        }
+        container.add_item(Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::SILVER, ItemForm::BAR, 'X', 2.0, 50)).unwrap();
+
+        // THEN we expect the gold bars to be stacked together
         assert_eq!(2, container.get_contents().len());
-        assert_eq!(3, container.get_contents()[1].get_contents().len());
+        assert_eq!(3, container.get_contents()[0].get_self_item().get_quantity());
+        assert_eq!(1, container.get_contents()[1].get_self_item().get_quantity());
+        // AND the weight and value to account for the whole stack
+        assert_eq!(8.0, container.get_weight_total());
+        assert_eq!(350, container.get_loot_value());
+    }
+
+    #[test]
+    fn test_split_and_remove_from_stack() {
+        // GIVEN a container holding a stack of 5 gold coins
+        let mut container = Container::new(Uuid::new_v4(), "Test Container".to_owned(), 'X', 0.0, 1, ContainerType::OBJECT, 100);
+        let mut coins = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        coins.set_quantity(5);
+        container.add_item(coins.clone()).unwrap();
+        let stack = container.get_contents()[0].clone();
+
+        // WHEN we split 2 coins off of the stack
+        let split = stack.clone_quantity(2);
+
+        // THEN we get a new stack of 2
+        assert!(!split.id_equals(&stack));
+        assert_eq!(2, split.get_self_item().get_quantity());
+        // AND asking for the whole stack just copies it
+        assert!(stack.clone_quantity(5).id_equals(&stack));
+
+        // AND when we remove 2 from the original stack, only those are removed
+        let mut removing = stack.clone();
+        removing.get_self_item_mut().set_quantity(2);
+        assert!(container.remove_item(&removing));
+        assert_eq!(3, container.get_contents()[0].get_self_item().get_quantity());
+        // AND removing the rest removes the stack entirely
+        assert!(container.remove_item(&stack));
+        assert!(container.get_contents().is_empty());
     }
 
     #[test]
@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+
+use crate::map::objects::items::ItemForm;
+
+// How hard it is to recognise an item without using it, against a d20 roll plus Intelligence
+const RECALL_DIFFICULTY: i32 = 15;
+
+const POTION_APPEARANCES: [&str; 6] = ["Murky", "Fizzing", "Cloudy", "Crimson", "Bubbling", "Oily"];
+const SCROLL_SYLLABLES: [&str; 10] = ["XY", "ZZY", "FOO", "KLA", "ATU", "NIH", "PRO", "VEL", "QUA", "ZOR"];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PotionType {
+    Healing,
+    Poison
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ScrollType {
+    Identify,
+    Fire
+}
+
+impl PotionType {
+    pub fn all() -> Vec<PotionType> {
+        vec![PotionType::Healing, PotionType::Poison]
+    }
+}
+
+impl ScrollType {
+    pub fn all() -> Vec<ScrollType> {
+        vec![ScrollType::Identify, ScrollType::Fire]
+    }
+}
+
+// Every kind of item that needs identifying
+pub fn all_unidentified_forms() -> Vec<ItemForm> {
+    let mut forms: Vec<ItemForm> = PotionType::all().into_iter().map(ItemForm::POTION).collect();
+    forms.extend(ScrollType::all().into_iter().map(ItemForm::SCROLL));
+    forms
+}
+
+pub fn needs_identifying(form: &ItemForm) -> bool {
+    matches!(form, ItemForm::POTION(_) | ItemForm::SCROLL(_))
+}
+
+/*
+ * What the player has learnt about magic items this run
+ * Each kind of potion and scroll is given a random appearance from the run's seed
+ * Once a kind is identified, every item of that kind shows it's true name
+ */
+#[derive(Clone, Debug)]
+pub struct Identification {
+    seed: String,
+    appearances: HashMap<ItemForm, String>,
+    known: HashSet<ItemForm>
+}
+
+impl Identification {
+    // Rolls the appearances for a new run, with nothing yet identified
+    pub fn new(seed: String) -> Identification {
+        let mut rng: Pcg64 = Seeder::from(format!("{}-appearances", seed)).into_rng();
+        let mut appearances = HashMap::new();
+
+        let mut potion_appearances = POTION_APPEARANCES.to_vec();
+        potion_appearances.shuffle(&mut rng);
+        for (potion, appearance) in PotionType::all().into_iter().zip(potion_appearances) {
+            appearances.insert(ItemForm::POTION(potion), format!("{} Potion", appearance));
+        }
+
+        // Each scroll is dealt a pair of syllables no other scroll has, so no two share a label
+        let mut scroll_syllables = SCROLL_SYLLABLES.to_vec();
+        scroll_syllables.shuffle(&mut rng);
+        for (scroll, syllables) in ScrollType::all().into_iter().zip(scroll_syllables.chunks(2)) {
+            appearances.insert(ItemForm::SCROLL(scroll), format!("Scroll labelled {}", syllables.concat()));
+        }
+
+        Identification { seed, appearances, known: HashSet::new() }
+    }
+
+    pub fn is_identified(&self, form: &ItemForm) -> bool {
+        !needs_identifying(form) || self.known.contains(form)
+    }
+
+    // Returns true if this kind of item wasn't already known
+    pub fn identify(&mut self, form: &ItemForm) -> bool {
+        needs_identifying(form) && self.known.insert(form.clone())
+    }
+
+    pub fn get_appearance(&self, form: &ItemForm) -> Option<String> {
+        self.appearances.get(form).cloned()
+    }
+
+    /*
+     * An Intelligence check to recognise an item on sight, identifying it on success
+     * The roll is fixed per kind of item for the run, so looking again doesn't help
+     */
+    pub fn try_recall(&mut self, form: &ItemForm, intelligence: i8) -> bool {
+        if self.is_identified(form) {
+            return true;
+        }
+        let mut rng: Pcg64 = Seeder::from(format!("{}-{:?}", self.seed, form)).into_rng();
+        let roll: i32 = rng.random_range(1..=20);
+        if roll + intelligence as i32 >= RECALL_DIFFICULTY {
+            self.identify(form);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::map::objects::identification::{all_unidentified_forms, Identification, PotionType, ScrollType};
+    use crate::map::objects::items::ItemForm;
+
+    #[test]
+    fn test_identification() {
+        // GIVEN a new run
+        let mut identification = Identification::new(String::from("test"));
+        let healing = ItemForm::POTION(PotionType::Healing);
+
+        // THEN magic items start unidentified, with an appearance of their own
+        assert!(!identification.is_identified(&healing));
+        assert!(identification.is_identified(&ItemForm::COIN));
+        let appearances: Vec<String> = all_unidentified_forms().iter().map(|f| identification.get_appearance(f).unwrap()).collect();
+        assert!(identification.get_appearance(&healing).unwrap().ends_with("Potion"));
+        assert!(identification.get_appearance(&ItemForm::SCROLL(ScrollType::Fire)).unwrap().starts_with("Scroll labelled "));
+        // AND the same seed always gives the same appearances
+        let same_seed = Identification::new(String::from("test"));
+        assert_eq!(appearances, all_unidentified_forms().iter().map(|f| same_seed.get_appearance(f).unwrap()).collect::<Vec<String>>());
+
+        // WHEN we identify a kind of item
+        // THEN it's known for the rest of the run
+        assert!(identification.identify(&healing));
+        assert!(!identification.identify(&healing));
+        assert!(identification.is_identified(&healing));
+
+        // AND a new run knows nothing of it
+        let mut new_run = Identification::new(String::from("test"));
+        assert!(!new_run.is_identified(&healing));
+
+        // AND a high enough Intelligence always recalls an item
+        assert!(new_run.try_recall(&healing, 20));
+        assert!(new_run.is_identified(&healing));
+    }
+
+    #[test]
+    fn test_appearances_are_distinct() {
+        // GIVEN runs from many different seeds
+        for i in 0..500 {
+            let identification = Identification::new(format!("seed-{}", i));
+
+            // WHEN we look at each kind of item
+            let appearances: HashSet<String> = all_unidentified_forms().iter().map(|f| identification.get_appearance(f).unwrap()).collect();
+
+            // THEN no two kinds look the same
+            assert_eq!(all_unidentified_forms().len(), appearances.len(), "Appearances shared for seed-{}", i);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use uuid::Uuid;
 
 use crate::character::equipment::EquipmentSlot;
+use crate::engine::loot_table::Rarity;
+use crate::map::objects::identification::{Identification, PotionType, ScrollType};
 use crate::map::objects::weapon_builder::BladedWeaponType;
 use crate::map::tile::{Colour, Symbol};
 
@@ -78,13 +80,56 @@ impl MaterialType {
         }
     }
 
+    /* Abstract trade value of a Kg of this material, precious metals being worth the most
+     */
+    pub fn value_per_kg(&self) -> i32 {
+        return match self {
+            MaterialType::CLOTH => { 5 }
+            MaterialType::LEATHER => { 8 }
+            MaterialType::WOOD => { 2 }
+            MaterialType::STONE => { 1 }
+            MaterialType::BRONZE => { 20 }
+            MaterialType::TIN => { 10 }
+            MaterialType::IRON => { 15 }
+            MaterialType::STEEL => { 30 }
+            MaterialType::SILVER => { 200 }
+            MaterialType::GOLD => { 500 }
+            MaterialType::UNKNOWN => { 1 }
+        }
+    }
+
+    /* Abstract protection given by armour made of this material, from 1 (next to none) up to 6
+     * These are a ranking rather than measured values, ordered by hardness:
+     * - Steel protects best, then iron, then bronze, which is an alloy harder than the tin in it
+     * - The softer precious metals are mostly for show, so they rank with stone and leather
+     * The metals rank the same way as their weapon damage does (see WeaponBlueprint)
+     */
+    pub fn armour_rating(&self) -> i32 {
+        return match self {
+            MaterialType::CLOTH => { 1 }
+            MaterialType::LEATHER => { 2 }
+            MaterialType::WOOD => { 2 }
+            MaterialType::STONE => { 3 }
+            MaterialType::BRONZE => { 4 }
+            MaterialType::TIN => { 3 }
+            MaterialType::IRON => { 5 }
+            MaterialType::STEEL => { 6 }
+            MaterialType::SILVER => { 3 }
+            MaterialType::GOLD => { 2 }
+            MaterialType::UNKNOWN => { 1 }
+        }
+    }
+
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ItemForm {
     COIN,
     BAR,
     BLADED(BladedWeaponType),
+    // Magic items, which need identifying before their true names are known
+    POTION(PotionType),
+    SCROLL(ScrollType),
     OTHER(String)
 }
 
@@ -94,11 +139,40 @@ impl ItemForm {
             ItemForm::COIN => { String::from("Coin") }
             ItemForm::BAR => { String::from("Bar") }
             ItemForm::BLADED(_sword_type) => { String::from("Sword") }
+            ItemForm::POTION(potion_type) => { format!("Potion of {:?}", potion_type) }
+            ItemForm::SCROLL(scroll_type) => { format!("Scroll of {:?}", scroll_type) }
             ItemForm::OTHER(description) => { description }
         }
     }
 }
 
+/*
+ * Broad groupings of items, e.g for choosing which items are picked up automatically
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemCategory {
+    Coins,
+    Valuables,
+    Weapons,
+    Armour,
+    Other
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Handedness {
+    OneHanded,
+    TwoHanded
+}
+
+impl Handedness {
+    pub fn name(self) -> String {
+        match self {
+            Handedness::OneHanded => String::from("One-handed"),
+            Handedness::TwoHanded => String::from("Two-handed")
+        }
+    }
+}
+
 pub struct Dimensions {
     pub(crate) height: f32,
     pub(crate) width: f32,
@@ -123,7 +197,9 @@ pub struct Item {
     pub symbol : Symbol,
     pub weight : f32, // weight in Kilograms
     pub value : i32,
-    equipment_slot: Option<EquipmentSlot>
+    equipment_slot: Option<EquipmentSlot>,
+    quantity: u32, // How many identical items this one represents, the weight and value are for each one
+    rarity: Rarity // As rolled for loot, anything else is common
 }
 
 impl Item {
@@ -139,10 +215,29 @@ impl Item {
             self.name.clone()
         }
     }
+    // The name the player sees, which hides the true name of anything not yet identified
+    pub fn get_display_name(&self, identification: &Identification) -> String {
+        if self.is_identified(identification) {
+            self.get_name()
+        } else {
+            identification.get_appearance(&self.item_form).unwrap_or_else(|| self.get_name())
+        }
+    }
+    pub fn is_identified(&self, identification: &Identification) -> bool {
+        identification.is_identified(&self.item_form)
+    }
     pub fn get_default_name(&self) -> String {
         format!("{} {}", self.material_type.clone().name(), self.item_form.clone().name())
     }
 
+    pub fn get_item_form(&self) -> ItemForm {
+        self.item_form.clone()
+    }
+
+    pub fn get_material_type(&self) -> MaterialType {
+        self.material_type.clone()
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name = name;
     }
@@ -153,9 +248,80 @@ impl Item {
     pub fn get_value(&self) -> i32 {
         self.value.clone()
     }
+    pub fn get_quantity(&self) -> u32 {
+        self.quantity
+    }
+    pub fn set_quantity(&mut self, quantity: u32) {
+        self.quantity = quantity;
+    }
+    // The weight / value of the whole stack
+    pub fn get_total_weight(&self) -> f32 {
+        self.weight * self.quantity as f32
+    }
+    pub fn get_total_value(&self) -> i32 {
+        self.value * self.quantity as i32
+    }
+    // i.e "Gold Coin (x5)" for a stack of coins, otherwise just the (displayed) name
+    pub fn get_stack_name(&self, identification: &Identification) -> String {
+        if self.quantity > 1 {
+            format!("{} (x{})", self.get_display_name(identification), self.quantity)
+        } else {
+            self.get_display_name(identification)
+        }
+    }
+    // A new stack of the given quantity of this item
+    pub fn split(&self, quantity: u32) -> Item {
+        Item { id: Uuid::new_v4(), quantity, ..self.clone() }
+    }
+    // Only plain items stack, anything that could be equipped is kept separate
+    pub fn is_stackable(&self) -> bool {
+        self.item_type == ItemType::ITEM && !self.is_equipped()
+    }
+    pub fn can_stack_with(&self, other: &Item) -> bool {
+        self.is_stackable() && other.is_stackable()
+            && self.item_form == other.item_form
+            && self.material_type == other.material_type
+            && self.get_name() == other.get_name()
+            && self.rarity == other.rarity
+    }
+    pub fn get_rarity(&self) -> Rarity {
+        self.rarity
+    }
+    pub fn set_rarity(&mut self, rarity: Rarity) {
+        self.rarity = rarity;
+    }
     pub fn is_container(&self) -> bool {
         self.item_type == ItemType::CONTAINER
     }
+    pub fn get_category(&self) -> ItemCategory {
+        match (&self.item_type, &self.item_form) {
+            (ItemType::WEAPON(_), _) => ItemCategory::Weapons,
+            (ItemType::HEADGEAR | ItemType::TORSO | ItemType::LEGS, _) => ItemCategory::Armour,
+            (ItemType::ITEM, ItemForm::COIN) => ItemCategory::Coins,
+            (ItemType::ITEM, ItemForm::BAR) => ItemCategory::Valuables,
+            _ => ItemCategory::Other
+        }
+    }
+    pub fn get_weapon_damage(&self) -> Option<i32> {
+        match &self.item_type {
+            ItemType::WEAPON(weapon) => Some(weapon.damage),
+            _ => None
+        }
+    }
+    // Only longswords need both hands, any other weapon can be used in one
+    pub fn get_handedness(&self) -> Option<Handedness> {
+        match (&self.item_type, &self.item_form) {
+            (ItemType::WEAPON(_), ItemForm::BLADED(BladedWeaponType::LONG)) => Some(Handedness::TwoHanded),
+            (ItemType::WEAPON(_), _) => Some(Handedness::OneHanded),
+            _ => None
+        }
+    }
+    pub fn get_armour_rating(&self) -> Option<i32> {
+        match self.item_type {
+            ItemType::HEADGEAR | ItemType::TORSO | ItemType::LEGS => Some(self.material_type.armour_rating()),
+            _ => None
+        }
+    }
     pub fn is_equipped(&self) -> bool { self.equipment_slot.is_some() }
     pub fn id_equals(&self, other: &Item) -> bool {
         self.id == other.id
@@ -180,25 +346,41 @@ impl Item {
          UNKNOWN material type
      */
     pub fn with_defaults(name: String, weight : f32, value : i32) -> Item {
-        Item {id: Uuid::new_v4(), item_type: ItemType::ITEM, item_form: ItemForm::OTHER(name.clone()), material_type: MaterialType::UNKNOWN, name, symbol: DEFAULT_SYMBOL, weight, value, equipment_slot: None }
+        Item {id: Uuid::new_v4(), item_type: ItemType::ITEM, item_form: ItemForm::OTHER(name.clone()), material_type: MaterialType::UNKNOWN, name, symbol: DEFAULT_SYMBOL, weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
     }
 
     /*
         Builds a true item of the type ItemType::ITEM
      */
     pub fn new(id: Uuid, name: String, material_type: MaterialType, symbol: char, weight : f32, value : i32) -> Item {
-        Item {id, item_type: ItemType::ITEM, item_form: ItemForm::OTHER(name.clone()), material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None }
+        Item {id, item_type: ItemType::ITEM, item_form: ItemForm::OTHER(name.clone()), material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
     }
 
     pub fn new_with_form(id: Uuid, name: String, material_type: MaterialType, item_form: ItemForm, symbol: char, weight : f32, value : i32) -> Item {
-        Item {id, item_type: ItemType::ITEM, item_form, material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None }
+        Item {id, item_type: ItemType::ITEM, item_form, material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
+    }
+
+    /*
+      Builds a potion or scroll named for what it really is
+      Until it's identified it's shown by the appearance given to it's kind for the run
+     */
+    pub fn magic(id: Uuid, item_form: ItemForm, symbol: char, weight : f32, value : i32) -> Item {
+        let name = item_form.clone().name();
+        Item {id, item_type: ItemType::ITEM, item_form, material_type: MaterialType::UNKNOWN, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
     }
 
     /*
       Builds an Item with the type of ItemType::CONTAINER,
      */
     pub fn container_item(id: Uuid, name: String, symbol: char, weight : f32, value : i32) -> Item {
-        Item {id, item_type: ItemType::CONTAINER, item_form: ItemForm::OTHER(name.clone()), material_type: MaterialType::UNKNOWN, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None }
+        Item {id, item_type: ItemType::CONTAINER, item_form: ItemForm::OTHER(name.clone()), material_type: MaterialType::UNKNOWN, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
+    }
+
+    /*
+      Builds a piece of armour, the item type being the slot it's worn in i.e ItemType::HEADGEAR
+     */
+    pub fn armour(id: Uuid, name: String, item_type: ItemType, material_type: MaterialType, symbol: char, weight : f32, value : i32) -> Item {
+        Item {id, item_type, item_form: ItemForm::OTHER(name.clone()), material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
     }
 
     /*
@@ -206,7 +388,7 @@ impl Item {
       Builds an Item with the type of ItemType::WEAPON,
      */
     pub fn weapon(id: Uuid, name: String, item_form: ItemForm, material_type: MaterialType, symbol: char, weight : f32, value : i32, weapon: Weapon) -> Item {
-        Item {id, item_type: ItemType::WEAPON(weapon), item_form, material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None }
+        Item {id, item_type: ItemType::WEAPON(weapon), item_form, material_type, name, symbol: Symbol::new(symbol, Colour::White), weight, value, equipment_slot: None, quantity: 1, rarity: Rarity::Common }
     }
 
 }
@@ -216,8 +398,10 @@ impl Item {
 mod tests {
     use uuid::Uuid;
 
+    use crate::map::objects::identification::Identification;
     use crate::map::objects::items;
-    use crate::map::objects::items::{Item, MaterialType};
+    use crate::map::objects::items::{Handedness, Item, ItemCategory, ItemForm, ItemType, MaterialType, Weapon};
+    use crate::map::objects::weapon_builder::BladedWeaponType;
     use crate::map::tile::Colour;
 
     #[test]
@@ -246,4 +430,84 @@ mod tests {
         assert_eq!(1.0, item.weight);
         assert_eq!(1, item.value);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_category() {
+        // GIVEN a coin, a bar and a container
+        let coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        let bar = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::BAR, 'X', 1.0, 100);
+        let container = Item::container_item(Uuid::new_v4(), "Bag".to_owned(), 'X', 1.0, 1);
+
+        // WHEN we check their categories
+        // THEN we expect each to be grouped as expected
+        assert_eq!(ItemCategory::Coins, coin.get_category());
+        assert_eq!(ItemCategory::Valuables, bar.get_category());
+        assert_eq!(ItemCategory::Other, container.get_category());
+    }
+
+    #[test]
+    fn test_handedness_and_armour_rating() {
+        // GIVEN a steel longsword, a steel dagger and a leather helmet
+        let longsword = Item::weapon(Uuid::new_v4(), "".to_owned(), ItemForm::BLADED(BladedWeaponType::LONG), MaterialType::STEEL, 'X', 1.5, 10, Weapon { damage: 30 });
+        let dagger = Item::weapon(Uuid::new_v4(), "".to_owned(), ItemForm::BLADED(BladedWeaponType::DAGGER), MaterialType::STEEL, 'X', 0.3, 5, Weapon { damage: 30 });
+        let mut helmet = Item::new(Uuid::new_v4(), "Helmet".to_owned(), MaterialType::LEATHER, 'X', 1.0, 5);
+        helmet.item_type = ItemType::HEADGEAR;
+
+        // WHEN we check their details
+        // THEN only the longsword needs both hands
+        assert_eq!(Some(Handedness::TwoHanded), longsword.get_handedness());
+        assert_eq!(Some(Handedness::OneHanded), dagger.get_handedness());
+        assert_eq!(None, helmet.get_handedness());
+        // AND only the helmet gives armour
+        assert_eq!(Some(2), helmet.get_armour_rating());
+        assert_eq!(None, dagger.get_armour_rating());
+        assert_eq!(Some(30), dagger.get_weapon_damage());
+    }
+
+    #[test]
+    fn test_armour_rating_ranking() {
+        // GIVEN the materials armour can be made from
+        // WHEN we check their ratings
+        // THEN the hardest metals protect best
+        assert!(MaterialType::STEEL.armour_rating() > MaterialType::IRON.armour_rating());
+        assert!(MaterialType::IRON.armour_rating() > MaterialType::BRONZE.armour_rating());
+        assert!(MaterialType::BRONZE.armour_rating() > MaterialType::TIN.armour_rating());
+        // AND the precious metals protect no better than stone, and worse than the weapon metals
+        assert!(MaterialType::SILVER.armour_rating() <= MaterialType::STONE.armour_rating());
+        assert!(MaterialType::GOLD.armour_rating() < MaterialType::SILVER.armour_rating());
+        assert!(MaterialType::SILVER.armour_rating() < MaterialType::IRON.armour_rating());
+        // AND cloth gives the least protection
+        assert_eq!(1, MaterialType::CLOTH.armour_rating());
+        assert!(MaterialType::CLOTH.armour_rating() < MaterialType::LEATHER.armour_rating());
+    }
+
+    #[test]
+    fn test_can_stack_with() {
+        // GIVEN 2 gold coins, a silver coin and a gold bar
+        let coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        let other_coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        let silver_coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::SILVER, ItemForm::COIN, 'X', 0.01, 1);
+        let bar = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::BAR, 'X', 1.0, 100);
+        let container = Item::container_item(Uuid::new_v4(), "Bag".to_owned(), 'X', 1.0, 1);
+
+        // WHEN we check which can stack together
+        // THEN only the identical coins can
+        assert!(coin.can_stack_with(&other_coin));
+        assert!(!coin.can_stack_with(&silver_coin));
+        assert!(!coin.can_stack_with(&bar));
+        assert!(!container.can_stack_with(&container.clone()));
+    }
+
+    #[test]
+    fn test_stack_totals() {
+        // GIVEN a stack of 5 gold coins
+        let mut coin = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.5, 2);
+        coin.set_quantity(5);
+
+        // WHEN we check the stack details
+        // THEN the weight and value are for the whole stack
+        assert_eq!(2.5, coin.get_total_weight());
+        assert_eq!(10, coin.get_total_value());
+        assert_eq!("Gold Coin (x5)", coin.get_stack_name(&Identification::new(String::new())));
+    }
+}
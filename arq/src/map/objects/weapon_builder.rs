@@ -81,7 +81,7 @@ impl WeaponBlueprint {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum BladedWeaponType {
     DAGGER,
     ARMING,
@@ -141,13 +141,13 @@ impl WeaponBuilder {
 
     pub fn build(&self) -> Item {
         let blueprint = &self.blueprint;
-        Item::weapon(Uuid::new_v4(), blueprint.name.clone(), ItemForm::OTHER(blueprint.name.clone()), blueprint.material_type.clone(), blueprint.symbol.character.clone(), blueprint.weight.clone(), blueprint.value.clone(), blueprint.weapon.clone())
+        Item::weapon(Uuid::new_v4(), blueprint.name.clone(), blueprint.item_form.clone(), blueprint.material_type.clone(), blueprint.symbol.character, blueprint.weight, blueprint.value, blueprint.weapon.clone())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::map::objects::items::{ItemForm, ItemType, MaterialType, Weapon};
+    use crate::map::objects::items::{Handedness, ItemForm, ItemType, MaterialType, Weapon};
     use crate::map::objects::weapon_builder::{BladedWeaponType, WeaponBlueprint, WeaponBuilder};
 
     #[test]
@@ -194,4 +194,22 @@ mod tests {
         // Weight in Kilograms
         assert_eq!(1.5840001, weapon.weight);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn test_build_keeps_item_form() {
+        // GIVEN builders for each type of steel blade
+        let build = |sword_type: BladedWeaponType| WeaponBuilder::new(WeaponBlueprint::new(MaterialType::STEEL, ItemForm::BLADED(sword_type)).unwrap()).build();
+
+        // WHEN we build them
+        let dagger = build(BladedWeaponType::DAGGER);
+        let arming_sword = build(BladedWeaponType::ARMING);
+        let long_sword = build(BladedWeaponType::LONG);
+
+        // THEN they keep the form they were built from
+        assert_eq!(ItemForm::BLADED(BladedWeaponType::LONG), long_sword.get_item_form());
+        // AND only the longsword needs both hands
+        assert_eq!(Some(Handedness::OneHanded), dagger.get_handedness());
+        assert_eq!(Some(Handedness::OneHanded), arming_sword.get_handedness());
+        assert_eq!(Some(Handedness::TwoHanded), long_sword.get_handedness());
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use rand_pcg::Pcg64;
+
+use crate::map::map_generator::build_area_containers;
+use crate::map::objects::door::build_door;
+use crate::map::position::{build_rectangular_area, Area, Position};
+use crate::map::room::{build_room, Room};
+use crate::map::tile::{build_library, TileDetails, TileType};
+use crate::map::{Map, Tiles};
+
+pub const OVERWORLD_SIZE_X: u16 = 120;
+pub const OVERWORLD_SIZE_Y: u16 = 50;
+
+// The town is a crossroads with a building in each corner, cleared out of the wilderness
+const TOWN_HALF_WIDTH: u16 = 7;
+const TOWN_HALF_HEIGHT: u16 = 6;
+const BUILDING_WIDTH: u16 = 5;
+const BUILDING_HEIGHT: u16 = 4;
+
+// Keeps dungeon entrances away from the map edges and each other
+const EDGE_MARGIN: u16 = 2;
+const MIN_ENTRANCE_SPACING: u16 = 12;
+const ENTRANCE_PLACEMENT_ATTEMPTS: usize = 1000;
+
+/*
+ * The generated wilderness, along with the points of interest on it
+ */
+#[derive(Debug, Clone)]
+pub struct Overworld {
+    pub map: Map,
+    pub town_centre: Position,
+    pub dungeon_entrances: Vec<Position>
+}
+
+pub struct OverworldGenerator<'rng> {
+    rng: &'rng mut Pcg64,
+    map_area: Area,
+    dungeon_count: usize,
+    tile_library: HashMap<TileType, TileDetails>,
+    tiles: Tiles,
+    rooms: Vec<Room>
+}
+
+pub fn build_overworld_generator(rng: &mut Pcg64, map_area: Area, dungeon_count: usize) -> OverworldGenerator<'_> {
+    OverworldGenerator { rng, map_area, dungeon_count, tile_library: build_library(), tiles: Tiles { tiles: Vec::new() }, rooms: Vec::new() }
+}
+
+impl OverworldGenerator<'_> {
+    pub fn generate(&mut self) -> Overworld {
+        self.fill(TileType::Grass);
+
+        // Rough patches of terrain, anything placed afterwards is carved out of these
+        let total_area = self.map_area.get_total_area() as usize;
+        self.add_patches(TileType::Forest, total_area / 400, 60);
+        self.add_patches(TileType::Water, total_area / 1200, 40);
+
+        let town_centre = Position::new(self.map_area.width / 2, self.map_area.height / 2);
+        self.add_town(town_centre);
+
+        let dungeon_entrances = self.add_dungeon_entrances(town_centre);
+        for entrance in &dungeon_entrances {
+            self.add_road(town_centre, *entrance);
+        }
+
+        let containers = build_area_containers(self.map_area, &self.tiles);
+        let map = Map { area: self.map_area, tiles: self.tiles.clone(), rooms: self.rooms.clone(), containers };
+        log::info!("Generated overworld with {} dungeon entrances", dungeon_entrances.len());
+        Overworld { map, town_centre, dungeon_entrances }
+    }
+
+    fn set_tile(&mut self, position: Position, tile_type: TileType) {
+        let tile = self.tile_library[&tile_type].clone();
+        self.tiles.set_tile(position, tile);
+    }
+
+    fn get_tile_type(&self, position: Position) -> Option<TileType> {
+        self.tiles.get_tile(position).map(|t| t.tile_type)
+    }
+
+    fn fill(&mut self, tile_type: TileType) {
+        let tile = self.tile_library[&tile_type].clone();
+        self.tiles.tiles = vec![vec![tile; self.map_area.width as usize]; self.map_area.height as usize];
+    }
+
+    // Random walks from random starting points, leaving the given terrain behind
+    fn add_patches(&mut self, tile_type: TileType, count: usize, size: usize) {
+        let max_x = self.map_area.width - 1;
+        let max_y = self.map_area.height - 1;
+        for _ in 0..count {
+            let mut position = Position::new(self.rng.random_range(0..=max_x), self.rng.random_range(0..=max_y));
+            for _ in 0..size {
+                self.set_tile(position, tile_type);
+                match self.rng.random_range(0..4) {
+                    0 => position.x = position.x.saturating_sub(1),
+                    1 => position.x = (position.x + 1).min(max_x),
+                    2 => position.y = position.y.saturating_sub(1),
+                    _ => position.y = (position.y + 1).min(max_y)
+                }
+            }
+        }
+    }
+
+    fn add_town(&mut self, centre: Position) {
+        for y in centre.y - TOWN_HALF_HEIGHT..=centre.y + TOWN_HALF_HEIGHT {
+            for x in centre.x - TOWN_HALF_WIDTH..=centre.x + TOWN_HALF_WIDTH {
+                let tile_type = if x == centre.x || y == centre.y { TileType::Road } else { TileType::Grass };
+                self.set_tile(Position::new(x, y), tile_type);
+            }
+        }
+
+        // One building in each corner of the crossroads, with a door facing the road
+        let left = centre.x - BUILDING_WIDTH - 1;
+        let right = centre.x + 2;
+        let top = centre.y - BUILDING_HEIGHT - 1;
+        let bottom = centre.y + 2;
+        let door_offset = BUILDING_WIDTH / 2;
+        self.add_building(Position::new(left, top), Position::new(left + door_offset, top + BUILDING_HEIGHT - 1));
+        self.add_building(Position::new(right, top), Position::new(right + door_offset, top + BUILDING_HEIGHT - 1));
+        self.add_building(Position::new(left, bottom), Position::new(left + door_offset, bottom));
+        self.add_building(Position::new(right, bottom), Position::new(right + door_offset, bottom));
+    }
+
+    fn add_building(&mut self, start_position: Position, door_position: Position) {
+        let area = build_rectangular_area(start_position, BUILDING_WIDTH, BUILDING_HEIGHT);
+        for y in area.start_position.y..=area.end_position.y {
+            for x in area.start_position.x..=area.end_position.x {
+                let position = Position::new(x, y);
+                let on_side = x == area.start_position.x || x == area.end_position.x || y == area.start_position.y || y == area.end_position.y;
+                self.set_tile(position, if on_side { TileType::Wall } else { TileType::Room });
+            }
+        }
+        self.set_tile(door_position, TileType::Door);
+        self.rooms.push(build_room(area, vec![build_door(door_position)]));
+    }
+
+    fn is_valid_entrance(&self, position: Position, town_centre: Position, entrances: &[Position]) -> bool {
+        // Entrances sit either side of the town so roads to them never cut through it's buildings
+        let outside_town = position.x.abs_diff(town_centre.x) > TOWN_HALF_WIDTH + EDGE_MARGIN;
+        let spaced_out = entrances.iter().all(|e| e.x.abs_diff(position.x) + e.y.abs_diff(position.y) >= MIN_ENTRANCE_SPACING);
+        outside_town && spaced_out
+    }
+
+    fn add_dungeon_entrances(&mut self, town_centre: Position) -> Vec<Position> {
+        let mut entrances: Vec<Position> = Vec::new();
+        for _ in 0..ENTRANCE_PLACEMENT_ATTEMPTS {
+            if entrances.len() >= self.dungeon_count {
+                break;
+            }
+            let x = self.rng.random_range(EDGE_MARGIN..self.map_area.width - EDGE_MARGIN);
+            let y = self.rng.random_range(EDGE_MARGIN..self.map_area.height - EDGE_MARGIN);
+            let position = Position::new(x, y);
+            if self.is_valid_entrance(position, town_centre, &entrances) {
+                self.set_tile(position, TileType::DungeonEntrance);
+                entrances.push(position);
+            }
+        }
+
+        if entrances.len() < self.dungeon_count {
+            log::error!("Only managed to place {} of {} dungeon entrances", entrances.len(), self.dungeon_count);
+        }
+        entrances
+    }
+
+    // Lays a road out of town along the main street, then turns towards the destination (bridging any water on the way)
+    fn add_road(&mut self, from: Position, to: Position) {
+        let mut path = Vec::new();
+        let (start_x, end_x) = (from.x.min(to.x), from.x.max(to.x));
+        for x in start_x..=end_x {
+            path.push(Position::new(x, from.y));
+        }
+        let (start_y, end_y) = (from.y.min(to.y), from.y.max(to.y));
+        for y in start_y..=end_y {
+            path.push(Position::new(to.x, y));
+        }
+
+        for position in path {
+            match self.get_tile_type(position) {
+                Some(TileType::DungeonEntrance) | Some(TileType::Wall) | Some(TileType::Door) | Some(TileType::Room) => {},
+                _ => self.set_tile(position, TileType::Road)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use rand_pcg::Pcg64;
+    use rand_seeder::Seeder;
+
+    use crate::map::overworld_generator::{build_overworld_generator, Overworld, OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y};
+    use crate::map::position::{build_rectangular_area, Position};
+    use crate::map::tile::TileType;
+
+    fn generate(seed: &str) -> Overworld {
+        let mut rng: Pcg64 = Seeder::from(seed).into_rng();
+        let map_area = build_rectangular_area(Position::zero(), OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y);
+        build_overworld_generator(&mut rng, map_area, 3).generate()
+    }
+
+    #[test]
+    fn test_generate() {
+        // GIVEN an overworld generator for 3 dungeons
+        // WHEN we generate the overworld
+        let overworld = generate("test");
+
+        // THEN we expect a full size map with a town at it's centre
+        let map = &overworld.map;
+        assert_eq!(OVERWORLD_SIZE_Y as usize, map.tiles.tiles.len());
+        assert_eq!(OVERWORLD_SIZE_X as usize, map.tiles.tiles[0].len());
+        assert_eq!(TileType::Road, map.tiles.get_tile(overworld.town_centre).unwrap().tile_type);
+        assert_eq!(4, map.rooms.len());
+
+        // AND 3 dungeon entrances
+        assert_eq!(3, overworld.dungeon_entrances.len());
+        for entrance in &overworld.dungeon_entrances {
+            assert_eq!(TileType::DungeonEntrance, map.tiles.get_tile(*entrance).unwrap().tile_type);
+        }
+    }
+
+    #[test]
+    fn test_generate_entrances_reachable_from_town() {
+        // GIVEN a generated overworld
+        let overworld = generate("reachable");
+        let map = &overworld.map;
+
+        // WHEN we walk everywhere we can from the town centre
+        let mut visited = HashSet::new();
+        let mut to_visit = VecDeque::from([overworld.town_centre]);
+        while let Some(position) = to_visit.pop_front() {
+            if visited.insert(position) {
+                for neighbor in map.get_neighbors(position) {
+                    if map.is_traversable(neighbor) && !visited.contains(&neighbor) {
+                        to_visit.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        // THEN every dungeon entrance can be reached
+        for entrance in &overworld.dungeon_entrances {
+            assert!(visited.contains(entrance), "Dungeon entrance at {:?} is unreachable", entrance);
+        }
+    }
+
+    #[test]
+    fn test_generate_is_seeded() {
+        // GIVEN two overworlds generated from the same seed
+        let first = generate("seeded");
+        let second = generate("seeded");
+
+        // THEN we expect the same layout
+        assert_eq!(first.dungeon_entrances, second.dungeon_entrances);
+    }
+}
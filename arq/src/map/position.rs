@@ -48,6 +48,25 @@ impl Position {
         positions
     }
 
+    pub fn get_diagonal_neighbors(&self) -> Vec<Position> {
+        [Direction::UP_LEFT, Direction::UP_RIGHT, Direction::DOWN_LEFT, Direction::DOWN_RIGHT].iter()
+            .filter_map(|direction| self.get_adjacent_position(*direction))
+            .collect()
+    }
+
+    // The position one step in the given direction, if that doesn't go out of range
+    pub fn get_adjacent_position(&self, direction: Direction) -> Option<Position> {
+        let (offset_x, offset_y) = direction.get_offset();
+        let x = self.x.checked_add_signed(offset_x as i16)?;
+        let y = self.y.checked_add_signed(offset_y as i16)?;
+        Some(Position { x, y })
+    }
+
+    // The number of king's moves (diagonals included) between 2 positions
+    pub fn chebyshev_distance(&self, other: Position) -> u16 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
 
     pub fn equals(&self, position: Position) -> bool {
         return self.x == position.x && self.y == position.y;
@@ -132,10 +151,22 @@ impl Area {
         let start_pos = &self.start_position;
         let mut sides = Vec::new();
         for side in all_sides().iter() {
-            if *side == Side::LEFT || *side == Side::RIGHT {
-                sides.push(build_line(start_pos.clone(), self.height, side.clone()));
-            } else {
-                sides.push(build_line(start_pos.clone(), self.width, side.clone()));
+            match side {
+                Side::LEFT => {
+                    sides.push(build_line(*start_pos, self.height, Side::LEFT));
+                },
+                Side::TOP => {
+                    sides.push(build_line(*start_pos, self.width, Side::TOP));
+                },
+                // build_line offsets by the line size, which only lines up for square areas
+                Side::RIGHT => {
+                    let area = Area { start_position: Position { x: self.end_position.x, y: start_pos.y }, end_position: self.end_position, width: 1, height: self.height };
+                    sides.push(AreaSide { area, side: Side::RIGHT });
+                },
+                Side::BOTTOM => {
+                    let area = Area { start_position: Position { x: start_pos.x, y: self.end_position.y }, end_position: self.end_position, width: self.width, height: 1 };
+                    sides.push(AreaSide { area, side: Side::BOTTOM });
+                }
             }
         }
         sides
@@ -250,6 +281,83 @@ pub enum Side {
     BOTTOM
 }
 
+/*
+ * One of the 8 ways a character can step from a tile, diagonals included
+ * Unlike a Side, this has nothing to do with the shape of an Area
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum Direction {
+    UP,
+    DOWN,
+    LEFT,
+    RIGHT,
+    UP_LEFT,
+    UP_RIGHT,
+    DOWN_LEFT,
+    DOWN_RIGHT
+}
+
+impl Direction {
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Direction::UP_LEFT | Direction::UP_RIGHT | Direction::DOWN_LEFT | Direction::DOWN_RIGHT)
+    }
+
+    // The x/y change for a single step in this direction
+    pub fn get_offset(&self) -> (i32, i32) {
+        match self {
+            Direction::UP => (0, -1),
+            Direction::DOWN => (0, 1),
+            Direction::LEFT => (-1, 0),
+            Direction::RIGHT => (1, 0),
+            Direction::UP_LEFT => (-1, -1),
+            Direction::UP_RIGHT => (1, -1),
+            Direction::DOWN_LEFT => (-1, 1),
+            Direction::DOWN_RIGHT => (1, 1)
+        }
+    }
+
+    pub fn get_opposite(&self) -> Direction {
+        match self {
+            Direction::UP => Direction::DOWN,
+            Direction::DOWN => Direction::UP,
+            Direction::LEFT => Direction::RIGHT,
+            Direction::RIGHT => Direction::LEFT,
+            Direction::UP_LEFT => Direction::DOWN_RIGHT,
+            Direction::UP_RIGHT => Direction::DOWN_LEFT,
+            Direction::DOWN_LEFT => Direction::UP_RIGHT,
+            Direction::DOWN_RIGHT => Direction::UP_LEFT
+        }
+    }
+
+    // The direction a single step of the given x/y change is in, if it is one
+    pub fn from_offset(x: i32, y: i32) -> Option<Direction> {
+        match (x, y) {
+            (0, -1) => Some(Direction::UP),
+            (0, 1) => Some(Direction::DOWN),
+            (-1, 0) => Some(Direction::LEFT),
+            (1, 0) => Some(Direction::RIGHT),
+            (-1, -1) => Some(Direction::UP_LEFT),
+            (1, -1) => Some(Direction::UP_RIGHT),
+            (-1, 1) => Some(Direction::DOWN_LEFT),
+            (1, 1) => Some(Direction::DOWN_RIGHT),
+            _ => None
+        }
+    }
+}
+
+// Stepping out of a tile through one of it's sides
+impl From<Side> for Direction {
+    fn from(side: Side) -> Direction {
+        match side {
+            Side::LEFT => Direction::LEFT,
+            Side::RIGHT => Direction::RIGHT,
+            Side::TOP => Direction::UP,
+            Side::BOTTOM => Direction::DOWN
+        }
+    }
+}
+
 impl Distribution<Side> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Side {
         match rng.gen_range(0..=3) {
@@ -363,7 +471,7 @@ pub const fn build_rectangular_area(start_position : Position, size_x: u16, size
 mod tests {
     use ratatui::layout::Rect;
 
-    use crate::map::position::{build_rectangular_area, build_square_area, Area, Position, Side};
+    use crate::map::position::{build_rectangular_area, build_square_area, Area, Direction, Position, Side};
 
     #[test]
     fn test_get_neighbors_top_left() {
@@ -429,6 +537,59 @@ mod tests {
         assert_eq!(Position{x: u16::MAX/2, y: u16::MAX/2 + 1  }, *neighbors.get(3).unwrap());
     }
 
+    #[test]
+    fn test_get_diagonal_neighbors() {
+        // GIVEN a position in the top left corner
+        let start_pos = Position { x: 0, y: 0 };
+        // WHEN we call to get it's diagonal neighbors
+        let neighbors = start_pos.get_diagonal_neighbors();
+        // THEN we expect only the bottom right neighbor
+        assert_eq!(vec![Position { x: 1, y: 1 }], neighbors);
+
+        // AND a position with room on all sides to have 4 diagonal neighbors
+        let neighbors = Position { x: 5, y: 5 }.get_diagonal_neighbors();
+        assert_eq!(vec![Position::new(4, 4), Position::new(6, 4), Position::new(4, 6), Position::new(6, 6)], neighbors);
+    }
+
+    #[test]
+    fn test_get_adjacent_position() {
+        // GIVEN a position
+        let position = Position { x: 1, y: 0 };
+        // WHEN we step in each direction
+        // THEN we expect the adjacent positions, unless they're out of range
+        assert_eq!(Some(Position::new(0, 0)), position.get_adjacent_position(Direction::LEFT));
+        assert_eq!(Some(Position::new(2, 1)), position.get_adjacent_position(Direction::DOWN_RIGHT));
+        assert_eq!(Some(Position::new(0, 1)), position.get_adjacent_position(Direction::DOWN_LEFT));
+        assert_eq!(None, position.get_adjacent_position(Direction::UP_LEFT));
+        assert_eq!(None, position.get_adjacent_position(Direction::UP));
+    }
+
+    #[test]
+    fn test_direction_from_offset() {
+        // GIVEN the offset of each direction
+        let directions = [Direction::UP, Direction::DOWN, Direction::LEFT, Direction::RIGHT, Direction::UP_LEFT, Direction::UP_RIGHT, Direction::DOWN_LEFT, Direction::DOWN_RIGHT];
+        // WHEN we look up the direction for each offset
+        // THEN we expect the same direction back, and the opposite to be the reverse step
+        for direction in directions {
+            let (x, y) = direction.get_offset();
+            assert_eq!(Some(direction), Direction::from_offset(x, y));
+            assert_eq!(Some(direction.get_opposite()), Direction::from_offset(-x, -y));
+        }
+        // AND anything further than a single step isn't a direction
+        assert_eq!(None, Direction::from_offset(2, 0));
+        assert_eq!(None, Direction::from_offset(0, 0));
+    }
+
+    #[test]
+    fn test_chebyshev_distance() {
+        // GIVEN 2 positions 3 apart horizontally and 5 vertically
+        let a = Position { x: 1, y: 1 };
+        let b = Position { x: 4, y: 6 };
+        // THEN the distance is the larger of the 2
+        assert_eq!(5, a.chebyshev_distance(b));
+        assert_eq!(5, b.chebyshev_distance(a));
+    }
+
     #[test]
     fn test_build_square_area() {
         let start_pos = Position { x: 1, y: 2 };
@@ -851,6 +1012,51 @@ mod tests {
         assert_eq!(2, bottom.area.end_position.y);
     }
 
+    #[test]
+    fn test_get_sides_rectangular() {
+        // GIVEN a 4x2 area
+        let area = build_rectangular_area(Position { x: 1, y: 1}, 4, 2);
+        // WHEN we call to get the sides
+        let sides = area.get_sides();
+
+        // THEN we expect the right and bottom sides to sit along the end position
+        let right = sides[1];
+        assert_eq!(Side::RIGHT, right.side);
+        assert_eq!(Position { x: 4, y: 1}, right.area.start_position);
+        assert_eq!(Position { x: 4, y: 2}, right.area.end_position);
+
+        let bottom = sides[3];
+        assert_eq!(Side::BOTTOM, bottom.side);
+        assert_eq!(Position { x: 1, y: 2}, bottom.area.start_position);
+        assert_eq!(Position { x: 4, y: 2}, bottom.area.end_position);
+    }
+
+    #[test]
+    fn test_get_sides_on_the_edges() {
+        // GIVEN areas wider than they are tall and taller than they are wide, like the map and hand-made rooms
+        let wide_area = build_rectangular_area(Position { x: 0, y: 0}, 80, 30);
+        let tall_area = build_rectangular_area(Position { x: 2, y: 3}, 3, 6);
+
+        for area in [wide_area, tall_area] {
+            // WHEN we call to get the sides
+            let sides = area.get_sides();
+
+            // THEN every side runs the full length of the matching edge, and no further
+            for area_side in sides {
+                let positions = area_side.area.get_positions();
+                let on_edge = |p: &Position| match area_side.side {
+                    Side::LEFT => p.x == area.start_position.x,
+                    Side::RIGHT => p.x == area.end_position.x,
+                    Side::TOP => p.y == area.start_position.y,
+                    Side::BOTTOM => p.y == area.end_position.y
+                };
+                assert!(positions.iter().all(|p| area.contains_position(*p) && on_edge(p)), "{:?} side isn't on the edge of {:?}", area_side.side, area);
+                let length = if matches!(area_side.side, Side::LEFT | Side::RIGHT) { area.height } else { area.width };
+                assert_eq!(length as usize, positions.len());
+            }
+        }
+    }
+
     #[test]
     fn test_offset_positive() {
         // GIVEN an initial position
@@ -1,4 +1,3 @@
-use rand::rngs::ThreadRng;
 use rand::Rng;
 use uuid::Uuid;
 
@@ -38,7 +37,7 @@ impl Room {
         build_rectangular_area(start_position,  self.area.get_size_x()-2,  self.area.get_size_y() - 2 )
     }
 
-    pub fn random_inside_pos(&self, rng: &mut ThreadRng) -> Position {
+    pub fn random_inside_pos<R: Rng>(&self, rng: &mut R) -> Position {
         let inside_area = self.get_inside_area();
         let size_x = inside_area.get_size_x();
         let size_y = inside_area.get_size_y();
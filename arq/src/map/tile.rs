@@ -5,7 +5,9 @@ use std::collections::HashMap;
 #[derive(Copy, Clone)]
 pub enum TileType
 {
-    NoTile,Corridor,Room,Wall,Window,Door,Entry,Exit,Deadly
+    NoTile,Corridor,Room,Wall,Window,Door,Entry,Exit,Deadly,
+    // Overworld terrain
+    Grass,Forest,Water,Road,DungeonEntrance
 }
 
 #[derive(Copy)]
@@ -50,7 +52,12 @@ pub fn build_library() -> HashMap<TileType, TileDetails> {
         TileDetails {id: 5,     tile_type:  TileType::Door,     traversable: true, symbol: Symbol::new('=', Colour::White), name:  "Door".to_string()},
         TileDetails {id: 6,     tile_type:  TileType::Entry,    traversable: true, symbol: Symbol::new('^', Colour::Red), name:  "Entry".to_string()},
         TileDetails {id: 7,     tile_type:  TileType::Exit,     traversable: true, symbol: Symbol::new('^', Colour::Green), name:  "Exit".to_string()},
-        TileDetails {id: 8,     tile_type:  TileType::Deadly,   traversable: false, symbol: Symbol::new('!', Colour::Red), name:  "Deadly".to_string()}
+        TileDetails {id: 8,     tile_type:  TileType::Deadly,   traversable: false, symbol: Symbol::new('!', Colour::Red), name:  "Deadly".to_string()},
+        TileDetails {id: 9,     tile_type:  TileType::Grass,    traversable: true, symbol: Symbol::new(',', Colour::Green), name:  "Grass".to_string()},
+        TileDetails {id: 10,    tile_type:  TileType::Forest,   traversable: true, symbol: Symbol::new('T', Colour::Green), name:  "Forest".to_string()},
+        TileDetails {id: 11,    tile_type:  TileType::Water,    traversable: false, symbol: Symbol::new('~', Colour::Blue), name:  "Water".to_string()},
+        TileDetails {id: 12,    tile_type:  TileType::Road,     traversable: true, symbol: Symbol::new(':', Colour::Brown), name:  "Road".to_string()},
+        TileDetails {id: 13,    tile_type:  TileType::DungeonEntrance, traversable: true, symbol: Symbol::new('O', Colour::Red), name:  "Dungeon Entrance".to_string()}
     ];
 
     let mut tile_map = HashMap::new();
@@ -67,6 +74,6 @@ mod tests {
     #[test]
     fn test_build_library() {
         let library = build_library();
-        assert_eq!(9, library.len());
+        assert_eq!(14, library.len());
     }
 }
\ No newline at end of file
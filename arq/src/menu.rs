@@ -38,6 +38,22 @@ impl ToList for Menu {
     }
 }
 
+impl Menu {
+    // How wide the menu is drawn, i.e for working out what's been clicked
+    pub fn get_width(&self) -> usize {
+        let highlight_width = self.highlight_text.as_ref().map_or(0, |h| h.chars().count());
+        highlight_width + self.menu_titles.iter().map(|t| t.chars().count()).max().unwrap_or(0)
+    }
+
+    // Picks the given entry outright i.e when it's clicked
+    pub fn choose(&mut self, index: usize) {
+        if index < self.menu_titles.len() {
+            self.selection = index;
+            self.selected = true;
+        }
+    }
+}
+
 impl Selection for Menu {
     fn select_up(&mut self) {
         if self.selection > 0 {
@@ -82,7 +98,7 @@ impl Selection for Menu {
 pub fn build_start_menu(game_started: bool) -> Menu {
 
     let play_or_resume = if game_started { "Resume".to_owned() } else { "Play".to_owned() };
-    let titles = vec![play_or_resume, "Settings".to_owned(), "Info".to_owned(), "Quit".to_owned()];
+    let titles = vec![play_or_resume, "Settings".to_owned(), "Info".to_owned(), "Leaderboard".to_owned(), "Quit".to_owned()];
     let prompt = Some("-> ".to_owned());
     let menu = Menu { menu_titles : titles,  highlight_text : prompt, selection : 0, selected: false, exit: false};
     menu
@@ -154,4 +170,42 @@ mod tests {
         // THEN we expect the selection to be at the lowest possible index of 2
         assert_eq!(2, menu.selection);
     }
+
+    #[test]
+    fn test_menu_choose() {
+        // GIVEN a menu of 3 choices
+        let mut menu = build_test_menu();
+
+        // WHEN we choose the last one
+        menu.choose(2);
+
+        // THEN we expect it to be selected
+        assert_eq!(2, menu.selection);
+        assert!(menu.selected);
+    }
+
+    #[test]
+    fn test_menu_get_width() {
+        // GIVEN a menu of single letter choices
+        let mut menu = build_test_menu();
+        // THEN it's as wide as a letter
+        assert_eq!(1, menu.get_width());
+
+        // AND any highlight is drawn before them
+        menu.highlight_text = Some(String::from("> "));
+        assert_eq!(3, menu.get_width());
+    }
+
+    #[test]
+    fn test_menu_choose_out_of_range() {
+        // GIVEN a menu of 3 choices
+        let mut menu = build_test_menu();
+
+        // WHEN we choose past the end of it
+        menu.choose(3);
+
+        // THEN we expect nothing to change
+        assert_eq!(0, menu.selection);
+        assert!(!menu.selected);
+    }
 }
@@ -3,21 +3,31 @@ use rand::distr::Alphanumeric;
 use rand::{thread_rng, Rng};
 
 use crate::global_flags::GLOBALS;
+use crate::map::objects::items::ItemCategory;
 use crate::sound::sound::RESOURCE_MUSIC_BACKGROUND_FOLDER;
 use crate::ui::bindings::action_bindings::{build_default_action_keybindings, ActionKeyBindings};
 use crate::ui::bindings::input_bindings::{AllKeyBindings, CommandSpecificKeyBindings};
 use crate::ui::bindings::inventory_bindings::InventoryKeyBindings;
 use crate::ui::bindings::look_bindings::{build_default_look_keybindings, LookKeyBindings};
 use crate::ui::bindings::open_bindings::{build_default_open_keybindings, OpenKeyBindings};
+use crate::ui::glyphs::{get_glyph_set_dropdown_options, GlyphSet};
 use crate::ui::resolution::Resolution;
+use crate::ui::theme::{get_theme_dropdown_options, ThemeChoice};
 use crate::widget::stateful::dropdown_widget::{get_resolution_dropdown_options, DropdownOption, DropdownSetting};
 
 pub const SETTING_FOG_OF_WAR : &str = "Fog of War";
 pub const SETTING_RNG_SEED : &str = "Map RNG Seed";
 pub const SETTING_BG_MUSIC : &str = "Background music";
+pub const SETTING_SFX_VOLUME : &str = "Sound effects";
 pub const SETTING_RESOLUTION : &str = "Resolution";
+pub const SETTING_THEME : &str = "Theme";
+pub const SETTING_GLYPHS : &str = "Glyphs";
+pub const SETTING_DIAGONAL_MOVEMENT : &str = "Diagonal movement";
+pub const SETTING_AUTO_PICKUP_COINS : &str = "Auto-pickup coins";
+pub const SETTING_AUTO_PICKUP_VALUABLES : &str = "Auto-pickup valuables";
 
 pub const SETTING_BG_MUSIC_VOLUME_DEFAULT : u32 = 50;
+pub const SETTING_SFX_VOLUME_DEFAULT : u32 = 50;
 
 pub const RESOURCE_SETTINGS_FILE: &str = "resources/settings.json";
 
@@ -31,6 +41,8 @@ pub struct Settings {
     pub u32_settings : Vec<Setting<u32>>,
     pub string_settings : Vec<Setting<String>>,
     pub dropdown_settings : Vec<Setting<DropdownSetting<DropdownOption<Resolution>>>>,
+    pub theme_settings : Vec<Setting<DropdownSetting<DropdownOption<ThemeChoice>>>>,
+    pub glyph_settings : Vec<Setting<DropdownSetting<DropdownOption<GlyphSet>>>>,
     pub key_bindings: AllKeyBindings
 }
 
@@ -75,6 +87,27 @@ impl Settings {
         self.find_bool_setting_value(SETTING_FOG_OF_WAR.to_string()).or_else(|| Some(false)).unwrap()
     }
 
+    /*
+    * Either returns the bool value for SETTING_DIAGONAL_MOVEMENT, or defaults to true
+     */
+    pub fn is_diagonal_movement(&self) -> bool {
+        self.find_bool_setting_value(SETTING_DIAGONAL_MOVEMENT.to_string()).unwrap_or(true)
+    }
+
+    /*
+    * The categories of items picked up automatically when walking over them, none by default
+     */
+    pub fn get_auto_pickup_categories(&self) -> Vec<ItemCategory> {
+        let mut categories = Vec::new();
+        if self.find_bool_setting_value(SETTING_AUTO_PICKUP_COINS.to_string()).unwrap_or(false) {
+            categories.push(ItemCategory::Coins);
+        }
+        if self.find_bool_setting_value(SETTING_AUTO_PICKUP_VALUABLES.to_string()).unwrap_or(false) {
+            categories.push(ItemCategory::Valuables);
+        }
+        categories
+    }
+
     pub fn get_rng_seed(&self) -> Option<String> {
         if let Some(seed_override) = GLOBALS.rng_seed_override {
             return Some(String::from(seed_override))
@@ -90,9 +123,34 @@ impl Settings {
         self.find_u32_setting_value(SETTING_BG_MUSIC.to_string()).or_else(|| Some(100)).unwrap()
     }
 
+    /*
+    * Either returns the u32 value for SETTING_SFX_VOLUME, or defaults to SETTING_SFX_VOLUME_DEFAULT
+     */
+    pub fn get_sfx_volume(&self) -> u32 {
+        self.find_u32_setting_value(SETTING_SFX_VOLUME.to_string()).unwrap_or(SETTING_SFX_VOLUME_DEFAULT)
+    }
+
     pub fn get_resolution(&self) -> DropdownOption<Resolution> {
         self.find_dropdown_setting_value(SETTING_RESOLUTION.to_string()).unwrap()
     }
+
+    /*
+    * Either returns the chosen theme for SETTING_THEME, or defaults to the Default theme
+     */
+    pub fn get_theme(&self) -> ThemeChoice {
+        self.theme_settings.iter().find(|setting| setting.name == SETTING_THEME)
+            .and_then(|setting| setting.value.chosen_option.value)
+            .unwrap_or(ThemeChoice::Default)
+    }
+
+    /*
+    * Either returns the chosen glyph set for SETTING_GLYPHS, or defaults to ASCII
+     */
+    pub fn get_glyph_set(&self) -> GlyphSet {
+        self.glyph_settings.iter().find(|setting| setting.name == SETTING_GLYPHS)
+            .and_then(|setting| setting.value.chosen_option.value)
+            .unwrap_or(GlyphSet::Ascii)
+    }
 }
 
 pub fn build_default_bindings() -> AllKeyBindings {
@@ -110,8 +168,12 @@ pub fn build_settings() -> Settings {
     let settings_raw = fs::read_to_string(RESOURCE_SETTINGS_FILE).unwrap();
     let settings_json : serde_json::Value = serde_json::from_str(&settings_raw).unwrap();
     let bg_music_volume_default : u32 = settings_json.get("BG_MUSIC_VOLUME_DEFAULT").unwrap().as_u64().unwrap() as u32;
+    let sfx_volume_default : u32 = settings_json.get("SFX_VOLUME_DEFAULT").and_then(|v| v.as_u64()).map_or(SETTING_SFX_VOLUME_DEFAULT, |v| v as u32);
     
     let fog_of_war : Setting<bool> = Setting { name: SETTING_FOG_OF_WAR.to_string(), value: false };
+    let diagonal_movement : Setting<bool> = Setting { name: SETTING_DIAGONAL_MOVEMENT.to_string(), value: true };
+    let auto_pickup_coins : Setting<bool> = Setting { name: SETTING_AUTO_PICKUP_COINS.to_string(), value: false };
+    let auto_pickup_valuables : Setting<bool> = Setting { name: SETTING_AUTO_PICKUP_VALUABLES.to_string(), value: false };
     // Generate a new random seed
     let random_seed: String = thread_rng()
         .sample_iter(&Alphanumeric)
@@ -120,6 +182,7 @@ pub fn build_settings() -> Settings {
         .collect();
     let map_seed : Setting<String> = Setting { name: SETTING_RNG_SEED.to_string(), value: random_seed };
     let bg_music_volume : Setting<u32> = Setting { name: SETTING_BG_MUSIC.to_string(), value: bg_music_volume_default };
+    let sfx_volume : Setting<u32> = Setting { name: SETTING_SFX_VOLUME.to_string(), value: sfx_volume_default };
 
 
     let resolution_options = get_resolution_dropdown_options();
@@ -129,7 +192,21 @@ pub fn build_settings() -> Settings {
         chosen_option: initial_option.clone()
     };
     let resolution : Setting<DropdownSetting<DropdownOption<Resolution>>> = Setting { name: SETTING_RESOLUTION.to_string(), value: resolution_dropdown_setting };
-    Settings { bool_settings: vec![fog_of_war], string_settings: vec![map_seed], u32_settings: vec![bg_music_volume], dropdown_settings: vec![resolution], key_bindings: build_default_bindings() }
+
+    let theme_options = get_theme_dropdown_options();
+    let theme_dropdown_setting : DropdownSetting<DropdownOption<ThemeChoice>> = DropdownSetting {
+        chosen_option: theme_options[0].clone(),
+        options: theme_options
+    };
+    let theme : Setting<DropdownSetting<DropdownOption<ThemeChoice>>> = Setting { name: SETTING_THEME.to_string(), value: theme_dropdown_setting };
+
+    let glyph_options = get_glyph_set_dropdown_options();
+    let glyph_dropdown_setting : DropdownSetting<DropdownOption<GlyphSet>> = DropdownSetting {
+        chosen_option: glyph_options[0].clone(),
+        options: glyph_options
+    };
+    let glyphs : Setting<DropdownSetting<DropdownOption<GlyphSet>>> = Setting { name: SETTING_GLYPHS.to_string(), value: glyph_dropdown_setting };
+    Settings { bool_settings: vec![fog_of_war, diagonal_movement, auto_pickup_coins, auto_pickup_valuables], string_settings: vec![map_seed], u32_settings: vec![bg_music_volume, sfx_volume], dropdown_settings: vec![resolution], theme_settings: vec![theme], glyph_settings: vec![glyphs], key_bindings: build_default_bindings() }
 }
 
 pub trait Toggleable {
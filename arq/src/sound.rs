@@ -1,2 +1,5 @@
 pub mod sound;
-pub mod audio_sink;
\ No newline at end of file
+pub mod audio_backend;
+pub mod rodio_backend;
+pub mod sound_effects;
+pub mod music;
@@ -0,0 +1,96 @@
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+use crate::sound::music::MusicContext;
+use crate::sound::sound_effects::SoundEffect;
+
+/*
+ * Somewhere for sound to go
+ * The game only talks to this, so it can carry on the same whether or not there's anything to hear it
+ */
+pub trait AudioBackend {
+    // A short name for logging which backend was chosen
+    fn name(&self) -> &'static str;
+    fn play(&mut self);
+    // Volume is a percentage, from 0 to 100
+    fn configure(&mut self, volume: u32);
+    // Plays a short clip over anything already playing
+    fn play_effect(&mut self, effect: SoundEffect);
+    // Changes the music to suit what's going on in game
+    fn set_music_context(&mut self, context: MusicContext);
+}
+
+// Used when there's no output device or music, everything is quietly ignored
+pub struct SilentBackend {}
+
+impl SilentBackend {
+    pub fn new() -> SilentBackend {
+        SilentBackend {}
+    }
+}
+
+impl AudioBackend for SilentBackend {
+    fn name(&self) -> &'static str {
+        "silent"
+    }
+
+    fn play(&mut self) {}
+
+    fn configure(&mut self, _volume: u32) {}
+
+    fn play_effect(&mut self, _effect: SoundEffect) {}
+
+    fn set_music_context(&mut self, _context: MusicContext) {}
+}
+
+/*
+ * Keeps a log of what would have been heard, for tests
+ * Clones share the same log, so a test can keep one while the game owns the other
+ */
+#[cfg(test)]
+#[derive(Clone)]
+pub struct RecordingBackend {
+    recorded: Arc<Mutex<Vec<String>>>
+}
+
+#[cfg(test)]
+impl RecordingBackend {
+    pub fn new() -> RecordingBackend {
+        RecordingBackend { recorded: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    fn record(&self, event: String) {
+        log::info!("Recorded audio: {}", event);
+        match self.recorded.lock() {
+            Ok(mut recorded) => recorded.push(event),
+            Err(e) => log::warn!("Failed to record audio event: {}", e)
+        }
+    }
+
+    pub fn get_recorded(&self) -> Vec<String> {
+        self.recorded.lock().map(|recorded| recorded.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+impl AudioBackend for RecordingBackend {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn play(&mut self) {
+        self.record(String::from("play"));
+    }
+
+    fn configure(&mut self, volume: u32) {
+        self.record(format!("volume {}", volume));
+    }
+
+    fn play_effect(&mut self, effect: SoundEffect) {
+        self.record(format!("sfx {}", effect.clip_name()));
+    }
+
+    fn set_music_context(&mut self, context: MusicContext) {
+        self.record(format!("music {:?}", context));
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use log::{info, warn};
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use rodio::{Decoder, OutputStreamHandle, Sink};
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::time::sleep;
+
+use crate::error::errors::ErrorWrapper;
+
+pub const RESOURCE_MUSIC_MAPPING_FILE: &str = "resources/music.json";
+
+// Used when the mapping file doesn't say where deep levels start
+const DEEP_FROM_DEPTH_DEFAULT: usize = 4;
+// How often to check whether a track has finished
+const MUSIC_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CROSSFADE_DURATION: Duration = Duration::from_secs(2);
+const CROSSFADE_STEPS: u32 = 20;
+
+// What's going on in the game, which decides what music is played
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MusicContext {
+    Menu,
+    // Exploring at the given depth, the overworld being 0
+    Level(usize),
+    Combat,
+    GameOver
+}
+
+/*
+ * Which tracks go with which context, as read from the mapping file
+ * Each context is given a folder of tracks within the music folder, i.e "resources/background/combat"
+ */
+pub struct MusicMapping {
+    music_folder: String,
+    deep_from_depth: usize,
+    folders: HashMap<String, String>
+}
+
+impl MusicMapping {
+    // With no mapping, any track in the music folder can be played at any time
+    pub fn new(music_folder: &str) -> MusicMapping {
+        MusicMapping { music_folder: music_folder.to_string(), deep_from_depth: DEEP_FROM_DEPTH_DEFAULT, folders: HashMap::new() }
+    }
+
+    pub fn load(mapping_file: &str, music_folder: &str) -> Result<MusicMapping, ErrorWrapper> {
+        let raw = fs::read_to_string(mapping_file)?;
+        let value: Value = serde_json::from_str(&raw)
+            .map_err(|e| ErrorWrapper::new_internal(format!("Failed to parse music mapping: {}", e)))?;
+
+        let mut mapping = MusicMapping::new(music_folder);
+        if let Some(depth) = value.get("DEEP_FROM_DEPTH").and_then(|d| d.as_u64()) {
+            mapping.deep_from_depth = depth as usize;
+        }
+        let tracks = value.get("TRACKS").and_then(|t| t.as_object())
+            .ok_or(ErrorWrapper::new_internal(String::from("Music mapping is missing the 'TRACKS' field")))?;
+        for (key, folder) in tracks {
+            if let Some(folder) = folder.as_str() {
+                mapping.folders.insert(key.clone(), folder.to_string());
+            }
+        }
+        Ok(mapping)
+    }
+
+    // The key in the mapping file for the given context
+    pub fn get_key(&self, context: MusicContext) -> &'static str {
+        match context {
+            MusicContext::Menu => "MENU",
+            MusicContext::Level(depth) if depth >= self.deep_from_depth => "DEEP",
+            MusicContext::Level(_) => "SHALLOW",
+            MusicContext::Combat => "COMBAT",
+            MusicContext::GameOver => "GAME_OVER"
+        }
+    }
+
+    // Every track for the given key, falling back to those directly in the music folder
+    pub fn get_tracks(&self, key: &str) -> Vec<PathBuf> {
+        let mapped = self.folders.get(key)
+            .map(|folder| list_tracks(&Path::new(&self.music_folder).join(folder)))
+            .unwrap_or_default();
+        if mapped.is_empty() {
+            return list_tracks(Path::new(&self.music_folder));
+        }
+        mapped
+    }
+}
+
+fn list_tracks(folder: &Path) -> Vec<PathBuf> {
+    let mut tracks: Vec<PathBuf> = fs::read_dir(folder)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()).collect())
+        .unwrap_or_default();
+    // Sorted so the choice only depends on the RNG, not the order the filesystem gives
+    tracks.sort();
+    tracks
+}
+
+// The state the music is driven by, as set by the game
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicState {
+    pub context: MusicContext,
+    pub volume: f32,
+    pub playing: bool
+}
+
+impl MusicState {
+    pub fn new() -> MusicState {
+        MusicState { context: MusicContext::Menu, volume: 1.0, playing: false }
+    }
+}
+
+// The volumes of the incoming and outgoing tracks at the given step of a crossfade
+pub fn crossfade_volumes(step: u32, steps: u32, volume: f32) -> (f32, f32) {
+    let progress = step.min(steps) as f32 / steps as f32;
+    (volume * progress, volume * (1.0 - progress))
+}
+
+async fn crossfade(previous: Option<Sink>, next: &Sink, volume: f32) {
+    for step in 1..=CROSSFADE_STEPS {
+        let (next_volume, previous_volume) = crossfade_volumes(step, CROSSFADE_STEPS, volume);
+        next.set_volume(next_volume);
+        if let Some(previous) = &previous {
+            previous.set_volume(previous_volume);
+        }
+        sleep(CROSSFADE_DURATION / CROSSFADE_STEPS).await;
+    }
+    if let Some(previous) = previous {
+        previous.stop();
+    }
+}
+
+fn start_track(stream_handle: &OutputStreamHandle, tracks: &[PathBuf], rng: &mut impl Rng) -> Result<Sink, ErrorWrapper> {
+    let track = tracks.choose(rng).ok_or(ErrorWrapper::new_internal(String::from("There are no tracks to play")))?;
+    info!("Picked a new background track: {:?}", track);
+    let decoder = Decoder::new(BufReader::new(File::open(track)?))
+        .map_err(|e| ErrorWrapper::new_internal(format!("Failed to decode background track: {:?}, {}", track, e)))?;
+    let sink = Sink::try_new(stream_handle)
+        .map_err(|e| ErrorWrapper::new_internal(format!("Failed to create an audio sink: {}", e)))?;
+    // Silent until faded in
+    sink.set_volume(0.0);
+    sink.append(decoder);
+    Ok(sink)
+}
+
+/*
+ * Plays music to suit the game's state until the sender is dropped
+ * A new track is crossfaded in whenever the context changes, or the last track ends
+ * Music is only a nicety, so any failure is logged and the music waits for the next change
+ */
+pub async fn play_music(stream_handle: OutputStreamHandle, mapping: MusicMapping, mut state_receiver: watch::Receiver<MusicState>) {
+    // Music has an RNG of it's own, so it can never change what's generated in game
+    let mut rng = Pcg64::from_os_rng();
+    let mut current: Option<(&'static str, Sink)> = None;
+    let mut failed_key: Option<&'static str> = None;
+    loop {
+        let state = *state_receiver.borrow_and_update();
+        if state.playing {
+            let key = mapping.get_key(state.context);
+            let needs_track = current.as_ref().is_none_or(|(current_key, sink)| *current_key != key || sink.empty());
+            if needs_track && failed_key != Some(key) {
+                match start_track(&stream_handle, &mapping.get_tracks(key), &mut rng) {
+                    Ok(sink) => {
+                        failed_key = None;
+                        crossfade(current.take().map(|(_, previous)| previous), &sink, state.volume).await;
+                        current = Some((key, sink));
+                    },
+                    Err(e) => {
+                        warn!("Failed to play music for {}: {}", key, e);
+                        failed_key = Some(key);
+                    }
+                }
+            }
+            if let Some((_, sink)) = &current {
+                sink.set_volume(state.volume);
+                sink.play();
+            }
+        } else if let Some((_, sink)) = &current {
+            sink.pause();
+        }
+
+        tokio::select! {
+            changed = state_receiver.changed() => {
+                if changed.is_err() {
+                    info!("Stopping music..");
+                    return;
+                }
+            },
+            _ = sleep(MUSIC_POLL_INTERVAL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    use crate::sound::music::{crossfade_volumes, MusicContext, MusicMapping, RESOURCE_MUSIC_MAPPING_FILE};
+
+    #[test]
+    fn test_music_mapping() {
+        // GIVEN a music folder with combat tracks of their own, and a track for everything else
+        let folder = std::env::temp_dir().join(format!("arq-music-{}", Uuid::new_v4()));
+        fs::create_dir_all(folder.join("combat")).unwrap();
+        fs::write(folder.join("combat").join("battle.mp3"), []).unwrap();
+        fs::write(folder.join("ambient.mp3"), []).unwrap();
+
+        // WHEN we load the mapping for it
+        let mapping = MusicMapping::load(RESOURCE_MUSIC_MAPPING_FILE, folder.to_str().unwrap()).unwrap();
+
+        // THEN each context has it's own key, with depth deciding between shallow and deep levels
+        assert_eq!("MENU", mapping.get_key(MusicContext::Menu));
+        assert_eq!("SHALLOW", mapping.get_key(MusicContext::Level(0)));
+        assert_eq!("DEEP", mapping.get_key(MusicContext::Level(10)));
+        assert_eq!("GAME_OVER", mapping.get_key(MusicContext::GameOver));
+        // AND combat uses it's own tracks, while contexts without any fall back to the music folder
+        assert_eq!(vec![folder.join("combat").join("battle.mp3")], mapping.get_tracks(mapping.get_key(MusicContext::Combat)));
+        assert_eq!(vec![folder.join("ambient.mp3")], mapping.get_tracks(mapping.get_key(MusicContext::Menu)));
+        fs::remove_dir_all(&folder).unwrap();
+
+        // AND a missing folder has no tracks at all
+        assert_eq!(Vec::<PathBuf>::new(), MusicMapping::new("resources/no-such-folder").get_tracks("MENU"));
+    }
+
+    #[test]
+    fn test_crossfade_volumes() {
+        // GIVEN a crossfade at half volume
+        // THEN the incoming track rises as the outgoing one falls
+        assert_eq!((0.0, 0.5), crossfade_volumes(0, 10, 0.5));
+        assert_eq!((0.25, 0.25), crossfade_volumes(5, 10, 0.5));
+        assert_eq!((0.5, 0.0), crossfade_volumes(10, 10, 0.5));
+        // AND never beyond the end of the fade
+        assert_eq!((0.5, 0.0), crossfade_volumes(12, 10, 0.5));
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+
+use crate::error::errors::ErrorWrapper;
+use crate::sound::audio_backend::AudioBackend;
+use crate::sound::music::{play_music, MusicContext, MusicMapping, MusicState};
+use crate::sound::sound_effects::{SoundCatalogue, SoundEffect};
+
+// Plays through the default output device
+pub struct RodioBackend {
+    // Sound stops as soon as the stream is dropped, so it's kept alongside the handle
+    _os : OutputStream,
+    stream_handle: OutputStreamHandle,
+    catalogue: SoundCatalogue,
+    // Drives the music task, if there is one. Dropping this stops the music
+    music: Option<watch::Sender<MusicState>>,
+    volume: f32
+}
+
+impl RodioBackend {
+    // Fails if there's no output device to play through, i.e over SSH or in a container
+    pub fn try_new(catalogue: SoundCatalogue) -> Result<RodioBackend, ErrorWrapper> {
+        let (os, stream_handle) = OutputStream::try_default()
+            .map_err(|e| ErrorWrapper::new_internal(format!("No audio output device available: {}", e)))?;
+        Ok(RodioBackend { _os: os, stream_handle, catalogue, music: None, volume: 1.0 })
+    }
+
+    // Starts playing music in the background, to suit the context it's given
+    pub fn start_music(&mut self, mapping: MusicMapping) -> Result<(), ErrorWrapper> {
+        let runtime = Handle::try_current()
+            .map_err(|e| ErrorWrapper::new_internal(format!("No async runtime to play music on: {}", e)))?;
+        log::info!("Starting background music..");
+        let (sender, receiver) = watch::channel(MusicState { volume: self.volume, ..MusicState::new() });
+        runtime.spawn(play_music(self.stream_handle.clone(), mapping, receiver));
+        self.music = Some(sender);
+        Ok(())
+    }
+
+    fn update_music(&self, update: impl FnOnce(&mut MusicState)) {
+        if let Some(music) = &self.music {
+            music.send_modify(update);
+        }
+    }
+
+    fn try_play_effect(&self, effect: SoundEffect) -> Result<(), ErrorWrapper> {
+        let clip = match self.catalogue.get_clip(effect) {
+            Some(clip) => clip,
+            None => return Ok(())
+        };
+        let decoder = Decoder::new(BufReader::new(File::open(clip)?))
+            .map_err(|e| ErrorWrapper::new_internal(format!("Failed to decode sound effect: {:?}, {}", clip, e)))?;
+        // Each effect gets a sink of it's own, so effects can overlap one another
+        let sink = Sink::try_new(&self.stream_handle)
+            .map_err(|e| ErrorWrapper::new_internal(format!("Failed to create an audio sink: {}", e)))?;
+        sink.set_volume(self.volume);
+        sink.append(decoder);
+        sink.detach();
+        Ok(())
+    }
+}
+
+impl AudioBackend for RodioBackend {
+    fn name(&self) -> &'static str {
+        "rodio"
+    }
+
+    fn play(&mut self) {
+        self.update_music(|state| state.playing = true);
+    }
+
+    fn configure(&mut self, volume: u32) {
+        self.volume = volume as f32 / 100.0;
+        let volume = self.volume;
+        self.update_music(|state| state.volume = volume);
+    }
+
+    fn play_effect(&mut self, effect: SoundEffect) {
+        if let Err(e) = self.try_play_effect(effect) {
+            log::warn!("Failed to play sound effect {:?}: {}", effect, e);
+        }
+    }
+
+    fn set_music_context(&mut self, context: MusicContext) {
+        self.update_music(|state| state.context = context);
+    }
+}
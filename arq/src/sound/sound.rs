@@ -1,88 +1,157 @@
-use std::fs;
-use std::fs::{File, ReadDir};
-use std::io::{BufReader, Error, ErrorKind};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
-
-use log::{error, info};
-use rand::seq::IteratorRandom;
-use rand::{Rng, SeedableRng};
-use rand_pcg::Pcg64;
-use rodio::{Decoder, OutputStream, Sink};
-use tokio::task;
-use tokio::time::sleep;
-
-use crate::sound::audio_sink::AudioSink;
+use std::path::Path;
+
+use log::{info, warn};
+
+use crate::sound::audio_backend::{AudioBackend, SilentBackend};
+use crate::sound::music::{MusicContext, MusicMapping, RESOURCE_MUSIC_MAPPING_FILE};
+use crate::sound::rodio_backend::RodioBackend;
+use crate::sound::sound_effects::{SoundCatalogue, SoundEffect, RESOURCE_SFX_FOLDER};
 
 pub const RESOURCE_MUSIC_BACKGROUND_FOLDER: &str = "resources/background";
 
+/*
+ * Where the game's sound goes
+ * Anything in the engine that makes a sound is lent these to play it through
+ */
 pub struct SoundSinks {
-    bg_sink: AudioSink
+    bg_sink: Box<dyn AudioBackend>,
+    sfx_sink: Box<dyn AudioBackend>,
+    music_context: Option<MusicContext>
 }
 
 pub fn build_sound_sinks() -> SoundSinks {
-    let bg_sink = AudioSink::new();
-    let mut sinks = SoundSinks { bg_sink };
-    sinks.setup_background_music();
-    return sinks;
+    SoundSinks::new(build_bg_backend(RESOURCE_MUSIC_BACKGROUND_FOLDER), build_sfx_backend(RESOURCE_SFX_FOLDER))
 }
 
-pub fn pick_background_track(paths: &mut ReadDir, rng: &mut impl Rng) -> Result<std::io::BufReader<std::fs::File>, std::io::Error> {
-    let chosen_dir = paths.choose(rng);
-    if let Some(dir_entry) = chosen_dir {
-        match dir_entry {
-            Ok(de) => {
-                let file_path = de.path();
-                info!("Picked a new background track: {:?}", file_path);
-                let path = de.path();
-                return Ok(BufReader::new(File::open(path).unwrap()))
-            },
-            Err(e) => {
-                return Err(e)
+/*
+ * Picks where background music goes, falling back to silence rather than failing
+ * Without music to play or a device to play it on (i.e a headless server) there's nothing to hear anyway
+ */
+pub fn build_bg_backend(music_folder: &str) -> Box<dyn AudioBackend> {
+    if !Path::new(music_folder).is_dir() {
+        warn!("No background music found at: {}, continuing without sound.", music_folder);
+        return Box::new(SilentBackend::new());
+    }
+
+    let mapping = MusicMapping::load(RESOURCE_MUSIC_MAPPING_FILE, music_folder).unwrap_or_else(|e| {
+        warn!("Failed to load the music mapping: {}, any track may play at any time.", e);
+        MusicMapping::new(music_folder)
+    });
+    match RodioBackend::try_new(SoundCatalogue::new()) {
+        Ok(mut backend) => {
+            if let Err(e) = backend.start_music(mapping) {
+                warn!("{}, continuing without music.", e);
             }
+            Box::new(backend)
+        },
+        Err(e) => {
+            warn!("{}, continuing without sound.", e);
+            Box::new(SilentBackend::new())
         }
-    } else {
-        Err(Error::new(ErrorKind::Other, "Unable to choose a file path (ran out of directory paths?)"))
     }
 }
 
-pub async fn handle_background_music(sink_arc: Arc<RwLock<Sink>>) {
-    loop {
-        if sink_arc.write().unwrap().empty() {
-            let mut paths = fs::read_dir(RESOURCE_MUSIC_BACKGROUND_FOLDER).unwrap();
-            let mut rng = Pcg64::from_os_rng();
-            match pick_background_track(&mut paths, &mut rng) {
-                Ok(track) => {
-                    let decoder = Decoder::new_mp3(track).unwrap();
-                    sink_arc.write().unwrap().append(decoder);
-                },
-                Err(e) => {
-                    error!("Failed to pick background track: {}", e)
-                }
-            }
-        } else {
-            info!("Background audio sink is not empty. Waiting 60s..");
-            sleep(Duration::from_secs(60)).await;
+// As with background music, silence is used if there are no clips or nothing to play them on
+pub fn build_sfx_backend(sfx_folder: &str) -> Box<dyn AudioBackend> {
+    let catalogue = SoundCatalogue::load(sfx_folder);
+    // Loading the catalogue has already logged whatever was missing
+    if catalogue.is_empty() {
+        return Box::new(SilentBackend::new());
+    }
+
+    match RodioBackend::try_new(catalogue) {
+        Ok(backend) => Box::new(backend),
+        Err(e) => {
+            warn!("{}, continuing without sound effects.", e);
+            Box::new(SilentBackend::new())
         }
     }
 }
 
-
 impl SoundSinks {
+    pub fn new(bg_sink: Box<dyn AudioBackend>, sfx_sink: Box<dyn AudioBackend>) -> SoundSinks {
+        info!("Using the {} audio backend for background music, and {} for sound effects.", bg_sink.name(), sfx_sink.name());
+        SoundSinks { bg_sink, sfx_sink, music_context: None }
+    }
+
+    // Only passed on when it changes, so this can be called as often as the game likes
+    pub fn set_music_context(&mut self, context: MusicContext) {
+        if self.music_context != Some(context) {
+            self.music_context = Some(context);
+            self.bg_sink.set_music_context(context);
+        }
+    }
 
-    pub fn setup_background_music(&mut self) {
-        log::info!("Starting background music..");
-        let (stream, stream_handle) = OutputStream::try_default().unwrap();
-        self.bg_sink.set_os(Some(Arc::new(stream)));
-        let sink_arc = Arc::new(RwLock::new(Sink::try_new(&stream_handle).unwrap()));
-        self.bg_sink.set_sink(Some(sink_arc.clone()));
-        
-        task::spawn(async move {
-            handle_background_music(sink_arc).await
-        });
+    pub fn get_bg_sink_mut(&mut self) -> &mut dyn AudioBackend {
+        self.bg_sink.as_mut()
     }
-    pub fn get_bg_sink_mut(&mut self) -> &mut AudioSink {
-        &mut self.bg_sink
+
+    pub fn get_sfx_sink_mut(&mut self) -> &mut dyn AudioBackend {
+        self.sfx_sink.as_mut()
+    }
+
+    // Plays the sound for something that's just happened
+    pub fn play_effect(&mut self, effect: SoundEffect) {
+        self.sfx_sink.play_effect(effect);
     }
 }
 
+// For anything lent the sinks only when there are any, i.e not in tests
+pub fn play_effect(sound_sinks: &mut Option<&mut SoundSinks>, effect: SoundEffect) {
+    if let Some(sinks) = sound_sinks {
+        sinks.play_effect(effect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sound::audio_backend::{RecordingBackend, SilentBackend};
+    use crate::sound::music::MusicContext;
+    use crate::sound::sound::{build_bg_backend, build_sfx_backend, SoundSinks};
+    use crate::sound::sound_effects::SoundEffect;
+
+    #[test]
+    fn test_build_bg_backend_without_music() {
+        // GIVEN a music folder that doesn't exist
+        // WHEN we build the background music backend
+        let backend = build_bg_backend("resources/no-such-folder");
+
+        // THEN we expect to fall back to silence rather than failing
+        assert_eq!("silent", backend.name());
+        // AND the same for sound effects
+        assert_eq!("silent", build_sfx_backend("resources/no-such-folder").name());
+    }
+
+    #[test]
+    fn test_recording_backend() {
+        // GIVEN sound sinks that record what they'd play
+        let recording = RecordingBackend::new();
+        let mut sinks = SoundSinks::new(Box::new(recording.clone()), Box::new(SilentBackend::new()));
+
+        // WHEN we configure and play the background music
+        sinks.get_bg_sink_mut().configure(40);
+        sinks.get_bg_sink_mut().play();
+
+        // AND the music context is set, then set again to the same
+        sinks.set_music_context(MusicContext::Level(1));
+        sinks.set_music_context(MusicContext::Level(1));
+        sinks.set_music_context(MusicContext::Combat);
+
+        // THEN we expect each of them to have been recorded in order, without repeating the context
+        assert_eq!(vec!["volume 40", "play", "music Level(1)", "music Combat"], recording.get_recorded());
+    }
+
+    #[test]
+    fn test_play_effect() {
+        // GIVEN sound sinks with sound effects that are recorded
+        let recording = RecordingBackend::new();
+        let mut sinks = SoundSinks::new(Box::new(SilentBackend::new()), Box::new(recording.clone()));
+
+        // WHEN sound effects are played
+        sinks.play_effect(SoundEffect::Footstep);
+        sinks.play_effect(SoundEffect::Pickup);
+
+        // THEN we expect them to have been played in order
+        assert_eq!(vec!["sfx footstep", "sfx pickup"], recording.get_recorded());
+    }
+}
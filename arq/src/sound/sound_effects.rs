@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const RESOURCE_SFX_FOLDER: &str = "resources/sfx";
+
+// Clips can be any of these, i.e footstep.wav
+const CLIP_EXTENSIONS: [&str; 3] = ["wav", "ogg", "mp3"];
+
+// Something that happened in game worth hearing
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SoundEffect {
+    Footstep,
+    DoorOpen,
+    ChestOpen,
+    Hit,
+    Miss,
+    Death,
+    LevelChange,
+    Pickup
+}
+
+impl SoundEffect {
+    pub fn all() -> Vec<SoundEffect> {
+        vec![SoundEffect::Footstep, SoundEffect::DoorOpen, SoundEffect::ChestOpen, SoundEffect::Hit,
+             SoundEffect::Miss, SoundEffect::Death, SoundEffect::LevelChange, SoundEffect::Pickup]
+    }
+
+    // The name of the clip played for this, without an extension
+    pub fn clip_name(&self) -> &'static str {
+        match self {
+            SoundEffect::Footstep => "footstep",
+            SoundEffect::DoorOpen => "door_open",
+            SoundEffect::ChestOpen => "chest_open",
+            SoundEffect::Hit => "hit",
+            SoundEffect::Miss => "miss",
+            SoundEffect::Death => "death",
+            SoundEffect::LevelChange => "level_change",
+            SoundEffect::Pickup => "pickup"
+        }
+    }
+}
+
+/*
+ * The short clips available for each sound effect
+ * Any effect without a clip in the folder is just not heard
+ */
+pub struct SoundCatalogue {
+    clips: HashMap<SoundEffect, PathBuf>
+}
+
+impl SoundCatalogue {
+    pub fn new() -> SoundCatalogue {
+        SoundCatalogue { clips: HashMap::new() }
+    }
+
+    pub fn load(folder: &str) -> SoundCatalogue {
+        let mut catalogue = SoundCatalogue::new();
+        if !Path::new(folder).is_dir() {
+            let clip_names: Vec<&str> = SoundEffect::all().iter().map(|effect| effect.clip_name()).collect();
+            log::warn!("No sound effects folder at: {}, expected clips named: {} (as .wav, .ogg or .mp3)", folder, clip_names.join(", "));
+            return catalogue;
+        }
+
+        for effect in SoundEffect::all() {
+            let clip = CLIP_EXTENSIONS.iter()
+                .map(|extension| Path::new(folder).join(format!("{}.{}", effect.clip_name(), extension)))
+                .find(|path| path.is_file());
+            match clip {
+                Some(path) => {
+                    catalogue.clips.insert(effect, path);
+                },
+                None => log::warn!("No sound effect clip found for: {:?} in: {}", effect, folder)
+            }
+        }
+        catalogue
+    }
+
+    pub fn get_clip(&self, effect: SoundEffect) -> Option<&PathBuf> {
+        self.clips.get(&effect)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clips.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use crate::sound::sound_effects::{SoundCatalogue, SoundEffect};
+
+    #[test]
+    fn test_load_catalogue() {
+        // GIVEN a folder with clips for only some of the sound effects
+        let folder = std::env::temp_dir().join(format!("arq-sfx-{}", Uuid::new_v4()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("footstep.wav"), []).unwrap();
+        fs::write(folder.join("hit.ogg"), []).unwrap();
+
+        // WHEN we load the catalogue from it
+        let catalogue = SoundCatalogue::load(folder.to_str().unwrap());
+
+        // THEN we expect only those effects to have clips
+        assert_eq!(Some(&folder.join("footstep.wav")), catalogue.get_clip(SoundEffect::Footstep));
+        assert_eq!(Some(&folder.join("hit.ogg")), catalogue.get_clip(SoundEffect::Hit));
+        assert!(catalogue.get_clip(SoundEffect::Death).is_none());
+        fs::remove_dir_all(folder).unwrap();
+
+        // AND a missing folder gives an empty catalogue
+        assert!(SoundCatalogue::load("resources/no-such-folder").is_empty());
+    }
+}
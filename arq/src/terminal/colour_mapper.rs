@@ -28,3 +28,34 @@ pub fn map_colour(tile_colour: Colour) -> ratatui::style::Color {
         }
     }
 }
+
+// The basic 16 terminal colours, with the values xterm gives them
+const BASIC_COLOURS: [(ratatui::style::Color, (i32, i32, i32)); 16] = [
+    (ratatui::style::Color::Black, (0, 0, 0)),
+    (ratatui::style::Color::Red, (205, 0, 0)),
+    (ratatui::style::Color::Green, (0, 205, 0)),
+    (ratatui::style::Color::Yellow, (205, 205, 0)),
+    (ratatui::style::Color::Blue, (0, 0, 238)),
+    (ratatui::style::Color::Magenta, (205, 0, 205)),
+    (ratatui::style::Color::Cyan, (0, 205, 205)),
+    (ratatui::style::Color::Gray, (229, 229, 229)),
+    (ratatui::style::Color::DarkGray, (127, 127, 127)),
+    (ratatui::style::Color::LightRed, (255, 0, 0)),
+    (ratatui::style::Color::LightGreen, (0, 255, 0)),
+    (ratatui::style::Color::LightYellow, (255, 255, 0)),
+    (ratatui::style::Color::LightBlue, (92, 92, 255)),
+    (ratatui::style::Color::LightMagenta, (255, 0, 255)),
+    (ratatui::style::Color::LightCyan, (0, 255, 255)),
+    (ratatui::style::Color::White, (255, 255, 255))
+];
+
+// The closest of the basic 16 colours, for terminals without truecolor
+pub fn to_16_colour(colour: ratatui::style::Color) -> ratatui::style::Color {
+    match colour {
+        ratatui::style::Color::Rgb(r, g, b) => {
+            let distance = |(br, bg, bb): (i32, i32, i32)| (r as i32 - br).pow(2) + (g as i32 - bg).pow(2) + (b as i32 - bb).pow(2);
+            BASIC_COLOURS.iter().min_by_key(|(_, rgb)| distance(*rgb)).map(|(basic, _)| *basic).unwrap_or(colour)
+        },
+        _ => colour
+    }
+}
@@ -1,6 +1,10 @@
 use std::io;
+use std::panic;
 
-use termion::raw::{IntoRawMode, RawTerminal};
+use crossterm::cursor::Show;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use ratatui::backend::{Backend, CrosstermBackend, TestBackend};
 use ratatui::Terminal;
 
@@ -16,16 +20,41 @@ impl <B : Backend>  TerminalManager<B> {
     }
 }
 
-pub fn init() -> Result<TerminalManager<CrosstermBackend<RawTerminal<io::Stdout>>>, io::Error> {
-    let stdout = io::stdout().into_raw_mode()?;
+/*
+ * Puts the terminal into raw mode with the mouse captured, ready to draw to
+ * This needs undoing with restore when the game's done, a panic hook makes sure that happens if it crashes
+ */
+pub fn init() -> Result<TerminalManager<CrosstermBackend<io::Stdout>>, io::Error> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnableMouseCapture)?;
+    restore_on_panic();
+
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
-    let manager = TerminalManager::<CrosstermBackend<RawTerminal<io::Stdout>>> { terminal };
+    let manager = TerminalManager::<CrosstermBackend<io::Stdout>> { terminal };
 
     log::info!("Terminal initialised.");
     return Ok(manager);
 }
 
+// Hands the terminal back as it was before init
+pub fn restore() -> Result<(), io::Error> {
+    execute!(io::stdout(), DisableMouseCapture, Show)?;
+    disable_raw_mode()
+}
+
+// Otherwise a panic leaves the user's shell in raw mode, and the panic message unreadable
+fn restore_on_panic() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Err(e) = restore() {
+            eprintln!("Failed to restore the terminal: {}", e);
+        }
+        default_hook(info);
+    }));
+}
+
 pub fn init_test(resolution : Resolution) -> Result<TerminalManager<TestBackend>, io::Error> {
     let backend = TestBackend::new(resolution.width,resolution.height);
     let terminal = Terminal::new(backend)?;
@@ -1,5 +1,6 @@
 mod text_widget_tests;
 mod dropdown_widget_tests;
 mod number_widget_tests;
+mod engine_e2e_tests;
 
 pub mod utils;
@@ -0,0 +1,496 @@
+/*
+ * End-to-end tests, playing whole games through the GameEngine with scripted keys
+ * Everything is drawn to a TestBackend, so these run headless
+ */
+#[cfg(test)]
+mod engine_e2e {
+    use rand_seeder::Seeder;
+    use termion::event::Key;
+    use uuid::Uuid;
+
+    use crate::character::builder::character_builder::{CharacterBuilder, CharacterPattern};
+    use crate::character::characters::Characters;
+    use crate::engine::level::{init_level_manager, Level};
+    use crate::engine::run_history::{load_run_history, record_run, RunOutcome, RunRecord};
+    use crate::global_flags::ENTER_KEY;
+    use crate::input::session::SessionEntry;
+    use crate::input::InputEvent;
+    use crate::map::map_loader::{load_map_file, RESOURCE_MAPS_FOLDER};
+    use crate::map::objects::items::{Item, MaterialType};
+    use crate::map::position::{Area, Position};
+    use crate::test::utils::engine_harness::{assert_buffer_text_eq, EngineHarness};
+    use crate::test::utils::test_resource_loader::read_expected_buffer_file;
+    use crate::ui::ui_wrapper::build_map_view_areas;
+    use crate::view::game_over_view::GameOverChoice;
+
+    const SEED: &str = "e2e";
+    // Positions of interest on the tutorial map
+    const TUTORIAL_ENTRY: Position = Position { x: 3, y: 2 };
+    const TUTORIAL_CHEST: Position = Position { x: 27, y: 2 };
+    const TUTORIAL_EXIT: Position = Position { x: 29, y: 5 };
+
+    // The tutorial map, with the player at the given position and a few items in the chest
+    fn build_tutorial_level(player_position: Position) -> Level {
+        let mut map = load_map_file(&format!("{}/tutorial.txt", RESOURCE_MAPS_FOLDER)).unwrap().map;
+        let floor = map.find_container_mut(TUTORIAL_CHEST).expect("There should be a chest on the tutorial map");
+        let chest = floor.get_contents_mut().first_mut().unwrap();
+        chest.add_item(Item::new(Uuid::new_v4(), String::from("Gold Bar"), MaterialType::GOLD, 'X', 1.0, 100)).unwrap();
+        chest.add_item(Item::new(Uuid::new_v4(), String::from("Silver Bar"), MaterialType::SILVER, 'X', 1.0, 50)).unwrap();
+
+        let player = CharacterBuilder::new(CharacterPattern::new_player().unwrap())
+            .position(player_position)
+            .build(String::from("Player"));
+        Level::new(Some(map), Characters::new(Some(player), Vec::new()))
+    }
+
+    fn repeat(key: Key, times: usize) -> Vec<Key> {
+        vec![key; times]
+    }
+
+    async fn start_seeded_game() -> EngineHarness {
+        let levels = init_level_manager(String::from("unused"), Seeder::from("unused").into_rng());
+        let mut harness = EngineHarness::new(levels);
+        harness.script(Some(SEED), Vec::new());
+        assert!(harness.start_game().await.unwrap().is_none());
+        harness
+    }
+
+    #[tokio::test]
+    async fn test_start_game() {
+        // GIVEN a fixed seed
+        // WHEN we start a game and play no turns
+        let mut harness = start_seeded_game().await;
+
+        // THEN the game is running using that seed
+        assert!(harness.engine.is_game_running());
+        assert_eq!(SEED, harness.engine.levels.get_seed());
+        // AND the player has been drawn onto the overworld
+        let player_position = harness.get_player_position();
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains('@')));
+        // AND the sound has been started at the configured volumes, without needing an audio device
+        // AND the music suits being out on the overworld
+        let settings = &harness.engine.settings;
+        let expected_audio = vec![format!("volume {}", settings.get_bg_music_volume()), String::from("play"), format!("volume {}", settings.get_sfx_volume()),
+            String::from("music Level(0)")];
+        assert_eq!(expected_audio, harness.get_recorded_audio());
+
+        // AND starting again with the same seed gives the very same game
+        let mut replayed = start_seeded_game().await;
+        assert_eq!(player_position, replayed.get_player_position());
+        assert_buffer_text_eq(harness.get_buffer(), replayed.get_buffer());
+    }
+
+    #[tokio::test]
+    async fn test_walk_to_the_chest() {
+        // GIVEN the player at the tutorial entry
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we walk through the corridor to the chest
+        harness.script(None, repeat(Key::Right, 23));
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player stops beside it
+        assert_eq!(Position::new(26, 2), harness.get_player_position());
+        // AND every step taken was heard, including through the doors along the way
+        let recorded = harness.get_recorded_audio();
+        let count = |effect: &str| recorded.iter().filter(|sound| *sound == effect).count();
+        assert_eq!((21, 2), (count("sfx footstep"), count("sfx door_open")));
+        // AND the map view looks as expected
+        let expected = read_expected_buffer_file(String::from("resources/test/e2e_tutorial_chest.txt"), Area::from_rect(harness.get_buffer().area));
+        assert_buffer_text_eq(&expected, harness.get_buffer());
+    }
+
+    #[tokio::test]
+    async fn test_click_to_travel_to_the_chest() {
+        // GIVEN the player at the tutorial entry, with the map drawn
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+        let level = harness.engine.levels.get_level_mut();
+        harness.engine.ui_wrapper.draw_map_view(level, harness.engine.input_handler.get_key_input()).unwrap();
+
+        // WHEN we click beside the chest
+        let level = harness.engine.levels.get_level_mut();
+        let map_view_areas = build_map_view_areas(&harness.engine.ui_wrapper.ui, level).unwrap();
+        let target = Position::new(26, 2);
+        let local = map_view_areas.global_to_local(target).unwrap();
+        let click = map_view_areas.map_view_area.get_position(local.x, local.y);
+        harness.script_events(None, vec![InputEvent::Click(click)]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player travels all the way there, through the doors along the way, without stopping on seeing the chest
+        assert_eq!(target, harness.get_player_position());
+        assert!(harness.get_recorded_audio().contains(&String::from("sfx door_open")));
+    }
+
+    #[tokio::test]
+    async fn test_auto_explore_until_finding_the_chest() {
+        // GIVEN the player at the tutorial entry
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we auto-explore
+        harness.script(None, vec![Key::Char('e')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player stops as soon as they've seen the chest
+        let level = harness.engine.levels.get_level_mut();
+        assert_eq!(Some(TUTORIAL_CHEST), level.get_last_seen_item());
+        let chest_room = level.map.as_ref().unwrap().rooms.iter().find(|r| r.get_area().contains_position(TUTORIAL_CHEST)).unwrap();
+        assert!(chest_room.get_area().contains_position(harness.get_player_position()));
+        // AND we're told why
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("You stop, you've spotted something.")));
+    }
+
+    #[tokio::test]
+    async fn test_travel_to_last_seen_item() {
+        // GIVEN the player has found the chest by exploring
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we explore and then travel to the last item seen
+        harness.script(None, vec![Key::Char('e'), Key::Char('l')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player is standing at the chest
+        assert_eq!(TUTORIAL_CHEST, harness.get_player_position());
+    }
+
+    #[tokio::test]
+    async fn test_travel_to_item_seen_while_walking() {
+        // GIVEN the player in the corridor just outside the chest room
+        let mut harness = EngineHarness::for_level(build_tutorial_level(Position::new(22, 2)));
+
+        // WHEN we walk into the doorway, seeing the chest, and then travel to the last item seen
+        harness.script(None, vec![Key::Right, Key::Char('l')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player is standing at the chest
+        assert_eq!(TUTORIAL_CHEST, harness.get_player_position());
+        // AND the way they walked by hand has been explored
+        assert!(harness.engine.levels.get_level().is_explored(Position::new(23, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_travel_to_exit() {
+        // GIVEN the player beside the tutorial chest
+        let mut harness = EngineHarness::for_level(build_tutorial_level(Position::new(26, 2)));
+
+        // WHEN we travel to the exit and decline to leave
+        harness.script(None, vec![Key::Char('>'), Key::Char('n')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player is standing on the exit, still on the first level
+        assert_eq!(TUTORIAL_EXIT, harness.get_player_position());
+        assert_eq!(0, harness.engine.levels.get_current_level());
+    }
+
+    #[tokio::test]
+    async fn test_key_press_interrupts_travel() {
+        // GIVEN the player at the tutorial entry
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we travel to the exit, but press a key a couple of steps in
+        harness.script_session(None, vec![SessionEntry::Key(Key::Char('>')), SessionEntry::Interrupt(2)]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player stops after those couple of steps
+        assert_eq!(2, TUTORIAL_ENTRY.chebyshev_distance(harness.get_player_position()));
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("You stop.")));
+    }
+
+    #[tokio::test]
+    async fn test_run_to_the_doors() {
+        // GIVEN the player at the tutorial entry
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we run right
+        harness.script(None, vec![Key::Char('D')]);
+        assert!(harness.play().await.unwrap().is_none());
+        // THEN the player stops in the doorway
+        assert_eq!(Position::new(8, 2), harness.get_player_position());
+
+        // WHEN we run right again
+        harness.script(None, vec![Key::ShiftRight]);
+        assert!(harness.play().await.unwrap().is_none());
+        // THEN the player runs the length of the corridor, stopping at the door to the chest room
+        assert_eq!(Position::new(23, 2), harness.get_player_position());
+    }
+
+    // The tutorial level, with a goblin of the given health standing just right of the player
+    fn build_goblin_level(goblin_health: u16) -> Level {
+        let mut level = build_tutorial_level(TUTORIAL_ENTRY);
+        let mut goblin = CharacterBuilder::new(CharacterPattern::goblin().unwrap())
+            .position(Position::new(4, 2))
+            .build(String::from("Ruggo"));
+        goblin.set_health(goblin_health);
+        level.characters.get_npcs_mut().push(goblin);
+        level
+    }
+
+    #[tokio::test]
+    async fn test_no_exploring_with_an_npc_in_view() {
+        // GIVEN a goblin in view of the player
+        let mut harness = EngineHarness::for_level(build_goblin_level(80));
+
+        // WHEN we try to auto-explore
+        harness.script(None, vec![Key::Char('e')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player stays put rather than walking into a fight
+        assert_eq!(TUTORIAL_ENTRY, harness.get_player_position());
+        assert_eq!(80, harness.engine.levels.get_level().characters.get_npcs()[0].get_health());
+        // AND we're told why
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("Not with someone nearby!")));
+    }
+
+    #[tokio::test]
+    async fn test_only_actions_take_turns() {
+        // GIVEN the player at the tutorial entry
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we look through the inventory and press a key that does nothing
+        harness.script(None, vec![Key::Char('i'), Key::Esc, Key::Char('#')]);
+        assert!(harness.play().await.unwrap().is_none());
+        // THEN no time has passed
+        assert_eq!(0, harness.engine.levels.get_turn());
+
+        // WHEN we take a step
+        harness.script(None, vec![Key::Right]);
+        assert!(harness.play().await.unwrap().is_none());
+        // THEN that takes a turn
+        assert_eq!(1, harness.engine.levels.get_turn());
+    }
+
+    #[tokio::test]
+    async fn test_fight_until_the_npc_dies() {
+        // GIVEN a badly hurt goblin beside the player
+        let mut harness = EngineHarness::for_level(build_goblin_level(1));
+
+        // WHEN we walk into it and attack
+        harness.script(None, vec![Key::Right, ENTER_KEY]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the goblin is dead, leaving it's corpse where it fell
+        let level = harness.engine.levels.get_level_mut();
+        assert!(level.characters.get_npcs().is_empty());
+        let floor = level.get_map_mut().unwrap().find_container_mut(Position::new(4, 2)).unwrap();
+        assert!(floor.get_contents().iter().any(|c| c.get_self_item().get_name() == "A Goblin's dead body"));
+        // AND the player survived and is told about it
+        assert!(!harness.get_player().is_dead());
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("You killed Ruggo!")));
+        assert!(harness.get_recorded_audio().contains(&String::from("sfx death")));
+    }
+
+    #[tokio::test]
+    async fn test_fight_until_the_player_dies() {
+        // GIVEN a healthy goblin beside a badly hurt player
+        let mut harness = EngineHarness::for_level(build_goblin_level(80));
+        harness.get_player().set_health(1);
+
+        // WHEN we walk into it, attack and choose to restart after dying
+        harness.script(None, vec![Key::Right, ENTER_KEY, ENTER_KEY]);
+        let result = harness.play().await.unwrap();
+
+        // THEN the game is over
+        assert!(matches!(result, Some(GameOverChoice::RESTART)));
+        // AND the death is recorded in the run history
+        let history = load_run_history(harness.get_run_history_path());
+        assert_eq!(1, history.get_runs().len());
+        assert_eq!(RunOutcome::Died(String::from("Ruggo")), history.get_runs()[0].outcome);
+    }
+
+    #[tokio::test]
+    async fn test_open_chest_and_take_items() {
+        // GIVEN the player beside the tutorial chest
+        let mut harness = EngineHarness::for_level(build_tutorial_level(Position::new(26, 2)));
+
+        // WHEN we open the chest, select the first item and take it
+        harness.script(None, vec![Key::Char('o'), Key::Right, ENTER_KEY, Key::Char('t'), Key::Esc]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the item is moved from the chest into the player's inventory
+        let inventory = harness.get_player().get_inventory_mut();
+        assert!(inventory.get_contents().iter().any(|c| c.get_self_item().get_name() == "Gold Bar"));
+
+        let map = harness.engine.levels.get_level_mut().get_map_mut().unwrap();
+        let chest = &map.find_container_mut(TUTORIAL_CHEST).unwrap().get_contents()[0];
+        let chest_items: Vec<String> = chest.get_contents().iter().map(|c| c.get_self_item().get_name()).collect();
+        assert_eq!(vec![String::from("Silver Bar")], chest_items);
+        // AND the chest was heard opening
+        assert!(harness.get_recorded_audio().contains(&String::from("sfx chest_open")));
+    }
+
+    #[tokio::test]
+    async fn test_pick_up_nothing() {
+        // GIVEN the player at the tutorial entry, with nothing on the floor
+        let mut harness = EngineHarness::for_level(build_tutorial_level(TUTORIAL_ENTRY));
+
+        // WHEN we try to pick up
+        harness.script(None, vec![Key::Char('g')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN we're told there's nothing there, and the message stays on screen
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("There's nothing here to pick up.")));
+    }
+
+    #[tokio::test]
+    async fn test_pick_up_from_the_floor() {
+        // GIVEN 2 items on the floor beside the player
+        let mut level = build_tutorial_level(Position::new(4, 2));
+        let floor = level.get_map_mut().unwrap().find_container_mut(Position::new(5, 2)).unwrap();
+        floor.add_item(Item::new(Uuid::new_v4(), String::from("Gold Bar"), MaterialType::GOLD, 'X', 1.0, 100)).unwrap();
+        floor.add_item(Item::new(Uuid::new_v4(), String::from("Silver Bar"), MaterialType::SILVER, 'X', 1.0, 50)).unwrap();
+        let mut harness = EngineHarness::for_level(level);
+
+        // WHEN we step onto them, pick up and choose only the second item
+        harness.script(None, vec![Key::Right, Key::Char('g'), Key::Down, Key::Char(' '), ENTER_KEY]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN only that item is moved into the player's inventory
+        let inventory = harness.get_player().get_inventory_mut();
+        assert!(inventory.get_contents().iter().any(|c| c.get_self_item().get_name() == "Silver Bar"));
+        assert!(!inventory.get_contents().iter().any(|c| c.get_self_item().get_name() == "Gold Bar"));
+        // AND we're told about it
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("You pick up the Silver Bar.")));
+    }
+
+    #[tokio::test]
+    async fn test_descend() {
+        // GIVEN the player beside the tutorial chest
+        let mut harness = EngineHarness::for_level(build_tutorial_level(Position::new(26, 2)));
+
+        // WHEN we walk around the chest to the exit, agree to leave and confirm the new map
+        let mut keys = repeat(Key::Down, 3);
+        keys.append(&mut repeat(Key::Right, 3));
+        keys.append(&mut vec![Key::Char('y'), Key::Char(' '), Key::Char(' ')]);
+        harness.script(None, keys);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN we've descended to a newly generated level
+        assert_eq!(1, harness.engine.levels.get_current_level());
+        // AND the player is standing on it's entry
+        let player_position = harness.get_player_position();
+        let map = harness.engine.levels.get_level_mut().get_map_mut().unwrap();
+        assert!(map.rooms.iter().any(|r| r.get_entry() == Some(player_position)));
+        assert_ne!(TUTORIAL_EXIT, player_position);
+    }
+
+    #[tokio::test]
+    async fn test_escape_from_a_dungeon() {
+        // GIVEN the player beside the entry of the first level of a dungeon, in the overworld
+        let mut harness = EngineHarness::for_dungeon_level(build_tutorial_level(Position::new(4, 2)));
+
+        // WHEN we step onto the entry, agree to leave, agree to end the run and choose to restart
+        harness.script(None, vec![Key::Left, Key::Char('y'), Key::Char(' '), Key::Char('y'), ENTER_KEY]);
+        let result = harness.play().await.unwrap();
+
+        // THEN the game is over
+        assert!(matches!(result, Some(GameOverChoice::RESTART)));
+        // AND the escape is recorded in the run history
+        let history = load_run_history(harness.get_run_history_path());
+        assert_eq!(1, history.get_runs().len());
+        assert_eq!(RunOutcome::Escaped, history.get_runs()[0].outcome);
+    }
+
+    #[tokio::test]
+    async fn test_return_to_the_overworld() {
+        // GIVEN the player beside the entry of the first level of a dungeon, in the overworld
+        let mut harness = EngineHarness::for_dungeon_level(build_tutorial_level(Position::new(4, 2)));
+
+        // WHEN we step onto the entry, agree to leave, but carry on with the run
+        harness.script(None, vec![Key::Left, Key::Char('y'), Key::Char(' '), Key::Char('n')]);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the player is back in the overworld, outside the dungeon
+        let player_position = harness.get_player_position();
+        let world = harness.engine.get_world().unwrap();
+        assert!(!world.is_in_dungeon());
+        assert_eq!(Some(0), world.find_dungeon_at(player_position));
+        // AND nothing's been recorded, as the run goes on
+        assert!(load_run_history(harness.get_run_history_path()).get_runs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quit_from_the_menu() {
+        // GIVEN a game in progress
+        let mut harness = start_seeded_game().await;
+
+        // WHEN we open the menu and quit
+        let mut keys = vec![Key::Esc];
+        keys.append(&mut repeat(Key::Down, 4));
+        keys.push(ENTER_KEY);
+        harness.script(None, keys);
+        let result = harness.play().await.unwrap();
+
+        // THEN the game is exited
+        assert!(matches!(result, Some(GameOverChoice::EXIT)));
+        // AND the run is recorded as quit, having never left the overworld
+        let history = load_run_history(harness.get_run_history_path());
+        assert_eq!(1, history.get_runs().len());
+        assert_eq!(RunOutcome::Quit, history.get_runs()[0].outcome);
+        assert_eq!(0, history.get_runs()[0].deepest_level);
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_shows_recorded_runs() {
+        // GIVEN a game in progress, with a run already recorded in the engine's run history
+        let mut harness = start_seeded_game().await;
+        let run = RunRecord {
+            character_name: String::from("Hightower"),
+            race: String::from("Human"),
+            class: String::from("Warrior"),
+            seed: String::from(SEED),
+            deepest_level: 2,
+            loot_value: 50,
+            turns: 100,
+            outcome: RunOutcome::Escaped,
+            inventory: Vec::new()
+        };
+        record_run(harness.get_run_history_path(), run).unwrap();
+
+        // WHEN we open the menu and choose the leaderboard
+        let mut keys = vec![Key::Esc];
+        keys.append(&mut repeat(Key::Down, 3));
+        keys.push(ENTER_KEY);
+        harness.script(None, keys);
+        assert!(harness.play().await.unwrap().is_none());
+
+        // THEN the recorded run is listed
+        assert!(harness.get_buffer_lines().iter().any(|l| l.contains("Hightower")));
+    }
+
+    #[tokio::test]
+    async fn test_quit_with_a_corrupt_run_history() {
+        // GIVEN a game in progress, with a run history that can't be read
+        let mut harness = start_seeded_game().await;
+        std::fs::write(harness.get_run_history_path(), "{ not json").unwrap();
+
+        // WHEN we open the menu and quit
+        let mut keys = vec![Key::Esc];
+        keys.append(&mut repeat(Key::Down, 4));
+        keys.push(ENTER_KEY);
+        harness.script(None, keys);
+        let result = harness.play().await.unwrap();
+
+        // THEN the game is still exited
+        assert!(matches!(result, Some(GameOverChoice::EXIT)));
+        // AND the run is recorded in place of the corrupt history
+        let history = load_run_history(harness.get_run_history_path());
+        assert_eq!(1, history.get_runs().len());
+        assert_eq!(RunOutcome::Quit, history.get_runs()[0].outcome);
+    }
+
+    #[tokio::test]
+    async fn test_escape() {
+        // GIVEN the player beside the tutorial entry, on the first level
+        let mut harness = EngineHarness::for_level(build_tutorial_level(Position::new(4, 2)));
+
+        // WHEN we step onto the entry, agree to leave and choose to restart
+        harness.script(None, vec![Key::Left, Key::Char('y'), Key::Char(' '), ENTER_KEY]);
+        let result = harness.play().await.unwrap();
+
+        // THEN the game is over
+        assert!(matches!(result, Some(GameOverChoice::RESTART)));
+        // AND the escape is recorded in the run history
+        let history = load_run_history(harness.get_run_history_path());
+        assert_eq!(1, history.get_runs().len());
+        assert_eq!(RunOutcome::Escaped, history.get_runs()[0].outcome);
+    }
+}
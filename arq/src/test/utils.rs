@@ -1,2 +1,4 @@
 pub mod test_utils;
 pub mod test_resource_loader;
+#[cfg(test)]
+pub mod engine_harness;
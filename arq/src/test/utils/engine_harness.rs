@@ -0,0 +1,155 @@
+use rand_seeder::Seeder;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use termion::event::Key;
+use uuid::Uuid;
+
+use crate::character::characters::Characters;
+use crate::character::Character;
+use crate::engine::game_engine::{build_test_game_engine, GameEngine};
+use crate::engine::level::{init_level_manager, Level, Levels};
+use crate::engine::world::build_world;
+use crate::error::errors::ErrorWrapper;
+use crate::input::session::{is_replay_finished, ReplayKeyInputResolver, SessionEntry};
+use crate::input::InputEvent;
+use crate::map::overworld_generator::{build_overworld_generator, OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y};
+use crate::map::position::{build_rectangular_area, Position};
+use crate::sound::audio_backend::RecordingBackend;
+use crate::sound::sound::SoundSinks;
+use crate::terminal::terminal_manager;
+use crate::test::utils::test_utils::build_test_levels_for_level;
+use crate::ui::resolution::MIN_RESOLUTION;
+use crate::view::game_over_view::GameOverChoice;
+
+/*
+ * Drives a whole GameEngine headlessly for end-to-end tests
+ * Keys are fed through the same input source as replayed sessions, and everything is drawn to a TestBackend
+ */
+pub struct EngineHarness {
+    pub engine: GameEngine<TestBackend>,
+    run_history_path: String,
+    recording: RecordingBackend
+}
+
+impl EngineHarness {
+    pub fn new(levels: Levels) -> EngineHarness {
+        let terminal_manager = terminal_manager::init_test(MIN_RESOLUTION).unwrap();
+        let mut engine = build_test_game_engine(levels, terminal_manager).unwrap();
+        // There's no audio device here, so sound is recorded instead of played
+        let recording = RecordingBackend::new();
+        engine.start_sound(SoundSinks::new(Box::new(recording.clone()), Box::new(recording.clone())));
+        engine.init().unwrap();
+
+        // Keep any runs finished by a test out of the real run history
+        let run_history_path = std::env::temp_dir().join(format!("arq-e2e-history-{}.json", Uuid::new_v4()))
+            .to_str().unwrap().to_string();
+        engine.set_run_history_path(run_history_path.clone());
+        EngineHarness { engine, run_history_path, recording }
+    }
+
+    // Plays on the given level, as the only level of the dungeon
+    pub fn for_level(level: Level) -> EngineHarness {
+        EngineHarness::new(build_test_levels_for_level(level))
+    }
+
+    // Plays on the given level, as the first level of a dungeon in a generated overworld
+    pub fn for_dungeon_level(level: Level) -> EngineHarness {
+        let mut levels = init_level_manager(String::from("test"), Seeder::from("test").into_rng());
+        let map_area = build_rectangular_area(Position::zero(), OVERWORLD_SIZE_X, OVERWORLD_SIZE_Y);
+        let overworld = build_overworld_generator(&mut levels.rng, map_area, 1).generate();
+        levels.add_level_directly(Level::new(Some(overworld.map.clone()), Characters::default()));
+
+        let mut world = build_world(String::from("test"), &overworld);
+        world.enter_dungeon(0, &mut levels);
+        levels.add_level_directly(level);
+        let mut harness = EngineHarness::new(levels);
+        harness.engine.set_world(world);
+        harness
+    }
+
+    pub fn get_run_history_path(&self) -> &str {
+        &self.run_history_path
+    }
+
+    // Everything that would have been heard so far
+    pub fn get_recorded_audio(&self) -> Vec<String> {
+        self.recording.get_recorded()
+    }
+
+    // Sets the keys pressed from here on, plus the seed handed to the next game started
+    pub fn script(&mut self, seed: Option<&str>, keys: Vec<Key>) {
+        self.script_session(seed, keys.into_iter().map(SessionEntry::Key).collect());
+    }
+
+    // As script, but for any kind of input i.e clicks
+    pub fn script_events(&mut self, seed: Option<&str>, events: Vec<InputEvent>) {
+        self.script_session(seed, events.into_iter().filter_map(SessionEntry::from_event).collect());
+    }
+
+    // As script, but with entries as they'd be recorded in a session i.e including interruptions
+    pub fn script_session(&mut self, seed: Option<&str>, session: Vec<SessionEntry>) {
+        let mut entries: Vec<SessionEntry> = seed.map(|s| SessionEntry::Seed(s.to_string())).into_iter().collect();
+        entries.extend(session);
+        self.engine.input_handler.set_key_input(Box::new(ReplayKeyInputResolver::new(entries, None)));
+    }
+
+    // Starts a new game, playing through the scripted keys until they run out or the game ends
+    pub async fn start_game(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        EngineHarness::until_replay_finished(self.engine.start_game().await)
+    }
+
+    // Plays turns on the current levels until the scripted keys run out or the game ends
+    pub async fn play(&mut self) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        loop {
+            match self.engine.play_turn().await {
+                Ok(None) => {},
+                result => return EngineHarness::until_replay_finished(result)
+            }
+        }
+    }
+
+    fn until_replay_finished(result: Result<Option<GameOverChoice>, ErrorWrapper>) -> Result<Option<GameOverChoice>, ErrorWrapper> {
+        match result {
+            Err(e) if e.io_error.as_ref().is_some_and(is_replay_finished) => Ok(None),
+            other => other
+        }
+    }
+
+    pub fn get_player(&mut self) -> &mut Character {
+        self.engine.levels.get_level_mut().get_player_mut().expect("There should be a player on the current level")
+    }
+
+    pub fn get_player_position(&mut self) -> Position {
+        self.get_player().get_global_position()
+    }
+
+    pub fn get_buffer(&self) -> &Buffer {
+        self.engine.ui_wrapper.terminal_manager.terminal.backend().buffer()
+    }
+
+    // The text of each row of the last frame drawn
+    pub fn get_buffer_lines(&self) -> Vec<String> {
+        buffer_to_lines(self.get_buffer())
+    }
+}
+
+impl Drop for EngineHarness {
+    // Any runs recorded during the test don't outlive it
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.run_history_path);
+    }
+}
+
+pub fn buffer_to_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.y..area.y + area.height)
+        .map(|y| (area.x..area.x + area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+        .collect()
+}
+
+// Compares only the text of two buffers, ignoring styling
+pub fn assert_buffer_text_eq(expected: &Buffer, actual: &Buffer) {
+    let expected_lines = buffer_to_lines(expected);
+    let actual_lines = buffer_to_lines(actual);
+    assert_eq!(expected_lines, actual_lines, "\nExpected:\n{}\nActual:\n{}\n", expected_lines.join("\n"), actual_lines.join("\n"));
+}
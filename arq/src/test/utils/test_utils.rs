@@ -9,7 +9,13 @@ use crate::map::objects::container::{Container, ContainerType};
 use crate::map::position::{build_square_area, Position};
 use crate::map::tile::{Colour, TileType};
 use crate::map::{Map, Tiles};
+#[cfg(test)]
+use crate::map::map_generator::build_empty_chest;
+#[cfg(test)]
+use crate::map::objects::identification::{PotionType, ScrollType};
 use crate::map::objects::items::Item;
+#[cfg(test)]
+use crate::map::objects::items::{ItemForm, MaterialType};
 
 pub fn build_test_level(area_container: Option<(Position, Container)>, player: Option<Character>) -> Level {
     let tile_library = crate::map::tile::build_library();
@@ -45,14 +51,11 @@ pub fn build_test_level(area_container: Option<(Position, Container)>, player: O
             .build(String::from("Test Player"))
     };
 
-    return  Level { map: Some(map) , characters: Characters::new( Some(player_choice), Vec::new())  };
+    return  Level::new(Some(map), Characters::new(Some(player_choice), Vec::new()));
 }
 
 pub fn build_test_levels(map: Map, player: Character) -> Levels {
-    build_test_levels_for_level(Level {
-        map: Some(map.clone()),
-        characters: Characters::new(Some(player), Vec::new())
-    })
+    build_test_levels_for_level(Level::new(Some(map.clone()), Characters::new(Some(player), Vec::new())))
 }
 
 pub fn build_test_levels_for_level(level: Level) -> Levels {
@@ -86,3 +89,34 @@ pub fn build_test_container() -> Container {
     assert_eq!(4, contents.len());
     container
 }
+
+/*
+ * A chest with a fixed set of contents, including nested containers and enough items to need paging
+ */
+#[cfg(test)]
+pub fn build_dev_chest() -> Container {
+    let mut container = build_empty_chest();
+
+    // Items totalling 7 weight
+    let bronze_bar = Item::new(Uuid::new_v4(), "Bronze Bar".to_owned(), MaterialType::BRONZE, 'X', 1.0, 50);
+    let mut bag = Container::new(Uuid::new_v4(), "Bag".to_owned(), '$', 5.0, 50, ContainerType::OBJECT, 50);
+    let mut carton = Container::new(Uuid::new_v4(), "Carton".to_owned(), '$', 1.0, 50, ContainerType::OBJECT, 5);
+    let tin_bar = Item::new(Uuid::new_v4(), "Tin Bar".to_owned(), MaterialType::TIN, 'X', 1.0, 50);
+    carton.add_item(tin_bar).expect("Tin bar should have been added to the carton!");
+    bag.add(carton).expect("The carton should have been added to the bag!");
+    bag.add_item(bronze_bar).expect("Bronze bar should have been added to the bag!");
+    container.add(bag).expect("The Bag should have been added to the Chest");
+
+    // 60 extra weight
+    for i in 1..=60 {
+        let test_item = Item::new(Uuid::new_v4(), format!("Test Item {}", i), MaterialType::UNKNOWN, '$', 1.0, 100);
+        container.add_item(test_item).expect(format!("Test Item {} should have been added to the container", i).as_str());
+    }
+
+    // Magic items to identify, 1 extra weight
+    let potion = Item::magic(Uuid::new_v4(), ItemForm::POTION(PotionType::Healing), '!', 0.75, 30);
+    let scroll = Item::magic(Uuid::new_v4(), ItemForm::SCROLL(ScrollType::Identify), '?', 0.25, 40);
+    container.add_item(potion).expect("The potion should have been added to the Chest");
+    container.add_item(scroll).expect("The scroll should have been added to the Chest");
+    return container;
+}
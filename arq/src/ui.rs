@@ -12,5 +12,7 @@ pub mod ui_areas;
 pub mod ui_areas_builder;
 pub mod ui_util;
 pub mod resolution;
+pub mod theme;
+pub mod glyphs;
 
 pub mod bindings;
\ No newline at end of file
@@ -1,9 +1,9 @@
 use crate::ui::bindings::input_bindings::KeyBindings;
 use std::collections::HashMap;
 use termion::event::Key;
-use crate::map::position::Side;
-use crate::map::position::Side::{BOTTOM, LEFT, RIGHT, TOP};
-use crate::ui::bindings::action_bindings::Action::{DevBeginCombat, Escape, LookAround, MovePlayer, OpenNearby, ShowInventory};
+use crate::map::position::Direction;
+use crate::map::position::Direction::{DOWN, DOWN_LEFT, DOWN_RIGHT, LEFT, RIGHT, UP, UP_LEFT, UP_RIGHT};
+use crate::ui::bindings::action_bindings::Action::{AutoExplore, DevBeginCombat, DevExportLevel, Escape, LookAround, MovePlayer, OpenNearby, RunPlayer, PickUp, ShowInventory, TravelToEntry, TravelToExit, TravelToItem};
 use crate::ui::bindings::look_bindings::{LookInput, LookKeyBindings};
 /*
   An Action that the Player can take
@@ -12,9 +12,16 @@ use crate::ui::bindings::look_bindings::{LookInput, LookKeyBindings};
 pub enum Action {
     ShowInventory,
     DevBeginCombat, // For development of combat view
+    DevExportLevel, // Dumps the current level to ASCII / JSON files for debugging
     LookAround,
     OpenNearby,
-    MovePlayer(Side),
+    PickUp, // Takes items from the floor under the player
+    AutoExplore, // Walks towards whatever's nearest that hasn't been explored yet
+    TravelToExit,
+    TravelToEntry,
+    TravelToItem, // Heads back to the last item the player saw
+    MovePlayer(Direction),
+    RunPlayer(Direction), // Keeps moving in one direction until there's something worth stopping for
     Escape // This can open the pause menu, close a container view, etc
 }
 
@@ -30,20 +37,62 @@ pub fn build_default_action_keybindings() -> ActionKeyBindings {
     bindings.insert(Key::Char('i'), ShowInventory);
     bindings.insert(Key::Char('k'), LookAround);
     bindings.insert(Key::Char('o'), OpenNearby);
+    bindings.insert(Key::Char('g'), PickUp);
+    bindings.insert(Key::Char(','), PickUp);
+    bindings.insert(Key::Char('e'), AutoExplore);
+    bindings.insert(Key::Char('>'), TravelToExit);
+    bindings.insert(Key::Char('<'), TravelToEntry);
+    bindings.insert(Key::Char('l'), TravelToItem);
+    // Behind a modifier, so it's not pressed by accident during play
+    bindings.insert(Key::Ctrl('x'), DevExportLevel);
     
     
     // Player movement bindings (arrows)
-    bindings.insert(Key::Up, MovePlayer(TOP));
-    bindings.insert(Key::Down, MovePlayer(BOTTOM));
+    bindings.insert(Key::Up, MovePlayer(UP));
+    bindings.insert(Key::Down, MovePlayer(DOWN));
     bindings.insert(Key::Left, MovePlayer(LEFT));
     bindings.insert(Key::Right, MovePlayer(RIGHT));
     
     // Player movement bindings (WASD)
-    bindings.insert(Key::Char('w'), MovePlayer(TOP));
-    bindings.insert(Key::Char('s'), MovePlayer(BOTTOM));
+    bindings.insert(Key::Char('w'), MovePlayer(UP));
+    bindings.insert(Key::Char('s'), MovePlayer(DOWN));
     bindings.insert(Key::Char('a'), MovePlayer(LEFT));
     bindings.insert(Key::Char('d'), MovePlayer(RIGHT));
 
+    // Player diagonal movement bindings (yubn)
+    bindings.insert(Key::Char('y'), MovePlayer(UP_LEFT));
+    bindings.insert(Key::Char('u'), MovePlayer(UP_RIGHT));
+    bindings.insert(Key::Char('b'), MovePlayer(DOWN_LEFT));
+    bindings.insert(Key::Char('n'), MovePlayer(DOWN_RIGHT));
+
+    // Player running bindings (shift + arrows, WASD or YUBN)
+    bindings.insert(Key::ShiftUp, RunPlayer(UP));
+    bindings.insert(Key::ShiftDown, RunPlayer(DOWN));
+    bindings.insert(Key::ShiftLeft, RunPlayer(LEFT));
+    bindings.insert(Key::ShiftRight, RunPlayer(RIGHT));
+    bindings.insert(Key::Char('W'), RunPlayer(UP));
+    bindings.insert(Key::Char('S'), RunPlayer(DOWN));
+    bindings.insert(Key::Char('A'), RunPlayer(LEFT));
+    bindings.insert(Key::Char('D'), RunPlayer(RIGHT));
+    bindings.insert(Key::Char('Y'), RunPlayer(UP_LEFT));
+    bindings.insert(Key::Char('U'), RunPlayer(UP_RIGHT));
+    bindings.insert(Key::Char('B'), RunPlayer(DOWN_LEFT));
+    bindings.insert(Key::Char('N'), RunPlayer(DOWN_RIGHT));
+
+    // Player movement bindings (numpad, with and without num lock)
+    bindings.insert(Key::Char('8'), MovePlayer(UP));
+    bindings.insert(Key::Char('2'), MovePlayer(DOWN));
+    bindings.insert(Key::Char('4'), MovePlayer(LEFT));
+    bindings.insert(Key::Char('6'), MovePlayer(RIGHT));
+    bindings.insert(Key::Char('7'), MovePlayer(UP_LEFT));
+    bindings.insert(Key::Char('9'), MovePlayer(UP_RIGHT));
+    bindings.insert(Key::Char('1'), MovePlayer(DOWN_LEFT));
+    bindings.insert(Key::Char('3'), MovePlayer(DOWN_RIGHT));
+    bindings.insert(Key::Home, MovePlayer(UP_LEFT));
+    bindings.insert(Key::PageUp, MovePlayer(UP_RIGHT));
+    bindings.insert(Key::End, MovePlayer(DOWN_LEFT));
+    bindings.insert(Key::PageDown, MovePlayer(DOWN_RIGHT));
+
     ActionKeyBindings {
         bindings
     }
@@ -0,0 +1,151 @@
+use crate::map::position::{Direction, Position};
+use crate::map::tile::TileType;
+use crate::map::Tiles;
+use crate::widget::stateful::dropdown_widget::DropdownOption;
+
+// Box-drawing walls, in the order: horizontal, vertical, the 4 corners, the 4 T-junctions and then a crossing
+type WallGlyphs = [char; 11];
+const LIGHT_WALLS: WallGlyphs = ['─', '│', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼'];
+const DOUBLE_WALLS: WallGlyphs = ['═', '║', '╔', '╗', '╚', '╝', '╠', '╣', '╦', '╩', '╬'];
+
+/*
+ * The characters the map is drawn with
+ * Only what's drawn changes, tiles keep their TileType and the symbol given to them by build_library
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GlyphSet {
+    // Plain ASCII for limited terminals
+    Ascii,
+    Unicode,
+    // The characters of the original IBM PC, as used by classic roguelikes
+    Cp437
+}
+
+pub fn get_glyph_set_dropdown_options() -> Vec<DropdownOption<GlyphSet>> {
+    vec![
+        DropdownOption { display_name: "ASCII", value: Some(GlyphSet::Ascii) },
+        DropdownOption { display_name: "Unicode", value: Some(GlyphSet::Unicode) },
+        DropdownOption { display_name: "CP437", value: Some(GlyphSet::Cp437) }
+    ]
+}
+
+// Which sides a wall meets another part of the wall on, so it can be drawn joined up to them
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WallJoins {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool
+}
+
+// Doors and windows sit within walls, so walls join on to them too
+fn is_part_of_wall(tile_type: &TileType) -> bool {
+    matches!(tile_type, TileType::Wall | TileType::Door | TileType::Window)
+}
+
+impl WallJoins {
+    pub fn find(tiles: &Tiles, position: Position) -> WallJoins {
+        let joins = |direction: Direction| position.get_adjacent_position(direction)
+            .and_then(|p| tiles.get_tile(p))
+            .is_some_and(|t| is_part_of_wall(&t.tile_type));
+        WallJoins { up: joins(Direction::UP), down: joins(Direction::DOWN), left: joins(Direction::LEFT), right: joins(Direction::RIGHT) }
+    }
+
+    fn pick(&self, glyphs: &WallGlyphs) -> char {
+        match (self.up, self.down, self.left, self.right) {
+            (true, true, true, true) => glyphs[10],
+            (false, true, true, true) => glyphs[8],
+            (true, false, true, true) => glyphs[9],
+            (true, true, false, true) => glyphs[6],
+            (true, true, true, false) => glyphs[7],
+            (false, true, false, true) => glyphs[2],
+            (false, true, true, false) => glyphs[3],
+            (true, false, false, true) => glyphs[4],
+            (true, false, true, false) => glyphs[5],
+            (true, _, false, false) | (_, true, false, false) => glyphs[1],
+            _ => glyphs[0]
+        }
+    }
+}
+
+impl GlyphSet {
+    // The character to draw a tile with, falling back to the tile's own symbol
+    pub fn tile_glyph(&self, tile_type: &TileType, joins: WallJoins, symbol: char) -> char {
+        match (self, tile_type) {
+            (GlyphSet::Ascii, TileType::Room) => '.',
+            (GlyphSet::Ascii, TileType::Entry) => '<',
+            (GlyphSet::Ascii, TileType::Exit) => '>',
+
+            (GlyphSet::Unicode, TileType::Wall) => joins.pick(&LIGHT_WALLS),
+            (GlyphSet::Unicode, TileType::Room) => '·',
+            (GlyphSet::Unicode, TileType::Corridor) => '░',
+            (GlyphSet::Unicode, TileType::Door) => '▫',
+            (GlyphSet::Unicode, TileType::Window) => '□',
+            (GlyphSet::Unicode, TileType::Entry) => '▲',
+            (GlyphSet::Unicode, TileType::Exit) => '▼',
+            (GlyphSet::Unicode, TileType::Water) => '≈',
+            (GlyphSet::Unicode, TileType::Forest) => '♣',
+            (GlyphSet::Unicode, TileType::DungeonEntrance) => 'Ω',
+
+            (GlyphSet::Cp437, TileType::Wall) => joins.pick(&DOUBLE_WALLS),
+            (GlyphSet::Cp437, TileType::Room) => '·',
+            (GlyphSet::Cp437, TileType::Corridor) => '▒',
+            (GlyphSet::Cp437, TileType::Door) => '+',
+            (GlyphSet::Cp437, TileType::Window) => '≡',
+            (GlyphSet::Cp437, TileType::Entry) => '<',
+            (GlyphSet::Cp437, TileType::Exit) => '>',
+            (GlyphSet::Cp437, TileType::Water) => '≈',
+            (GlyphSet::Cp437, TileType::Forest) => '♣',
+            (GlyphSet::Cp437, TileType::DungeonEntrance) => '∩',
+            _ => symbol
+        }
+    }
+
+    pub fn player_glyph(&self, symbol: char) -> char {
+        match self {
+            GlyphSet::Cp437 => '☺',
+            _ => symbol
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::map::position::Position;
+    use crate::map::tile::TileType;
+    use crate::test::utils::test_utils::build_test_level;
+    use crate::ui::glyphs::{GlyphSet, WallJoins};
+
+    #[test]
+    fn test_tile_glyphs() {
+        // GIVEN each glyph set
+        for glyph_set in [GlyphSet::Ascii, GlyphSet::Unicode, GlyphSet::Cp437] {
+            let glyph = |tile_type: TileType| glyph_set.tile_glyph(&tile_type, WallJoins::default(), '-');
+            // THEN room and corridor floors can be told apart, as can the way in and out
+            assert_ne!(glyph(TileType::Room), glyph(TileType::Corridor), "{:?}", glyph_set);
+            assert_ne!(glyph(TileType::Entry), glyph(TileType::Exit), "{:?}", glyph_set);
+        }
+
+        // AND ASCII only ever uses ASCII
+        let ascii = GlyphSet::Ascii;
+        assert!([TileType::Room, TileType::Entry, TileType::Exit].iter().all(|t| ascii.tile_glyph(t, WallJoins::default(), '#').is_ascii()));
+        // AND anything without a glyph of it's own keeps it's symbol
+        assert_eq!('#', ascii.tile_glyph(&TileType::Wall, WallJoins::default(), '#'));
+    }
+
+    #[test]
+    fn test_wall_joins() {
+        // GIVEN a walled room
+        let level = build_test_level(None, None);
+        let tiles = &level.map.as_ref().unwrap().tiles;
+        let joins = |x, y| WallJoins::find(tiles, Position::new(x, y));
+
+        // THEN each corner and side of the walls is joined up with box-drawing characters
+        let unicode = GlyphSet::Unicode;
+        assert_eq!('┌', unicode.tile_glyph(&TileType::Wall, joins(0, 0), '#'));
+        assert_eq!('─', unicode.tile_glyph(&TileType::Wall, joins(1, 0), '#'));
+        assert_eq!('┐', unicode.tile_glyph(&TileType::Wall, joins(2, 0), '#'));
+        assert_eq!('│', unicode.tile_glyph(&TileType::Wall, joins(0, 1), '#'));
+        assert_eq!('╝', GlyphSet::Cp437.tile_glyph(&TileType::Wall, joins(2, 2), '#'));
+    }
+}
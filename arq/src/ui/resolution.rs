@@ -1,6 +1,6 @@
 use ratatui::layout::Rect;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Resolution {
     pub width : u16,
     pub height : u16
@@ -16,6 +16,11 @@ impl Resolution {
         Resolution { width: rect.width, height: rect.height }
     }
 
+    // Whether this resolution can be shown within a terminal of the given size
+    pub fn fits_within(&self, width: u16, height: u16) -> bool {
+        self.width <= width && self.height <= height
+    }
+
     pub fn to_rect(&self) -> Rect {
         Rect {
             x: 0,
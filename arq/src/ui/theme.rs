@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde_json::Value;
+
+use crate::engine::loot_table::Rarity;
+use crate::error::errors::ErrorWrapper;
+use crate::terminal::colour_mapper::to_16_colour;
+use crate::widget::stateful::dropdown_widget::DropdownOption;
+
+pub const RESOURCE_THEME_FILE: &str = "resources/theme.json";
+
+// What a colour is used for, rather than the colour itself
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ThemeColour {
+    Background,
+    Wall,
+    Floor,
+    Door,
+    Entry,
+    Exit,
+    Player,
+    Hostile,
+    CommonItem,
+    UncommonItem,
+    RareItem
+}
+
+impl ThemeColour {
+    pub fn all() -> Vec<ThemeColour> {
+        vec![ThemeColour::Background, ThemeColour::Wall, ThemeColour::Floor, ThemeColour::Door, ThemeColour::Entry, ThemeColour::Exit,
+             ThemeColour::Player, ThemeColour::Hostile, ThemeColour::CommonItem, ThemeColour::UncommonItem, ThemeColour::RareItem]
+    }
+
+    // The key used for this in a theme file
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemeColour::Background => "BACKGROUND",
+            ThemeColour::Wall => "WALL",
+            ThemeColour::Floor => "FLOOR",
+            ThemeColour::Door => "DOOR",
+            ThemeColour::Entry => "ENTRY",
+            ThemeColour::Exit => "EXIT",
+            ThemeColour::Player => "PLAYER",
+            ThemeColour::Hostile => "HOSTILE",
+            ThemeColour::CommonItem => "COMMON_ITEM",
+            ThemeColour::UncommonItem => "UNCOMMON_ITEM",
+            ThemeColour::RareItem => "RARE_ITEM"
+        }
+    }
+
+    pub fn for_rarity(rarity: Rarity) -> ThemeColour {
+        match rarity {
+            Rarity::Common => ThemeColour::CommonItem,
+            Rarity::Uncommon => ThemeColour::UncommonItem,
+            Rarity::Rare => ThemeColour::RareItem
+        }
+    }
+}
+
+// The themes to choose from in the settings menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThemeChoice {
+    Default,
+    HighContrast,
+    Deuteranopia,
+    // Read from the theme file
+    Custom
+}
+
+pub fn get_theme_dropdown_options() -> Vec<DropdownOption<ThemeChoice>> {
+    vec![
+        DropdownOption { display_name: "Default", value: Some(ThemeChoice::Default) },
+        DropdownOption { display_name: "High Contrast", value: Some(ThemeChoice::HighContrast) },
+        DropdownOption { display_name: "Deuteranopia", value: Some(ThemeChoice::Deuteranopia) },
+        DropdownOption { display_name: "Custom", value: Some(ThemeChoice::Custom) }
+    ]
+}
+
+#[derive(Clone, Debug)]
+pub struct Theme {
+    colours: HashMap<ThemeColour, Color>,
+    // Without truecolor support every colour is brought down to the nearest of the 16 basic ones
+    truecolor: bool
+}
+
+impl Theme {
+    fn from_colours(colours: Vec<(ThemeColour, Color)>) -> Theme {
+        Theme { colours: colours.into_iter().collect(), truecolor: true }
+    }
+
+    // The original look of the game
+    pub fn default_theme() -> Theme {
+        Theme::from_colours(vec![
+            (ThemeColour::Background, Color::Black),
+            (ThemeColour::Wall, Color::Rgb(181, 137, 0)),
+            (ThemeColour::Floor, Color::Blue),
+            (ThemeColour::Door, Color::White),
+            (ThemeColour::Entry, Color::Red),
+            (ThemeColour::Exit, Color::Green),
+            (ThemeColour::Player, Color::Green),
+            (ThemeColour::Hostile, Color::Red),
+            (ThemeColour::CommonItem, Color::White),
+            (ThemeColour::UncommonItem, Color::Cyan),
+            (ThemeColour::RareItem, Color::Yellow)
+        ])
+    }
+
+    // Bright colours only, kept well apart from one another and the background
+    pub fn high_contrast() -> Theme {
+        Theme::from_colours(vec![
+            (ThemeColour::Background, Color::Black),
+            (ThemeColour::Wall, Color::White),
+            (ThemeColour::Floor, Color::DarkGray),
+            (ThemeColour::Door, Color::LightYellow),
+            (ThemeColour::Entry, Color::LightMagenta),
+            (ThemeColour::Exit, Color::LightGreen),
+            (ThemeColour::Player, Color::LightCyan),
+            (ThemeColour::Hostile, Color::LightRed),
+            (ThemeColour::CommonItem, Color::Gray),
+            (ThemeColour::UncommonItem, Color::LightBlue),
+            (ThemeColour::RareItem, Color::Yellow)
+        ])
+    }
+
+    /*
+     * Avoids telling things apart by red and green alone
+     * Based on the Okabe-Ito palette, which stays distinct with the most common kinds of colourblindness
+     */
+    pub fn deuteranopia() -> Theme {
+        Theme::from_colours(vec![
+            (ThemeColour::Background, Color::Black),
+            (ThemeColour::Wall, Color::Rgb(230, 159, 0)),
+            (ThemeColour::Floor, Color::Rgb(0, 114, 178)),
+            (ThemeColour::Door, Color::White),
+            (ThemeColour::Entry, Color::Rgb(213, 94, 0)),
+            (ThemeColour::Exit, Color::Rgb(86, 180, 233)),
+            (ThemeColour::Player, Color::Rgb(240, 228, 66)),
+            (ThemeColour::Hostile, Color::Rgb(204, 121, 167)),
+            (ThemeColour::CommonItem, Color::Gray),
+            (ThemeColour::UncommonItem, Color::Rgb(86, 180, 233)),
+            (ThemeColour::RareItem, Color::Rgb(240, 228, 66))
+        ])
+    }
+
+    /*
+     * Reads a theme file of colour names or hex values by key, i.e { "WALL": "#b58900", "PLAYER": "lightgreen" }
+     * Anything left out is taken from the default theme
+     */
+    pub fn load(path: &str) -> Result<Theme, ErrorWrapper> {
+        let raw = fs::read_to_string(path)?;
+        let value: Value = serde_json::from_str(&raw)
+            .map_err(|e| ErrorWrapper::new_internal(format!("Failed to parse theme: {}", e)))?;
+
+        let mut theme = Theme::default_theme();
+        for theme_colour in ThemeColour::all() {
+            if let Some(colour_name) = value.get(theme_colour.key()).and_then(|c| c.as_str()) {
+                let colour = Color::from_str(colour_name)
+                    .map_err(|_| ErrorWrapper::new_internal(format!("Unknown colour '{}' for {} in theme: {}", colour_name, theme_colour.key(), path)))?;
+                theme.colours.insert(theme_colour, colour);
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn with_truecolor(mut self, truecolor: bool) -> Theme {
+        self.truecolor = truecolor;
+        self
+    }
+
+    pub fn get_colour(&self, theme_colour: ThemeColour) -> Color {
+        let colour = self.colours.get(&theme_colour).copied().unwrap_or(Color::Reset);
+        self.fit_colour(colour)
+    }
+
+    // Brings any colour down to what the terminal can show
+    pub fn fit_colour(&self, colour: Color) -> Color {
+        if self.truecolor {
+            colour
+        } else {
+            to_16_colour(colour)
+        }
+    }
+}
+
+// Terminals advertise 24-bit colour through COLORTERM, anything else is assumed to only have the basic 16
+pub fn supports_truecolor() -> bool {
+    std::env::var("COLORTERM").is_ok_and(|colorterm| colorterm == "truecolor" || colorterm == "24bit")
+}
+
+pub fn build_theme(choice: ThemeChoice) -> Theme {
+    let theme = match choice {
+        ThemeChoice::Default => Theme::default_theme(),
+        ThemeChoice::HighContrast => Theme::high_contrast(),
+        ThemeChoice::Deuteranopia => Theme::deuteranopia(),
+        ThemeChoice::Custom => Theme::load(RESOURCE_THEME_FILE).unwrap_or_else(|e| {
+            log::warn!("Failed to load custom theme: {}, using the default theme.", e);
+            Theme::default_theme()
+        })
+    };
+    theme.with_truecolor(supports_truecolor())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use ratatui::style::Color;
+    use uuid::Uuid;
+
+    use crate::ui::theme::{Theme, ThemeColour, RESOURCE_THEME_FILE};
+
+    #[test]
+    fn test_built_in_themes() {
+        // GIVEN each built-in theme
+        for theme in [Theme::default_theme(), Theme::high_contrast(), Theme::deuteranopia()] {
+            // THEN every semantic colour is given a colour of it's own
+            for theme_colour in ThemeColour::all() {
+                assert_ne!(Color::Reset, theme.get_colour(theme_colour), "{:?} is missing", theme_colour);
+            }
+            // AND the player always stands out from anything hostile
+            assert_ne!(theme.get_colour(ThemeColour::Player), theme.get_colour(ThemeColour::Hostile));
+        }
+    }
+
+    #[test]
+    fn test_16_colour_fallback() {
+        // GIVEN the default theme, with it's truecolor walls
+        let theme = Theme::default_theme();
+        assert_eq!(Color::Rgb(181, 137, 0), theme.get_colour(ThemeColour::Wall));
+
+        // WHEN the terminal doesn't support truecolor
+        let theme = theme.with_truecolor(false);
+
+        // THEN the walls are brought down to the nearest basic colour, leaving basic colours alone
+        assert_eq!(Color::Yellow, theme.get_colour(ThemeColour::Wall));
+        assert_eq!(Color::Blue, theme.get_colour(ThemeColour::Floor));
+    }
+
+    #[test]
+    fn test_load_theme() {
+        // GIVEN a theme file setting only a couple of colours
+        let path = std::env::temp_dir().join(format!("arq-theme-{}.json", Uuid::new_v4()));
+        fs::write(&path, r##"{ "WALL": "#ffffff", "PLAYER": "lightmagenta" }"##).unwrap();
+
+        // WHEN we load it
+        let theme = Theme::load(path.to_str().unwrap()).unwrap();
+
+        // THEN those colours are used, with the default theme for the rest
+        assert_eq!(Color::Rgb(255, 255, 255), theme.get_colour(ThemeColour::Wall));
+        assert_eq!(Color::LightMagenta, theme.get_colour(ThemeColour::Player));
+        assert_eq!(Theme::default_theme().get_colour(ThemeColour::Floor), theme.get_colour(ThemeColour::Floor));
+
+        // AND the theme file shipped with the game looks just like the default theme
+        let shipped = Theme::load(RESOURCE_THEME_FILE).unwrap();
+        for theme_colour in ThemeColour::all() {
+            assert_eq!(Theme::default_theme().get_colour(theme_colour), shipped.get_colour(theme_colour));
+        }
+
+        // AND an unknown colour is an error rather than silently ignored
+        fs::write(&path, r#"{ "WALL": "not-a-colour" }"#).unwrap();
+        assert!(Theme::load(path.to_str().unwrap()).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,18 +1,19 @@
-use std::io;
-
-use termion::event::Key;
-use termion::input::TermRead;
+use log::info;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Span, Line};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use crate::engine::level::Level;
+use crate::error::errors::ErrorWrapper;
 use crate::map::position::Area;
-use crate::ui::resolution::Resolution;
+use crate::ui::resolution::{Resolution, MIN_RESOLUTION};
+use crate::ui::glyphs::GlyphSet;
+use crate::ui::theme::Theme;
 use crate::ui::ui_areas::{UI_AREA_NAME_CONSOLE, UI_AREA_NAME_MAIN};
 use crate::ui::ui_layout::{LayoutType, UILayout};
 use crate::view::framehandler::console::{ConsoleBuffer, ConsoleFrameHandler};
 use crate::view::framehandler::{FrameData, FrameHandler};
+use crate::view::util::cell_builder::CellBuilder;
 use crate::widget::{StandardWidgetType, StatefulWidgetState, StatefulWidgetType};
 
 pub struct UI {
@@ -21,7 +22,14 @@ pub struct UI {
     stateful_widgets: Vec<StatefulWidgetType>,
     additional_widgets: Vec<StandardWidgetType>,
     frame_size : Option<Area>,
+    // The resolution chosen in settings, or None to fill the whole terminal
+    resolution : Option<Resolution>,
     frame_handler: ConsoleFrameHandler,
+    // A one-off message for the console, shown in place of the usage hint the next time the map is drawn
+    console_message: Option<String>,
+    // The colours and characters chosen in settings, used for anything drawn from the map
+    theme: Theme,
+    glyph_set: GlyphSet,
     pub ui_layout: Option<UILayout>
 }
 
@@ -30,6 +38,7 @@ pub enum StartMenuChoice {
     Play,
     Settings,
     Info,
+    Leaderboard,
     Quit
 }
 
@@ -37,11 +46,15 @@ pub fn build_ui() -> UI {
     let frame_handler = ConsoleFrameHandler { buffer: ConsoleBuffer { content: String::from("") } };
     UI {
         frame_size : None,
+        resolution : None,
         render_additional: false,
         console_visible: false,
         stateful_widgets: Vec::new(),
         additional_widgets: Vec::new(),
         frame_handler,
+        console_message: None,
+        theme: Theme::default_theme(),
+        glyph_set: GlyphSet::Ascii,
         ui_layout: None
     }
 }
@@ -54,7 +67,8 @@ impl std::convert::TryFrom<usize> for StartMenuChoice {
             0 => Ok(StartMenuChoice::Play),
             1 => Ok(StartMenuChoice::Settings),
             2 => Ok(StartMenuChoice::Info),
-            3 =>  Ok(StartMenuChoice::Quit),
+            3 => Ok(StartMenuChoice::Leaderboard),
+            4 =>  Ok(StartMenuChoice::Quit),
             _ => Err("Failed to convert to StartMenuChoice".to_string())
         }
     }
@@ -102,12 +116,59 @@ impl UI {
         }
     }
 
-    pub fn re_init(&mut self, frame_area: Area) {
+    pub fn re_init(&mut self, frame_area: Area) -> Result<(), ErrorWrapper> {
         let resolution = Resolution::new(frame_area.width, frame_area.height);
-        self.frame_size = Some(frame_area);
         let mut ui_layout = UILayout::new(resolution);
-        ui_layout.rebuild_areas(frame_area.to_rect());
+        ui_layout.rebuild_areas(frame_area.to_rect())?;
+        self.frame_size = Some(frame_area);
         self.ui_layout = Some(ui_layout);
+        Ok(())
+    }
+
+    pub fn set_resolution(&mut self, resolution: Option<Resolution>) {
+        self.resolution = resolution;
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn get_theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn set_glyph_set(&mut self, glyph_set: GlyphSet) {
+        self.glyph_set = glyph_set;
+    }
+
+    // For drawing anything from the map in the current theme and glyph set
+    pub fn build_cell_builder(&self) -> CellBuilder {
+        CellBuilder::new(self.theme.clone(), self.glyph_set)
+    }
+
+    // The smallest terminal the UI can currently be drawn in
+    pub fn get_required_resolution(&self) -> Resolution {
+        self.resolution.unwrap_or(MIN_RESOLUTION)
+    }
+
+    /*
+        Sizes the UI for a terminal of the given size, following the terminal when fullscreen
+        Rebuilds the layout if the size has changed since last time, returning whether it did
+        Fails if the terminal is too small, leaving the UI as it was
+     */
+    pub fn fit_to_terminal(&mut self, terminal_size: Rect) -> Result<bool, ErrorWrapper> {
+        let required = self.get_required_resolution();
+        if !required.fits_within(terminal_size.width, terminal_size.height) {
+            return ErrorWrapper::internal_result(format!("A terminal size of {}x{} is too small, {}x{} is needed", terminal_size.width, terminal_size.height, required.width, required.height));
+        }
+
+        let frame_area = Area::from_resolution(self.resolution.unwrap_or(Resolution::from_rect(terminal_size)));
+        if self.ui_layout.is_some() && self.frame_size == Some(frame_area) {
+            return Ok(false);
+        }
+        info!("Resizing the UI to: {}x{}", frame_area.width, frame_area.height);
+        self.re_init(frame_area)?;
+        Ok(true)
     }
 
     /*
@@ -119,7 +180,7 @@ impl UI {
     pub fn render<'a>(&mut self, level: Option<Level>, frame: &mut ratatui::Frame<'_>) {
         let ui_layout =  self.ui_layout.as_mut().ok_or("Failed to get ui_layout, has it been initialised?").unwrap();
         let areas = ui_layout.get_or_build_areas(frame.size(), LayoutType::StandardSplit);
-        if let Some(main) = areas.get_area(UI_AREA_NAME_MAIN) {
+        if let Some(main) = areas.ok().and_then(|areas| areas.get_area(UI_AREA_NAME_MAIN)) {
             let main_area = main.area;
             let main_block = build_main_block();
             {
@@ -160,6 +221,14 @@ impl UI {
         self.frame_handler.buffer.content = String::new();
     }
 
+    pub fn set_console_message(&mut self, message: String) {
+        self.console_message = Some(message);
+    }
+
+    pub fn take_console_message(&mut self) -> Option<String> {
+        self.console_message.take()
+    }
+
     pub fn get_additional_widgets(&self) -> &Vec<StandardWidgetType> {
         &self.additional_widgets
     }
@@ -173,10 +242,6 @@ impl UI {
     }
 }
 
-pub fn get_input_key() -> Result<Key, io::Error> {
-    Ok(io::stdin().keys().next().unwrap()?)
-}
-
 impl Draw for UI {
 
     fn draw_info(&mut self, frame: &mut ratatui::Frame<'_>) {
@@ -258,4 +323,54 @@ impl Draw for UI {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
 
+    use crate::ui::resolution::Resolution;
+    use crate::ui::ui::build_ui;
+    use crate::ui::ui_areas::UI_AREA_NAME_MAIN;
+    use crate::ui::ui_layout::LayoutType;
+
+    fn get_main_width(ui: &crate::ui::ui::UI) -> u16 {
+        ui.ui_layout.as_ref().unwrap().get_ui_areas(LayoutType::StandardSplit).get_area(UI_AREA_NAME_MAIN).unwrap().area.width
+    }
+
+    #[test]
+    fn test_fit_to_terminal_fullscreen() {
+        // GIVEN a fullscreen UI fitted to an 80x24 terminal
+        let mut ui = build_ui();
+        assert!(ui.fit_to_terminal(Rect::new(0, 0, 80, 24)).unwrap());
+        assert_eq!(80, get_main_width(&ui));
+
+        // WHEN the terminal stays the same size
+        // THEN nothing needs rebuilding
+        assert!(!ui.fit_to_terminal(Rect::new(0, 0, 80, 24)).unwrap());
+
+        // WHEN the terminal is resized
+        // THEN the layout follows it
+        assert!(ui.fit_to_terminal(Rect::new(0, 0, 120, 40)).unwrap());
+        assert_eq!(120, get_main_width(&ui));
+
+        // AND a terminal below the minimum is too small
+        assert!(ui.fit_to_terminal(Rect::new(0, 0, 60, 20)).is_err());
+        // AND the layout is left as it was
+        assert_eq!(120, get_main_width(&ui));
+    }
+
+    #[test]
+    fn test_fit_to_terminal_chosen_resolution() {
+        // GIVEN a UI set to 100x30
+        let mut ui = build_ui();
+        ui.set_resolution(Some(Resolution::new(100, 30)));
+
+        // WHEN it's fitted to a larger terminal
+        // THEN the layout keeps to the chosen resolution
+        assert!(ui.fit_to_terminal(Rect::new(0, 0, 160, 50)).unwrap());
+        assert_eq!(100, get_main_width(&ui));
+
+        // AND any terminal smaller than that is too small, even if above the minimum
+        assert!(ui.fit_to_terminal(Rect::new(0, 0, 90, 30)).is_err());
+        assert_eq!(100, ui.get_required_resolution().width);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use log::info;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+use crate::error::errors::ErrorWrapper;
 use crate::map::position::{Area, Position};
 use crate::ui::ui_areas::{BorderedArea, UIArea, UIAreas, UI_AREA_NAME_CONSOLE, UI_AREA_NAME_MAIN, UI_AREA_NAME_MINIMAP};
 use crate::ui::ui_layout::LayoutType;
@@ -20,12 +21,12 @@ pub struct UIAreasBuilder {
     Tries to build a single centered Rect based on 80x24 minimum frame size
     If the view is smaller than this, the view will be split as per usual for smaller sizes
  */
-fn build_single_main_window_centered_areas(total_area: Area) -> HashMap::<String, UIArea> {
+fn build_single_main_window_centered_areas(total_area: Area) -> Result<HashMap::<String, UIArea>, ErrorWrapper> {
     let mut areas = HashMap::<String, UIArea>::new();
     let target = Rect::new(0, 0, MIN_RESOLUTION.width, MIN_RESOLUTION.height);
-    let area = center_area(target, total_area.to_rect(), MIN_RESOLUTION).unwrap();
+    let area = center_area(target, total_area.to_rect(), MIN_RESOLUTION)?;
     areas.insert(UI_AREA_NAME_MAIN.to_string(), UIArea { name: UI_AREA_NAME_MAIN.to_string(), area });
-    areas
+    Ok(areas)
 }
 
 pub(crate) fn build_single_main_window_areas(total_area: Area) -> HashMap::<String, UIArea>   {
@@ -63,7 +64,7 @@ pub main_area : BorderedArea,
 pub console_area : BorderedArea,
 pub minimap_area : BorderedArea
  */
-fn build_combat_view_areas(total_area: Area) -> HashMap::<String, UIArea>  {
+fn build_combat_view_areas(total_area: Area) -> Result<HashMap::<String, UIArea>, ErrorWrapper>  {
     // Split the entire view area into main and console
     let ui_areas = Layout::default()
         .direction(Direction::Vertical)
@@ -89,16 +90,16 @@ fn build_combat_view_areas(total_area: Area) -> HashMap::<String, UIArea>  {
     let minimap_position = Position::new(console_rect.x - minimap_width, console_rect.y);
     let minimap_ui_area = Area::new(minimap_position, minimap_width, console_rect.height);
 
-    let main_area = BorderedArea::from_area(Area::from_rect(ui_areas[0])).unwrap();
-    let console_area = BorderedArea::from_rect(console_rect).unwrap();
-    let minimap_area = BorderedArea::from_area(minimap_ui_area).unwrap();
+    let main_area = BorderedArea::from_area(Area::from_rect(ui_areas[0]))?;
+    let console_area = BorderedArea::from_rect(console_rect)?;
+    let minimap_area = BorderedArea::from_area(minimap_ui_area)?;
 
     let mut areas = HashMap::<String, UIArea>::new();
     areas.insert(UI_AREA_NAME_MAIN.to_string(), UIArea { name: UI_AREA_NAME_MAIN.to_string(), area: main_area.outer });
     areas.insert(UI_AREA_NAME_CONSOLE.to_string(), UIArea { name: UI_AREA_NAME_CONSOLE.to_string(), area: console_area.outer });
     areas.insert(UI_AREA_NAME_MINIMAP.to_string(), UIArea { name: UI_AREA_NAME_MINIMAP.to_string(), area: minimap_area.outer });
 
-    areas
+    Ok(areas)
 }
 
 impl UIAreasBuilder {
@@ -122,39 +123,38 @@ impl UIAreasBuilder {
       Builds the replacement for Frame.size() based on the target resolution
       This is meant to represent the total available view area
      */
-    fn build_total_area(&self) -> Area {
+    fn build_total_area(&self) -> Result<Area, ErrorWrapper> {
         let frame_size : Rect = self.frame_size.to_rect();
-        if frame_size.width >= 80 && frame_size.height >= 24 {
-            return Area::new(Position::zero(), frame_size.width, frame_size.height);
+        if MIN_RESOLUTION.fits_within(frame_size.width, frame_size.height) {
+            Ok(Area::new(Position::zero(), frame_size.width, frame_size.height))
         } else {
-            // TODO we probably want to throw an error / show the new error screen for this case
-            panic!("Screen resolution not supported, cannot build UI areas..");
+            ErrorWrapper::internal_result(format!("Screen resolution {}x{} not supported, cannot build UI areas..", frame_size.width, frame_size.height))
         }
     }
 
     // If the console if visible, splits a frame vertically into the 'main' and lower console areas
     // Otherwise returns the original frame size
-    pub fn build(&self) -> (LayoutType, UIAreas) {
-        let total_area = self.build_total_area();
+    pub fn build(&self) -> Result<(LayoutType, UIAreas), ErrorWrapper> {
+        let total_area = self.build_total_area()?;
         info!("Building layout of type: {:?} with total area: {:?}", self.layout_type, total_area);
         match self.layout_type {
             StandardSplit => {
                 let areas = build_split_areas(total_area);
-                return (StandardSplit, UIAreas::new(areas))
+                Ok((StandardSplit, UIAreas::new(areas)))
             },
             SingleMainWindow => {
                 let areas= build_single_main_window_areas(total_area);
-                return (SingleMainWindow, UIAreas::new(areas))
+                Ok((SingleMainWindow, UIAreas::new(areas)))
             },
             SingleMainWindowCentered => {
-                let areas = build_single_main_window_centered_areas(total_area);
-                return (SingleMainWindowCentered, UIAreas::new(areas))
+                let areas = build_single_main_window_centered_areas(total_area)?;
+                Ok((SingleMainWindowCentered, UIAreas::new(areas)))
             },
             CombatView => {
-                let areas = build_combat_view_areas(total_area);
-                return (CombatView, UIAreas::new(areas))
+                let areas = build_combat_view_areas(total_area)?;
+                Ok((CombatView, UIAreas::new(areas)))
             }
-        };
+        }
     }
 
     pub fn set_frame_size(&mut self, frame_size: Area) {
@@ -187,7 +187,7 @@ mod tests {
         let frame_size =  Area::new(Position::new(0,0), 80, 24);
         // WHEN we call to build the standard split areas (default)
         let builder = UIAreasBuilder::new(frame_size);
-        let result = builder.build();
+        let result = builder.build().unwrap();
         let areas = result.1;
 
         // THEN we expect 2 areas split 80/30% vertically
@@ -217,7 +217,7 @@ mod tests {
         // WHEN we call to build the a single main window
         let result = UIAreasBuilder::new(frame_size)
             .layout_type(LayoutType::SingleMainWindow)
-            .build();
+            .build().unwrap();
 
         let areas = result.1;
 
@@ -239,7 +239,7 @@ mod tests {
         // WHEN we call to build view areas
         let result = UIAreasBuilder::new(frame_size)
             .layout_type(LayoutType::CombatView)
-            .build().1;
+            .build().unwrap().1;
 
         // THEN we expect
         // A main area of
@@ -265,4 +265,37 @@ mod tests {
         assert_eq!(5, minimap_area.outer.height);
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_build_too_small() {
+        // GIVEN a frame size below the minimum of 80x24
+        let frame_size = Area::new(Position::new(0,0), 60, 20);
+        // WHEN we call to build any of the layouts
+        for layout_type in [LayoutType::StandardSplit, LayoutType::SingleMainWindow, LayoutType::SingleMainWindowCentered, LayoutType::CombatView] {
+            let result = UIAreasBuilder::new(frame_size)
+                .layout_type(layout_type)
+                .build();
+            // THEN we expect an error rather than a panic
+            assert!(result.is_err(), "{:?}", layout_type);
+        }
+    }
+
+    #[test]
+    fn test_build_combat_view_areas_160_50() {
+        // GIVEN a larger frame size of 160x50
+        let frame_size = Area::new(Position::new(0,0), 160, 50);
+        // WHEN we call to build view areas
+        let result = UIAreasBuilder::new(frame_size)
+            .layout_type(LayoutType::CombatView)
+            .build().unwrap().1;
+
+        // THEN we expect the areas to scale up with it
+        // The main area with 80% of the 50 lines (40)
+        let main_area = result.get_bordered_area(UI_AREA_NAME_MAIN);
+        assert_eq!(160, main_area.outer.width);
+        assert_eq!(40, main_area.outer.height);
+        // AND the console and minimap splitting the rest 80/20
+        assert_eq!(128, result.get_bordered_area(UI_AREA_NAME_CONSOLE).outer.width);
+        assert_eq!(32, result.get_bordered_area(UI_AREA_NAME_MINIMAP).outer.width);
+    }
+}
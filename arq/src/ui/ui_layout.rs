@@ -3,6 +3,7 @@ use std::slice::Iter;
 
 use ratatui::layout::Rect;
 
+use crate::error::errors::ErrorWrapper;
 use crate::map::position::Area;
 use crate::ui::resolution::Resolution;
 use crate::ui::ui_areas::UIAreas;
@@ -40,15 +41,15 @@ impl UILayout {
         UILayout { ui_areas: HashMap::new(), ui_areas_builder: UIAreasBuilder::new(frame_size) }
     }
 
-    fn rebuild_ui_areas(&mut self, frame_size: Rect, layout_type: LayoutType) -> &UIAreas {
+    fn rebuild_ui_areas(&mut self, frame_size: Rect, layout_type: LayoutType) -> Result<&UIAreas, ErrorWrapper> {
         self.ui_areas_builder.set_frame_size(Area::from_rect(frame_size));
         self.ui_areas_builder.layout_type = layout_type;
-        let ui_areas_result = self.ui_areas_builder.build();
+        let ui_areas_result = self.ui_areas_builder.build()?;
 
         let rebuilt_areas = ui_areas_result.1.clone();
         self.ui_areas.insert(layout_type.clone(), rebuilt_areas);
 
-        return self.get_ui_areas(layout_type);
+        Ok(self.get_ui_areas(layout_type))
     }
 
     /*
@@ -59,31 +60,26 @@ impl UILayout {
         return &self.ui_areas.get(&layout_type).as_ref().unwrap();
     }
 
-    pub fn init_areas(&mut self, frame_size: Rect) {
+    pub fn rebuild_areas(&mut self, frame_size: Rect) -> Result<(), ErrorWrapper> {
         for layout_type in Self::layout_types() {
-           self.get_or_build_areas(frame_size, *layout_type);
-        }
-    }
-
-    pub fn rebuild_areas(&mut self, frame_size: Rect) {
-        for layout_type in Self::layout_types() {
-            self.rebuild_ui_areas(frame_size, *layout_type);
+            self.rebuild_ui_areas(frame_size, *layout_type)?;
         }
+        Ok(())
     }
 
     /*
         This tries to either:
          1. Unwrap and return the current ui_areas
          2. Build a fresh ui_areas using the frame_size provided
-         This should always be safe to call as it will build the areas if needed
+         This will build the areas if needed, failing only if the frame is too small to fit them
      */
-    pub fn get_or_build_areas(&mut self, frame_size: Rect, layout_type: LayoutType) -> &UIAreas {
+    pub fn get_or_build_areas(&mut self, frame_size: Rect, layout_type: LayoutType) -> Result<&UIAreas, ErrorWrapper> {
         let current_ui_areas = &self.ui_areas.get(&layout_type);
         return if current_ui_areas.is_some() {
             if self.ui_areas_builder.needs_rebuilding(Area::from_rect(frame_size)) {
                 self.rebuild_ui_areas(frame_size, layout_type)
             } else {
-                &self.ui_areas.get(&layout_type).as_ref().unwrap()
+                Ok(self.ui_areas.get(&layout_type).as_ref().unwrap())
             }
         } else {
             self.rebuild_ui_areas(frame_size, layout_type)
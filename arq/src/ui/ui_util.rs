@@ -1,6 +1,6 @@
 use std::io::{Error, ErrorKind};
 
-use ratatui::layout::{Rect, Size};
+use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::text::{Span, Line};
 use ratatui::widgets::Paragraph;
@@ -101,18 +101,6 @@ pub fn center_area(target: Rect, frame_size: Rect, min_resolution: Resolution) -
     }
 }
 
-pub(crate) fn check_display_size(frame_size_result: Option<Size>) -> Result<(), std::io::Error> {
-    if let Some(frame_size) = frame_size_result {
-        // Check for 80x24 minimum resolution
-        if frame_size.height < MIN_RESOLUTION.height || frame_size.width < MIN_RESOLUTION.width {
-            return Err(Error::new(ErrorKind::Other, format!("Sorry, your terminal is below the minimum supported resolution of {}x{}.", MIN_RESOLUTION.height, MIN_RESOLUTION.width)));
-        }
-        Ok(())
-    } else {
-        return Err(Error::new(ErrorKind::Other, "Something went wrong; no frame size has been set!"));
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use ratatui::layout::Rect;
@@ -1,17 +1,15 @@
-use std::io;
-
 use crate::error::errors::ErrorWrapper;
 use termion::event::Key;
-use termion::input::TermRead;
 use ratatui::layout::Rect;
 use ratatui::CompletedFrame;
 use ratatui::Frame;
 
+use crate::input::{InputEvent, KeyInputResolver};
 use crate::map::position::Area;
 use crate::terminal::terminal_manager::TerminalManager;
 pub use crate::ui::resolution::MIN_RESOLUTION;
-use crate::ui::ui::get_input_key;
-use crate::ui::ui_util::{build_paragraph_multi, check_display_size};
+use crate::ui::ui::UI;
+use crate::ui::ui_util::build_paragraph_multi;
 
 pub mod framehandler;
 pub mod util;
@@ -24,6 +22,9 @@ pub mod combat_view;
 pub mod model;
 pub mod dialog_view;
 pub mod menu_view;
+pub mod leaderboard_view;
+pub mod pickup_view;
+pub mod item_detail_view;
 
 /*
     A "View" is:
@@ -63,37 +64,39 @@ pub fn resolve_area<B : ratatui::backend::Backend>(area: Option<Rect>, frame: &F
     }
 }
 
-pub fn resolve_input(input : Option<Key>) -> Result<Key, io::Error> {
-    match input {
-        Some(input_key) => {
-            Ok(input_key)
-        },
-        _ => {
-            Ok(get_input_key()?)
-        }
-    }
-
+// Frame handlers don't read input themselves, the view they're part of hands them each key
+pub fn expect_input(input : Option<Key>) -> Result<Key, ErrorWrapper> {
+    input.ok_or(ErrorWrapper::new_internal(String::from("Frame handlers need to be given the key to handle")))
 }
 
-pub fn verify_display_size<B : ratatui::backend::Backend>(terminal_manager : &mut TerminalManager<B>) {
+/*
+    Fits the UI to the terminal, rebuilding it's layout if the terminal has been resized since the last draw
+    Returns whether the UI was resized, so any areas worked out from the old layout can be worked out again
+    While the terminal is too small for the UI, a "terminal too small" screen is shown until it's resized
+ */
+pub fn verify_display_size<B : ratatui::backend::Backend>(ui: &mut UI, terminal_manager : &mut TerminalManager<B>, key_input: &mut dyn KeyInputResolver) -> bool {
     loop {
         let frame_size = terminal_manager.terminal.size().unwrap();
-        let result = check_display_size(Some(frame_size));
-        match result {
+        match ui.fit_to_terminal(Rect::new(0, 0, frame_size.width, frame_size.height)) {
+            Ok(resized) => {
+                if resized {
+                    terminal_manager.terminal.clear().expect("Terminal failed to clear!");
+                }
+                return resized;
+            },
             Err(_e) => {
+                let required = ui.get_required_resolution();
                 terminal_manager.terminal.clear().expect("Terminal failed to clear!");
                 let error_paragraph = build_paragraph_multi(
                     vec![String::from(
-                        "Window too small."),
-                         format!("Minimum is {}x{}", MIN_RESOLUTION.width, MIN_RESOLUTION.height),
-                         String::from("Please resize and hit any key to check again.") ]);
+                        "Terminal too small."),
+                         format!("Needs {}x{}, currently {}x{}", required.width, required.height, frame_size.width, frame_size.height),
+                         String::from("Please resize the terminal to carry on.") ]);
                 terminal_manager.terminal.draw(|frame|{
                     frame.render_widget(error_paragraph, frame.area());
                 }).expect("Failed to draw the frame!");
-                io::stdin().keys().next().unwrap().unwrap();
-            },
-            _ => {
-                return;
+                // Nothing else can change the size, so there's no point checking again until the terminal's resized
+                while !matches!(key_input.get_input_event().expect("The next input event should have been captured"), InputEvent::Resize(_, _)) {}
             }
         }
     }
@@ -11,6 +11,7 @@ use ratatui::widgets::{Block, Borders, Tabs};
 
 use crate::character::Character;
 use crate::error::errors::ErrorWrapper;
+use crate::input::{InputEvent, KeyInputResolver};
 use crate::map::position::{Area, Position};
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::ui::UI;
@@ -25,7 +26,8 @@ use crate::view::framehandler::{container, FrameData, FrameHandler};
 use crate::view::model::usage_line::{UsageCommand, UsageLine};
 use crate::view::util::callback::Callback;
 use crate::view::InputHandler;
-use crate::view::{resolve_input, verify_display_size, GenericInputResult, InputResult, View};
+use crate::view::item_detail_view::ItemDetailView;
+use crate::view::{verify_display_size, GenericInputResult, InputResult, View};
 use crate::view::util::try_build_container_choice_frame_handler;
 use crate::widget::widgets::WidgetList;
 
@@ -57,6 +59,21 @@ impl Tab {
     }
 }
 
+/*
+ * Works out where each tab's drawn, padding and all, given the area the Tabs widget is drawn to
+ * The block title takes the first row, then each tab is padded by a space either side with a divider between
+ */
+fn build_tab_areas(tabs: &[Tab], tabs_area: Rect) -> Vec<(TabChoice, Area)> {
+    let mut tab_areas = Vec::new();
+    let mut x = tabs_area.x;
+    for tab in tabs {
+        let width = tab.title.len() as u16 + 2;
+        tab_areas.push((tab.tab_choice.clone(), Area::new(Position::new(x, tabs_area.y + 1), width, 1)));
+        x += width + 1;
+    }
+    tab_areas
+}
+
 struct CharacterInfoViewFrameData {
     pub character : Character
 }
@@ -69,6 +86,7 @@ pub struct CharacterInfoView<'a, B : ratatui::backend::Backend> {
     pub character : &'a mut Character,
     pub ui : &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub frame_handler: CharacterInfoFrameHandler,
     pub callback : Box<dyn FnMut(ContainerFrameHandlerInputResult) -> Option<ContainerFrameHandlerInputResult> + 'a>
 }
@@ -80,11 +98,15 @@ impl <B : ratatui::backend::Backend> CharacterInfoView<'_, B> {
             UsageCommand::new('d', String::from("drop")),
             UsageCommand::new('m', String::from("move")),
             UsageCommand::new('c', String::from("move-to-container")),
-            UsageCommand::new('e', String::from("equip"))
+            UsageCommand::new('e', String::from("equip")),
+            UsageCommand::new('u', String::from("use")),
+            UsageCommand::new('s', String::from("sort")),
+            UsageCommand::new('f', String::from("filter")),
+            UsageCommand::new('/', String::from("search"))
         ];
         let usage_line = UsageLine::new(commands);
 
-        let inventory_view = container::build_container_frame_handler(self.character.get_inventory_mut().clone(), usage_line);
+        let inventory_view = container::build_container_frame_handler(self.character.get_inventory_mut().clone(), usage_line, self.character.get_identification().clone(), self.ui.get_theme().clone());
         self.frame_handler.container_frame_handlers = vec!(inventory_view);
 
         let character_view = CharacterStatsFrameHandler { character: self.character.clone(), widgets: WidgetList { widgets: Vec::new(), widget_index: None }, view_mode: ViewMode::VIEW, attributes_area: Area::new(Position::zero(), 0, 0) };
@@ -100,6 +122,19 @@ impl <B : ratatui::backend::Backend> CharacterInfoView<'_, B> {
         Ok(())
     }
 
+    // Switches to the tab clicked, otherwise passes the click through to the inventory
+    fn handle_click(&mut self, position: Position) {
+        if let Some(tab_choice) = self.frame_handler.find_clicked_tab(position) {
+            self.frame_handler.tab_choice = tab_choice;
+            return;
+        }
+        if self.frame_handler.tab_choice == TabChoice::INVENTORY && self.frame_handler.choice_frame_handler.is_none() {
+            if let Some(topmost_view) = self.frame_handler.container_frame_handlers.last_mut() {
+                topmost_view.handle_click(position);
+            }
+        }
+    }
+
     fn next_tab(&mut self)  {
         let tab_iter = Tab::values().into_iter();
         if let Some(max_index) = tab_iter.size_hint().1 {
@@ -188,6 +223,12 @@ impl <B : ratatui::backend::Backend> CharacterInfoView<'_, B> {
                         ContainerFrameHandlerInputResult::MoveToContainerChoice(_) => {
                             self.trigger_callback(view_specific_result);
                         },
+                        ContainerFrameHandlerInputResult::InspectItem(_) => {
+                            self.trigger_callback(view_specific_result);
+                        },
+                        ContainerFrameHandlerInputResult::UseItem(_) => {
+                            self.trigger_callback(view_specific_result);
+                        },
                         _ => {}
                     }
                 }
@@ -250,6 +291,28 @@ impl <'c, B : ratatui::backend::Backend> Callback<'c, ContainerFrameHandlerInput
                         self.ui.set_console_buffer(error.message.unwrap())
                     }
                 },
+                ContainerFrameHandlerInputResult::InspectItem(data) => {
+                    // Looking at an item may have recognised it, so it's listed by what's now known
+                    for fh in &mut self.frame_handler.container_frame_handlers {
+                        fh.set_identification(data.identification.clone());
+                    }
+                    let mut detail_view = ItemDetailView::new(&mut *self.ui, &mut *self.terminal_manager, &mut *self.key_input, data);
+                    if let Err(e) = detail_view.begin() {
+                        log::error!("Failed to show item details: {}", e);
+                    }
+                },
+                ContainerFrameHandlerInputResult::UseItem(ref data) => {
+                    if let Some(message) = &data.message {
+                        self.ui.set_console_buffer(message.clone());
+                    }
+                    // Using an item can identify others, even those within other containers
+                    if let Some(identification) = &data.identification {
+                        for fh in &mut self.frame_handler.container_frame_handlers {
+                            fh.set_identification(identification.clone());
+                        }
+                    }
+                    self.pass_result_to_latest_choice_handler(r);
+                },
                 ContainerFrameHandlerInputResult::MoveItems(ref data) => {
                     if data.target_container.is_some() {
                         // if target_container is the root view container
@@ -309,11 +372,11 @@ impl <'b, B : ratatui::backend::Backend> View<bool> for CharacterInfoView<'_, B>
         let character = self.character.clone();
         let ui = &mut self.ui;
 
-        verify_display_size::<B>(self.terminal_manager);
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
 
         let ui_layout = ui.ui_layout.as_mut().unwrap();
         let frame_size = self.terminal_manager.terminal.get_frame().size();
-        let ui_areas: UIAreas = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit).clone();
+        let ui_areas: UIAreas = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit)?.clone();
 
         if let Some(main) = ui_areas.get_area(UI_AREA_NAME_MAIN) {
             let main_area = main.area;
@@ -334,14 +397,30 @@ impl <'b, B : ratatui::backend::Backend> View<bool> for CharacterInfoView<'_, B>
 
 impl <COM: ratatui::backend::Backend> InputHandler<bool> for CharacterInfoView<'_, COM> {
     fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<bool>, ErrorWrapper> {
-        let key = resolve_input(input)?;
+        let continue_result = InputResult { generic_input_result: GenericInputResult { done: false, requires_view_refresh: false }, view_specific_result: None };
+        let key = match input {
+            Some(key) => key,
+            None => match self.key_input.get_input_event()? {
+                InputEvent::Click(position) => {
+                    self.handle_click(position);
+                    return Ok(continue_result);
+                },
+                event => match event.as_paging_key() {
+                    Some(key) => key,
+                    // i.e a resize, which the redraw after this handles
+                    None => return Ok(continue_result)
+                }
+            }
+        };
+        // Everything is typed into the container view while it's asking for a quantity or search
+        let capturing_input = self.frame_handler.container_frame_handlers.last().is_some_and(|fh| fh.is_capturing_input());
         match key {
-            Key::Esc => {
+            Key::Esc if !capturing_input => {
                 let done = self.quit_container_view()?;
                 return Ok(InputResult { generic_input_result: GenericInputResult { done, requires_view_refresh: false }, view_specific_result: None});
             },
             // Horizontal tab
-            Key::Char('\t') => {
+            Key::Char('\t') if !capturing_input => {
                 self.next_tab();
             }
             // Passthrough anything not handled here into the sub views
@@ -409,6 +488,7 @@ impl FrameHandler<CharacterInfoViewFrameData> for CharacterInfoFrameHandler {
             3
         );
         frame.render_widget(tabs, heading_area.to_rect());
+        self.tab_areas = build_tab_areas(&Tab::values(), heading_area.to_rect());
 
         let ui_areas  = data.ui_areas;
         let mut character = data.data.character;
@@ -454,8 +534,15 @@ mod tests {
     use crate::terminal::terminal_manager;
     use crate::test::utils::test_utils::build_test_level;
     use crate::ui::ui::build_ui;
-    use crate::view::character_info_view::{CharacterInfoFrameHandler, CharacterInfoView, TabChoice};
+    use std::collections::HashMap;
+
+    use crate::map::position::{Area, Position};
+    use crate::ui::ui_areas::UIAreas;
+    use crate::view::character_info_view::{CharacterInfoFrameHandler, CharacterInfoView, CharacterInfoViewFrameData, Tab, TabChoice};
+    use crate::view::framehandler::{FrameData, FrameHandler};
     use crate::view::MIN_RESOLUTION;
+    use crate::input::MockKeyInputResolver;
+    use std::collections::VecDeque;
 
     #[test]
     fn test_initialise() {
@@ -464,11 +551,39 @@ mod tests {
 
         let mut ui = build_ui();
         let mut terminal_manager = terminal_manager::init_test(MIN_RESOLUTION).unwrap();
-        let frame_handler = CharacterInfoFrameHandler { tab_choice: TabChoice::INVENTORY, container_frame_handlers: Vec::new(), choice_frame_handler: None, character_view: None };
-        let mut character_info_view = CharacterInfoView { character: level.characters.get_player_mut().unwrap(), ui: &mut ui, terminal_manager: &mut terminal_manager, frame_handler, callback: Box::new(|_data| {None}) };
+        let mut key_input = MockKeyInputResolver { key_results: VecDeque::new() };
+        let frame_handler = CharacterInfoFrameHandler { tab_choice: TabChoice::INVENTORY, container_frame_handlers: Vec::new(), choice_frame_handler: None, character_view: None, tab_areas: Vec::new() };
+        let mut character_info_view = CharacterInfoView { character: level.characters.get_player_mut().unwrap(), ui: &mut ui, terminal_manager: &mut terminal_manager, key_input: &mut key_input, frame_handler, callback: Box::new(|_data| {None}) };
 
         // WHEN we call to initialise
         // THEN we expect it to complete successfully
         character_info_view.initialise();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_tab_areas() {
+        // GIVEN a character info frame handler
+        let level = build_test_level(None, None);
+        let character = level.characters.get_player().unwrap().clone();
+        let mut terminal_manager = terminal_manager::init_test(MIN_RESOLUTION).unwrap();
+        let mut frame_handler = CharacterInfoFrameHandler { tab_choice: TabChoice::INVENTORY, container_frame_handlers: Vec::new(), choice_frame_handler: None, character_view: None, tab_areas: Vec::new() };
+
+        // WHEN we draw it
+        terminal_manager.terminal.draw(|frame| {
+            let frame_area = Area::from_rect(frame.area());
+            frame_handler.handle_frame(frame, FrameData { data: CharacterInfoViewFrameData { character }, ui_areas: UIAreas::new(HashMap::new()), frame_area });
+        }).unwrap();
+
+        // THEN we expect each tab's title to be drawn just inside of the area recorded for it
+        let buffer = terminal_manager.terminal.backend().buffer().clone();
+        for (tab, (tab_choice, area)) in Tab::values().iter().zip(frame_handler.tab_areas.iter()) {
+            assert_eq!(tab.tab_choice, *tab_choice);
+            let first_letter = &tab.title[0..1];
+            assert_eq!(first_letter, buffer[(area.start_position.x + 1, area.start_position.y)].symbol());
+            // AND clicking anywhere on it finds that tab
+            assert_eq!(Some(tab.tab_choice.clone()), frame_handler.find_clicked_tab(Position::new(area.end_position.x, area.start_position.y)));
+        }
+        // AND clicking the title finds nothing
+        assert_eq!(None, frame_handler.find_clicked_tab(Position::new(1, 0)));
+    }
+}
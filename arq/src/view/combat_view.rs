@@ -4,10 +4,10 @@ use termion::event::Key;
 use ratatui::CompletedFrame;
 
 use crate::character::battle::Battle;
-use crate::character::equipment::WeaponSlot;
 use crate::engine::combat::CombatTurnChoice;
 use crate::engine::level::Level;
 use crate::error::errors::ErrorWrapper;
+use crate::input::KeyInputResolver;
 use crate::map::position::Area;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::ui::UI;
@@ -15,11 +15,12 @@ use crate::ui::ui_layout::LayoutType;
 use crate::view::framehandler::combat::CombatFrameHandler;
 use crate::view::framehandler::{FrameData, FrameHandler};
 use crate::view::util::callback::Callback;
-use crate::view::{resolve_input, verify_display_size, GenericInputResult, InputHandler, InputResult, View};
+use crate::view::{verify_display_size, GenericInputResult, InputHandler, InputResult, View};
 
 pub struct CombatView<'a, B : ratatui::backend::Backend>  {
     ui : &'a mut UI,
     terminal_manager : &'a mut TerminalManager<B>,
+    key_input : &'a mut dyn KeyInputResolver,
     level: Level,
     battle : Battle,
     frame_handler : CombatFrameHandler,
@@ -27,10 +28,10 @@ pub struct CombatView<'a, B : ratatui::backend::Backend>  {
 }
 
 impl  <B: ratatui::backend::Backend> CombatView<'_, B> {
-    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, level: Level, battle: Battle) -> CombatView<'a, B> {
-        let frame_handler = CombatFrameHandler::new(level.clone());
+    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver, level: Level, battle: Battle) -> CombatView<'a, B> {
+        let frame_handler = CombatFrameHandler::new(level.clone(), ui.build_cell_builder());
         let callback = Box::new(|_data| {None});
-        CombatView { ui, terminal_manager, level: level, battle, frame_handler, callback }
+        CombatView { ui, terminal_manager, key_input, level, battle, frame_handler, callback }
     }
 
     fn re_render(&mut self) -> Result<(), io::Error>  {
@@ -82,12 +83,12 @@ impl <B : ratatui::backend::Backend> View<Battle> for CombatView<'_, B>  {
         let ui = &mut self.ui;
         ui.show_console();
         self.terminal_manager.clear_screen().expect("Screen should have been cleared");
-        verify_display_size::<B>(self.terminal_manager);
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
         let fh = &mut self.frame_handler;
 
         let frame_area = Area::from_rect(self.terminal_manager.terminal.get_frame().size());
         let ui_layout = ui.ui_layout.as_mut().unwrap();
-        let ui_areas = ui_layout.get_or_build_areas(frame_area.to_rect(), LayoutType::CombatView);
+        let ui_areas = ui_layout.get_or_build_areas(frame_area.to_rect(), LayoutType::CombatView)?;
 
         // TODO get the view areas and pass them to the FrameHandler
         return Ok(self.terminal_manager.terminal.draw(|frame| {
@@ -100,7 +101,7 @@ impl <B : ratatui::backend::Backend> View<Battle> for CombatView<'_, B>  {
 
 impl <COM: ratatui::backend::Backend> InputHandler<bool> for CombatView<'_, COM> {
     fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<bool>, ErrorWrapper> {
-        let key = resolve_input(input)?;
+        let key = self.key_input.get_or_return_input_key(input)?;
         match key {
             Key::Up => {
                 let index = self.frame_handler.selection.index;
@@ -121,12 +122,10 @@ impl <COM: ratatui::backend::Backend> InputHandler<bool> for CombatView<'_, COM>
             // Enter key
             crate::global_flags::ENTER_KEY => {
                 let selection = &self.frame_handler.selection;
-                let _option_chosen = selection.options.get(selection.index as usize).unwrap();
-
-                let data = CombatCallbackData { choice: CombatTurnChoice::ATTACK(WeaponSlot::PRIMARY), result: None };
-                //self.trigger_callback(data);
-
-                // TODO send-recieve battle turn option
+                if let Some(option_chosen) = selection.options.get(selection.index as usize) {
+                    let data = CombatCallbackData { choice: option_chosen.mapped.clone(), result: None };
+                    self.trigger_callback(data);
+                }
                 return Ok(self.build_input_not_done_result());
             },
             Key::Esc => {
@@ -147,7 +146,9 @@ pub struct CombatCallbackData {
 
 #[derive(Clone)]
 pub struct CombatResult {
-    pub(crate) messages: Vec<String>
+    pub(crate) messages: Vec<String>,
+    // The state of the battle after the turn
+    pub(crate) battle: Battle
 }
 
 impl <'a, B : ratatui::backend::Backend> Callback <'a, CombatCallbackData> for CombatView<'a, B>  {
@@ -167,8 +168,9 @@ impl <'a, B : ratatui::backend::Backend> Callback <'a, CombatCallbackData> for C
         Any information about the result of a battle action callback will be handled here
      */
     fn handle_callback_result(&mut self, data: Option<CombatCallbackData>) {
-        if let Some(_data) = data.clone() {
-            // TODO pass messages into the framehandler
+        if let Some(result) = data.and_then(|d| d.result) {
+            self.battle = result.battle;
+            self.frame_handler.messages = result.messages;
         }
     }
 }
@@ -7,9 +7,10 @@ use ratatui::CompletedFrame;
 use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
+use crate::input::KeyInputResolver;
 use crate::map::position::{Area, Position};
 use crate::terminal::terminal_manager::TerminalManager;
-use crate::ui::ui::{get_input_key, UI};
+use crate::ui::ui::UI;
 use crate::ui::ui_util::{center_area, MIN_RESOLUTION};
 use crate::view::{GenericInputResult, InputResult, View};
 
@@ -17,18 +18,19 @@ pub struct DialogView<'a, B : ratatui::backend::Backend> {
     message: String,
     ui : &'a mut UI,
     terminal_manager : &'a mut TerminalManager<B>,
+    key_input : &'a mut dyn KeyInputResolver
 }
 
 impl <B : ratatui::backend::Backend> DialogView<'_, B> {
-    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, message: String) -> DialogView<'a, B> {
-        DialogView { ui, terminal_manager, message }
+    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver, message: String) -> DialogView<'a, B> {
+        DialogView { ui, terminal_manager, key_input, message }
     }
 }
 
 impl <'b, B : ratatui::backend::Backend> View<()> for DialogView<'_, B>  {
     fn begin(&mut self) -> Result<InputResult<()>, ErrorWrapper> {
         self.draw(None).expect("The dialog view should have been drawn.");
-        get_input_key().expect("Keyboard input key should have been captured");
+        self.key_input.get_input_key().expect("Keyboard input key should have been captured");
         Ok(InputResult {
             generic_input_result: GenericInputResult { done: false, requires_view_refresh: false },
             view_specific_result: None
@@ -1,6 +1,7 @@
 /*
     This is responsible for properly displaying the tabbed screen, and each individual tab / frame handler
  */
+use crate::map::position::{Area, Position};
 use crate::view::character_info_view::TabChoice;
 use crate::view::framehandler::character_stats::CharacterStatsFrameHandler;
 use crate::view::framehandler::container::ContainerFrameHandler;
@@ -10,5 +11,15 @@ pub struct CharacterInfoFrameHandler {
     pub tab_choice : TabChoice,
     pub container_frame_handlers: Vec<ContainerFrameHandler>,
     pub choice_frame_handler: Option<ContainerChoiceFrameHandler>,
-    pub character_view : Option<CharacterStatsFrameHandler>
+    pub character_view : Option<CharacterStatsFrameHandler>,
+    // Where each tab was last drawn, to work out which was clicked
+    pub tab_areas: Vec<(TabChoice, Area)>
+}
+
+impl CharacterInfoFrameHandler {
+    pub fn find_clicked_tab(&self, position: Position) -> Option<TabChoice> {
+        self.tab_areas.iter()
+            .find(|(_, area)| area.contains_position(position))
+            .map(|(tab_choice, _)| tab_choice.clone())
+    }
 }
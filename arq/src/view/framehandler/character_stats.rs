@@ -13,7 +13,7 @@ use crate::ui::resolution::Resolution;
 use crate::ui::ui_util::center_area;
 use crate::view::framehandler::character_stats::CharacterFrameHandlerInputResult::{NONE, VALIDATION};
 use crate::view::framehandler::{FrameData, FrameHandler};
-use crate::view::{resolve_input, GenericInputResult, InputHandler, InputResult};
+use crate::view::{expect_input, GenericInputResult, InputHandler, InputResult};
 use crate::widget::stateful::button_widget::build_button;
 use crate::widget::stateful::dropdown_widget::build_dropdown;
 use crate::widget::stateful::number_widget::{build_number_input, build_number_input_with_value, NumberInputState};
@@ -297,7 +297,7 @@ impl InputHandler<CharacterFrameHandlerInputResult> for CharacterStatsFrameHandl
             generic_input_result: GenericInputResult { done, requires_view_refresh: true },
             view_specific_result: None
         });
-        let key = resolve_input(input)?;
+        let key = expect_input(input)?;
         match key {
             Key::Esc => {
                 return ErrorWrapper::internal_result("Quit interrupt.".to_string());
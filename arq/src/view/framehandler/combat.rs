@@ -13,11 +13,15 @@ use crate::map::position::{build_rectangular_area, Area, Position};
 use crate::option_list_selection::{MappedOption, OptionListSelection};
 use crate::ui::ui_areas::{BorderedArea, UIAreas, UI_AREA_NAME_CONSOLE, UI_AREA_NAME_MAIN, UI_AREA_NAME_MINIMAP};
 use crate::view::framehandler::{FrameData, FrameHandler};
+use crate::view::util::cell_builder::CellBuilder;
 use crate::widget::stateful::map_widget::MapWidget;
 
 pub struct CombatFrameHandler {
     pub selection: OptionListSelection<CombatTurnChoice>,
-    pub level: Level
+    pub level: Level,
+    // What happened in the last turn of combat
+    pub messages: Vec<String>,
+    cell_builder: CellBuilder
 }
 
 pub struct ConsoleWidgets<'a> {
@@ -26,8 +30,8 @@ pub struct ConsoleWidgets<'a> {
 }
 
 impl CombatFrameHandler {
-    pub fn new(level: Level) -> CombatFrameHandler {
-        CombatFrameHandler { selection: OptionListSelection::new(), level }
+    pub fn new(level: Level, cell_builder: CellBuilder) -> CombatFrameHandler {
+        CombatFrameHandler { selection: OptionListSelection::new(), level, messages: Vec::new(), cell_builder }
     }
 
     fn build_options(&self, equipment: Equipment) -> Vec<MappedOption<CombatTurnChoice>> {
@@ -77,6 +81,13 @@ impl CombatFrameHandler {
             i += 1;
         }
 
+        // Show what happened last turn beside the options
+        let messages_x = console_area_bordered.inner.start_position.x + largest_option_length + 3;
+        let messages_width = (console_area_bordered.inner.start_position.x + console_area_bordered.inner.width).saturating_sub(messages_x);
+        let messages_area = Rect::new(messages_x, console_area_bordered.inner.start_position.y + 1, messages_width, console_area_bordered.inner.height.saturating_sub(1));
+        let messages = self.messages.iter().map(|m| Line::from(m.clone())).collect::<Vec<Line>>();
+        paragraphs.push((Paragraph::new(messages), messages_area));
+
         return  ConsoleWidgets { window: (console_window_block, console_area_bordered.outer.to_rect()), paragraphs };
     }
 }
@@ -188,7 +199,7 @@ impl FrameHandler<Battle> for CombatFrameHandler {
         let minimap_block = Block::default().borders(Borders::ALL);
         frame.render_widget(minimap_block, bordered_minimap_area.outer.to_rect());
 
-        let map_widget = MapWidget::new(map_view_areas);
+        let map_widget = MapWidget::new(map_view_areas, self.cell_builder.clone());
         let dummy_area = Area::new(Position::new(0,0),0,0);
         frame.render_stateful_widget(map_widget, dummy_area.to_rect(), &mut self.level);
     }
@@ -2,22 +2,26 @@ use std::collections::HashMap;
 use std::convert::TryInto;
 use std::io::Error;
 
+use crate::engine::container_util::build_removal;
 use crate::error::errors::ErrorWrapper;
 use termion::event::Key;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::{Block, Borders};
+use ratatui::widgets::{Block, Borders, Clear};
 
 use crate::item_list_selection::{ItemListSelection, ListSelection};
 use crate::map::objects::container::Container;
+use crate::map::objects::identification::Identification;
 use crate::map::objects::items::Item;
 use crate::map::position::Position;
+use crate::ui::theme::{Theme, ThemeColour};
 use crate::ui::ui_util::build_paragraph;
+use crate::view::framehandler::util::listing::ItemListing;
 use crate::view::framehandler::util::paging::{build_page_count, build_weight_limit};
 use crate::view::framehandler::util::tabling::{build_headings, Column};
 use crate::view::framehandler::{FrameData, FrameHandler};
 use crate::view::model::usage_line::UsageLine;
-use crate::view::{resolve_input, GenericInputResult, InputHandler, InputResult};
+use crate::view::{expect_input, GenericInputResult, InputHandler, InputResult};
 use crate::global_flags::ENTER_KEY;
 
 /*
@@ -30,7 +34,27 @@ pub struct ContainerFrameHandler {
     columns : Vec<Column>,
     row_count: i32,
     pub item_list_selection : ItemListSelection,
-    usage_line : UsageLine
+    usage_line : UsageLine,
+    quantity_prompt: Option<QuantityPrompt>,
+    // How many of the selected stack to use, as chosen in the quantity prompt
+    chosen_quantity: Option<u32>,
+    listing: ItemListing,
+    // What the player knows of magic items, to name them by
+    identification: Identification,
+    // Where the item rows were last drawn, to work out which was clicked
+    rows_area: Rect,
+    // For colouring items by their rarity
+    theme: Theme
+}
+
+/*
+    Asks how many of a stack to use before an action (the key pressed) is carried out
+ */
+#[derive(Clone)]
+struct QuantityPrompt {
+    action: char,
+    max: u32,
+    input: String
 }
 
 #[derive(Clone)]
@@ -58,6 +82,24 @@ pub struct MoveItemsData {
     pub position: Option<Position>
 }
 
+// An item to show in detail, alongside anything equipped in the slots it would use
+#[derive(Clone)]
+pub struct InspectItemData {
+    pub item: Item,
+    pub equipped: Vec<Item>,
+    pub identification: Identification
+}
+
+// A single item used up from a stack, once used the callback returns what happened
+#[derive(Clone)]
+pub struct UseItemData {
+    pub item: Item,
+    pub used: bool,
+    pub message: Option<String>,
+    // What the player knows after using it, as using an item can identify things
+    pub identification: Option<Identification>
+}
+
 #[derive(Clone)]
 pub enum ContainerFrameHandlerInputResult {
     None,
@@ -67,7 +109,9 @@ pub enum ContainerFrameHandlerInputResult {
     MoveItems(MoveItemsData),
     TakeItems(TakeItemsData),
     DropItems(Vec<Item>),
-    EquipItems(Vec<Item>)
+    EquipItems(Vec<Item>),
+    InspectItem(InspectItemData),
+    UseItem(UseItemData)
 }
 
 fn build_default_columns() -> Vec<Column> {
@@ -78,25 +122,33 @@ fn build_default_columns() -> Vec<Column> {
     ]
 }
 
-fn build_column_text(column: &Column, item: &Item) -> String {
+fn build_column_text(column: &Column, item: &Item, identification: &Identification) -> String {
     match column.name.as_str() {
         "NAME" => {
             if item.is_equipped() {
-                format!("{} ({})", item.get_name(), item.get_equipment_slot().unwrap())
+                format!("{} ({})", item.get_display_name(identification), item.get_equipment_slot().unwrap())
             } else {
-                item.get_name()
+                item.get_stack_name(identification)
             }
         },
         "WEIGHT (Kg)" => {
-            item.get_weight().to_string()
+            item.get_total_weight().to_string()
         },
         "VALUE" => {
-            item.get_value().to_string()
+            item.get_total_value().to_string()
         },
         _ => { "".to_string() }
     }
 }
 
+// Keeps the view open and redrawn while the quantity prompt is in use
+fn build_prompt_result() -> InputResult<ContainerFrameHandlerInputResult> {
+    InputResult {
+        generic_input_result: GenericInputResult { done: false, requires_view_refresh: true },
+        view_specific_result: Some(ContainerFrameHandlerInputResult::None)
+    }
+}
+
 /**
 * Builds a ContainerFrameHandler for opening/displaying a container
 * this can be used in many scenarios:
@@ -104,7 +156,7 @@ fn build_column_text(column: &Column, item: &Item) -> String {
 * 2. Opening a nested container selected within an existing ContainerFrameHandler display (see build_handler_for_focused_container)
 * 3. Displaying the Player's inventory (See CharacterInfoView)
 */
-pub fn build_container_frame_handler(container: Container, usage_line : UsageLine) -> ContainerFrameHandler {
+pub fn build_container_frame_handler(container: Container, usage_line : UsageLine, identification: Identification, theme: Theme) -> ContainerFrameHandler {
     let columns = build_default_columns();
     let items = container.to_cloned_item_list();
     ContainerFrameHandler {
@@ -112,12 +164,41 @@ pub fn build_container_frame_handler(container: Container, usage_line : UsageLin
         columns,
         row_count: 1,
         item_list_selection: ItemListSelection::new(items.clone(), 1),
-        usage_line
+        usage_line,
+        quantity_prompt: None,
+        chosen_quantity: None,
+        listing: ItemListing::new(),
+        identification,
+        rows_area: Rect::default(),
+        theme
     }
 }
 
 impl ContainerFrameHandler {
 
+    /*
+    * Focuses the item row clicked, or toggles selecting it if it's already focused
+    * Returns true if a row was clicked
+    */
+    pub fn handle_click(&mut self, position: Position) -> bool {
+        let rows_area = self.rows_area;
+        let in_rows = position.x >= rows_area.x && position.x < rows_area.right() && position.y >= rows_area.y && position.y < rows_area.bottom();
+        if !in_rows || self.is_capturing_input() || self.item_list_selection.get_items().is_empty() {
+            return false;
+        }
+
+        let row = (position.y - rows_area.y) as i32;
+        if row > self.item_list_selection.determine_max_selection_index() {
+            return false;
+        }
+        if self.item_list_selection.is_focused(self.item_list_selection.get_start_index() + row) {
+            self.toggle_select();
+        } else {
+            self.item_list_selection.update_selection(row);
+        }
+        true
+    }
+
     fn find_focused_container(&mut self) -> Option<Container> {
         let list_selection = &self.item_list_selection;
         if list_selection.is_selecting() {
@@ -149,18 +230,126 @@ impl ContainerFrameHandler {
 
     pub fn cancel_selection(&mut self) {
         self.item_list_selection.cancel_selection();
+        self.chosen_quantity = None;
     }
 
     pub fn rebuild_selection(&mut self) {
-        self.item_list_selection = ItemListSelection::new(self.container.to_cloned_item_list(), 1);
+        self.item_list_selection = ItemListSelection::new(self.listing.apply(self.container.to_cloned_item_list(), &self.identification), 1);
+        self.chosen_quantity = None;
     }
 
     pub fn rebuild_selection_from(&mut self, container: &Container) {
-        self.item_list_selection = ItemListSelection::new(container.to_cloned_item_list(), 1);
+        self.item_list_selection = ItemListSelection::new(self.listing.apply(container.to_cloned_item_list(), &self.identification), 1);
+        self.chosen_quantity = None;
+    }
+
+    // Re-lists the items after the sort, filter or search changes, keeping anything selected
+    fn refresh_listing(&mut self) {
+        let listed = self.listing.apply(self.container.to_cloned_item_list(), &self.identification);
+        self.item_list_selection.rebuild_keeping_selection(listed);
+    }
+
+    // Re-lists the items by what the player now knows of them, as searching and sorting go by the names shown
+    pub fn set_identification(&mut self, identification: Identification) {
+        self.identification = identification;
+        self.refresh_listing();
+    }
+
+    // Keys are typed into the quantity prompt or search while either is in use, rather than acted on
+    pub fn is_capturing_input(&self) -> bool {
+        self.is_prompting_quantity() || self.listing.searching
     }
 
+    fn handle_search_input(&mut self, key: Key) -> Result<InputResult<ContainerFrameHandlerInputResult>, ErrorWrapper> {
+        match key {
+            ENTER_KEY => {
+                self.listing.searching = false;
+            },
+            Key::Esc => {
+                self.listing.searching = false;
+                self.listing.search.clear();
+                self.refresh_listing();
+            },
+            Key::Backspace => {
+                self.listing.search.pop();
+                self.refresh_listing();
+            },
+            Key::Char(c) => {
+                self.listing.search.push(c);
+                self.refresh_listing();
+            },
+            _ => {}
+        }
+        Ok(build_prompt_result())
+    }
+
+    // The selected items, with any quantity chosen for a selected stack applied
     pub fn get_selected_items(&self) -> Vec<Item> {
-        Vec::from(self.item_list_selection.get_selected_items().clone())
+        let mut selected = Vec::from(self.item_list_selection.get_selected_items().clone());
+        if let (Some(quantity), [stack]) = (self.chosen_quantity, selected.as_mut_slice()) {
+            stack.set_quantity(quantity.min(stack.get_quantity()));
+        }
+        selected
+    }
+
+    pub fn is_prompting_quantity(&self) -> bool {
+        self.quantity_prompt.is_some()
+    }
+
+    /*
+    * When a single stack is selected, starts asking how many of it the given action should use
+    * Returns true if the prompt was started, otherwise the action can go ahead as it is
+    */
+    pub fn try_prompt_quantity(&mut self, action: char) -> bool {
+        self.chosen_quantity = None;
+        let selected = self.item_list_selection.get_selected_items();
+        if let (1, Some(stack)) = (selected.len(), selected.front()) {
+            if stack.get_quantity() > 1 {
+                self.quantity_prompt = Some(QuantityPrompt { action, max: stack.get_quantity(), input: String::new() });
+                return true;
+            }
+        }
+        false
+    }
+
+    fn handle_quantity_prompt_input(&mut self, key: Key) -> Result<InputResult<ContainerFrameHandlerInputResult>, ErrorWrapper> {
+        if let Some(prompt) = &mut self.quantity_prompt {
+            match key {
+                Key::Char(c) if c.is_ascii_digit() && prompt.input.len() < prompt.max.to_string().len() => {
+                    prompt.input.push(c);
+                },
+                Key::Backspace => {
+                    prompt.input.pop();
+                },
+                Key::Esc => {
+                    self.quantity_prompt = None;
+                },
+                ENTER_KEY => {
+                    // Nothing entered means the whole stack
+                    let quantity = prompt.input.parse::<u32>().unwrap_or(prompt.max).clamp(1, prompt.max);
+                    let action = prompt.action;
+                    self.quantity_prompt = None;
+                    self.chosen_quantity = Some(quantity);
+                    return self.build_action_result(action);
+                },
+                _ => {}
+            }
+        }
+        Ok(build_prompt_result())
+    }
+
+    // Carries out an action that's been waiting on a quantity
+    fn build_action_result(&self, action: char) -> Result<InputResult<ContainerFrameHandlerInputResult>, ErrorWrapper> {
+        let result = match action {
+            'c' => return self.build_move_items_result(),
+            'd' => ContainerFrameHandlerInputResult::DropItems(self.get_selected_items()),
+            't' => ContainerFrameHandlerInputResult::TakeItems(TakeItemsData { source: self.container.clone(), to_take: self.get_selected_items(), position: None }),
+            _ => ContainerFrameHandlerInputResult::None
+        };
+        Ok(InputResult {
+            generic_input_result: GenericInputResult { done: false, requires_view_refresh: true },
+            view_specific_result: Some(result)
+        })
     }
 
     fn find_container_for_item(&self, item: &Item) -> Option<&Container> {
@@ -195,6 +384,7 @@ impl ContainerFrameHandler {
 
     fn toggle_select(&mut self) {
         self.item_list_selection.toggle_select();
+        self.chosen_quantity = None;
     }
 
     fn move_selected(&mut self) -> Result<InputResult<ContainerFrameHandlerInputResult>, ErrorWrapper> {
@@ -239,7 +429,7 @@ impl ContainerFrameHandler {
             if let Some(focused_item) = self.item_list_selection.get_focused_item() {
                 if focused_item.is_container() {
                     if let Some(focused_container) = self.container.find_mut(focused_item) {
-                        return Some(build_container_frame_handler(focused_container.clone(), self.usage_line.clone()))
+                        return Some(build_container_frame_handler(focused_container.clone(), self.usage_line.clone(), self.identification.clone(), self.theme.clone()))
                     }
                 }
             }
@@ -252,7 +442,10 @@ impl ContainerFrameHandler {
         let selected_items = self.get_selected_items();
         for item in selected_items {
             if let Some(found) = self.container.find(&item) {
-                items.push(found.clone());
+                // Only the quantity chosen from a stack
+                let mut selected = found.clone();
+                selected.get_self_item_mut().set_quantity(item.get_quantity());
+                items.push(selected);
             }
         }
         items
@@ -303,6 +496,19 @@ impl ContainerFrameHandler {
             ContainerFrameHandlerInputResult::TakeItems(data) => {
                 self.retain_selected_items(data.to_take);
             },
+            ContainerFrameHandlerInputResult::UseItem(data) => {
+                if let Some(identification) = data.identification {
+                    self.set_identification(identification);
+                }
+                if !data.used {
+                    return;
+                }
+                if let Some(container_item) = self.container.find(&data.item) {
+                    let removal = build_removal(container_item, &data.item);
+                    self.container.remove_matching_items(vec![removal]);
+                    self.rebuild_selection();
+                }
+            },
             ContainerFrameHandlerInputResult::MoveItems(data) => {
                 // Moving into a container
                 if let Some(_) = data.target_container {
@@ -347,12 +553,25 @@ impl FrameHandler<&mut Container> for ContainerFrameHandler {
         let frame_size = data.get_frame_area().clone();
         let container = data.get_data_mut();
 
+        // Any sort, filter or search in use is shown alongside the container name
+        let listing_description = self.listing.describe();
+        let title = if listing_description.is_empty() {
+            container.get_self_item().get_name()
+        } else {
+            format!("{} ({})", container.get_self_item().get_name(), listing_description)
+        };
         let window_block = Block::default()
             .borders(Borders::ALL)
-            .title(container.get_self_item().get_name().clone());
+            .title(title);
         let window_area = Rect::new(frame_size.x.clone(), frame_size.y.clone(), frame_size.width.clone(), frame_size.height.clone());
-        let inventory_item_lines = window_area.height - 3;
+        // The usage line runs along the bottom border beside the weight and page count, with anything that doesn't fit on rows above it
+        let info_width = build_page_count(&self.item_list_selection, window_area).2 + build_weight_limit(&self.container, window_area, 0).1.width as usize + 2;
+        let usage_lines = self.usage_line.describe_lines(window_area.width.saturating_sub(2 + info_width as u16) as usize);
+        let extra_usage_lines = usage_lines.len() as u16 - 1;
+        let inventory_item_lines = window_area.height.saturating_sub(3 + extra_usage_lines);
         self.row_count = inventory_item_lines as i32;
+        let rows_width = self.columns.iter().map(|c| c.size as u16).sum::<u16>().min(window_area.width.saturating_sub(2));
+        self.rows_area = Rect::new(frame_size.x + 1, frame_size.y + 2, rows_width, inventory_item_lines);
         self.item_list_selection.page_line_count = inventory_item_lines as i32;
         frame.render_widget(window_block, window_area);
 
@@ -366,10 +585,11 @@ impl FrameHandler<&mut Container> for ContainerFrameHandler {
         let end_of_page_representive_index = self.item_list_selection.get_end_of_page_index();
 
         if !container.get_contents().is_empty() {
-            let view_contents = &container.get_contents()[start_index as usize..=end_of_page_representive_index as usize];
-            for c in view_contents {
+            // Only the items listed (after any sorting / filtering) are shown
+            let listed_items = self.item_list_selection.get_items().clone();
+            let view_items = if listed_items.is_empty() { &[] } else { &listed_items[start_index as usize..=end_of_page_representive_index as usize] };
+            for item in view_items {
                 let item_index = start_index.clone() + line_index.clone();
-                let item = &c.get_self_item();
                 // The x offset is the starting x 
                 // + 1 to avoid the left-hand border
                 let mut x_offset: u16 = frame_size.x.clone() + 1;
@@ -381,15 +601,18 @@ impl FrameHandler<&mut Container> for ContainerFrameHandler {
                 let current_index = self.item_list_selection.is_focused(item_index);
                 let selected = self.item_list_selection.is_selected(item_index);
                 
+                let rarity_style = Style::default().fg(self.theme.get_colour(ThemeColour::for_rarity(item.get_rarity())));
                 for column in &self.columns {
-                    let text = build_column_text(column, item);
+                    let text = build_column_text(column, item, &self.identification);
                     let mut column_text = build_paragraph(text);
                     if current_index.clone() && selected.clone() {
                         column_text = column_text.style(Style::default().fg(Color::Green).add_modifier(Modifier::REVERSED));
                     } else if current_index {
-                        column_text = column_text.style(Style::default().add_modifier(Modifier::REVERSED));
+                        column_text = column_text.style(rarity_style.add_modifier(Modifier::REVERSED));
                     } else if selected {
                         column_text = column_text.style(Style::default().fg(Color::Green));
+                    } else {
+                        column_text = column_text.style(rarity_style);
                     }
 
                     let column_length = column.size as i8;
@@ -401,10 +624,12 @@ impl FrameHandler<&mut Container> for ContainerFrameHandler {
             }
 
 
-            let usage_description = self.usage_line.describe();
-            let usage_text = build_paragraph(usage_description.clone());
-            let text_area = Rect::new( window_area.x.clone() + 1, window_area.y.clone() + window_area.height.clone() - 1, usage_description.len().try_into().unwrap(), 1);
-            frame.render_widget(usage_text.clone(), text_area);
+            for (usage_index, usage_description) in usage_lines.iter().enumerate() {
+                let usage_text = build_paragraph(usage_description.clone());
+                let y_offset = window_area.y + window_area.height - 1 - extra_usage_lines + usage_index as u16;
+                let text_area = Rect::new(window_area.x + 1, y_offset, usage_description.len().try_into().unwrap(), 1);
+                frame.render_widget(usage_text, text_area);
+            }
 
             // From right hand to left hand side draw the info text
             let page_count = build_page_count(&self.item_list_selection, window_area.clone());
@@ -412,6 +637,14 @@ impl FrameHandler<&mut Container> for ContainerFrameHandler {
             let page_count_text_length = page_count.2;
             let weight_limit = build_weight_limit(&self.container, window_area.clone(), page_count_text_length);
             frame.render_widget(weight_limit.0, weight_limit.1);
+
+            if let Some(prompt) = &self.quantity_prompt {
+                let prompt_text = format!("How many? (1-{}): {}", prompt.max, prompt.input);
+                let prompt_width = (prompt_text.len() as u16 + 2).min(window_area.width.saturating_sub(4));
+                let prompt_area = Rect::new(window_area.x + 2, window_area.y + window_area.height.saturating_sub(4), prompt_width, 3);
+                frame.render_widget(Clear, prompt_area);
+                frame.render_widget(build_paragraph(prompt_text).block(Block::default().borders(Borders::ALL).title("Quantity")), prompt_area);
+            }
         }
     }
 }
@@ -422,11 +655,20 @@ impl InputHandler<ContainerFrameHandlerInputResult> for ContainerFrameHandler {
             generic_input_result: GenericInputResult { done: true, requires_view_refresh: true },
             view_specific_result: Some(ContainerFrameHandlerInputResult::None)});
         loop {
-            let key = resolve_input(input)?;
+            let key = expect_input(input)?;
+            if self.is_prompting_quantity() {
+                return self.handle_quantity_prompt_input(key);
+            }
+            if self.listing.searching {
+                return self.handle_search_input(key);
+            }
             match key {
                 Key::Char('d') => {
                     log::info!("[container frame handler] new result for DropItems..");
                     if self.usage_line.commands.iter().find( |c| c.key == 'd').is_some() {
+                        if self.try_prompt_quantity('d') {
+                            return Ok(build_prompt_result());
+                        }
                         let selected_container_items = self.get_selected_items();
                         return Ok(InputResult {
                             generic_input_result: GenericInputResult { done: false, requires_view_refresh: true },
@@ -460,10 +702,31 @@ impl InputHandler<ContainerFrameHandlerInputResult> for ContainerFrameHandler {
                         });
                     }
                 },
+                Key::Char('u') if self.usage_line.commands.iter().any(|c| c.key == 'u') => {
+                    if let Some(mut item) = self.find_focused_item() {
+                        // Only one of a stack is used at a time
+                        item.set_quantity(1);
+                        return Ok(InputResult {
+                            generic_input_result: GenericInputResult { done: false, requires_view_refresh: true },
+                            view_specific_result: Some(ContainerFrameHandlerInputResult::UseItem(UseItemData { item, used: false, message: None, identification: None }))
+                        });
+                    }
+                },
+                Key::Char('i') => {
+                    if let Some(focused_item) = self.find_focused_item() {
+                        return Ok(InputResult {
+                            generic_input_result: GenericInputResult { done: false, requires_view_refresh: true },
+                            view_specific_result: Some(ContainerFrameHandlerInputResult::InspectItem(InspectItemData { item: focused_item, equipped: Vec::new(), identification: self.identification.clone() }))
+                        });
+                    }
+                },
                 Key::Char('m') => {
                     return self.move_selected();
                 },
                 Key::Char('c') => {
+                    if self.try_prompt_quantity('c') {
+                        return Ok(build_prompt_result());
+                    }
                     return self.build_move_items_result();
                 },
                 ENTER_KEY => {
@@ -474,6 +737,17 @@ impl InputHandler<ContainerFrameHandlerInputResult> for ContainerFrameHandler {
                     // Clear selected items
                     self.cancel_selection();
                 },
+                Key::Char('s') => {
+                    self.listing.sort = self.listing.sort.next();
+                    self.refresh_listing();
+                },
+                Key::Char('f') => {
+                    self.listing.filter = self.listing.filter.next();
+                    self.refresh_listing();
+                },
+                Key::Char('/') => {
+                    self.listing.searching = true;
+                },
                 Key::Char(_c) => {},
                 Key::Backspace => {},
                 Key::Up => {
@@ -506,7 +780,13 @@ pub fn build_testing_container_frame_handler<'a>(container: Container) -> Contai
         columns,
         row_count: 1,
         item_list_selection: ItemListSelection::new(items.clone(), 1),
-        usage_line : UsageLine::new(Vec::new())
+        usage_line : UsageLine::new(Vec::new()),
+        quantity_prompt: None,
+        chosen_quantity: None,
+        listing: ItemListing::new(),
+        identification: Identification::new(String::new()),
+        rows_area: Rect::default(),
+        theme: Theme::default_theme()
     }
 }
 
@@ -515,18 +795,25 @@ mod tests {
     use std::collections::HashMap;
 
     use ratatui::layout::Rect;
-    use ratatui::style::{Modifier, Style};
+    use ratatui::style::{Color, Modifier, Style};
+    use termion::event::Key;
     use uuid::Uuid;
 
+    use crate::engine::loot_table::Rarity;
+    use crate::global_flags::ENTER_KEY;
     use crate::item_list_selection::ListSelection;
-    use crate::map::position::Area;
+    use crate::map::objects::container::{Container, ContainerType};
+    use crate::map::objects::items::{Item, ItemForm, MaterialType};
+    use crate::map::position::{Area, Position};
     use crate::menu;
     use crate::terminal::terminal_manager::init_test;
     use crate::test::utils::test_resource_loader::read_expected_buffer_file;
     use crate::test::utils::test_utils::build_test_container;
+    use crate::ui::theme::{Theme, ThemeColour};
     use crate::ui::ui_areas::UIAreas;
-    use crate::view::framehandler::container::{build_testing_container_frame_handler, ContainerFrameHandler, ContainerFrameHandlerInputResult};
+    use crate::view::framehandler::container::{build_testing_container_frame_handler, ContainerFrameHandler, ContainerFrameHandlerInputResult, TakeItemsData};
     use crate::view::framehandler::{FrameData, FrameHandler};
+    use crate::view::InputHandler;
     use crate::view::MIN_RESOLUTION;
     
     #[test]
@@ -577,6 +864,8 @@ mod tests {
         let total_line_width = columns[0].size + columns[1].size + columns[2].size;
         assert_eq!(54, total_line_width);
         
+        // Ensure the items are coloured by their rarity, all common here
+        expected.set_style(Rect::new(1, 2, total_line_width as u16, 4), Style::default().fg(Color::White));
         // Ensure the entire row is reverse highlighted
         expected.set_style(Rect::new(1, 2, total_line_width as u16, 1), Style::default().add_modifier(Modifier::REVERSED));
 
@@ -590,6 +879,60 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_items_coloured_by_rarity() {
+        // GIVEN a container with a rare item after the focused one, then an uncommon one
+        let mut container = build_test_container();
+        container.get_contents_mut()[1].get_self_item_mut().set_rarity(Rarity::Rare);
+        container.get_contents_mut()[2].get_self_item_mut().set_rarity(Rarity::Uncommon);
+        let mut handler_container = container.clone();
+        let mut frame_handler: ContainerFrameHandler = build_testing_container_frame_handler(container);
+        let mut terminal_manager = init_test(MIN_RESOLUTION).unwrap();
+
+        // WHEN we call to draw it
+        terminal_manager.terminal.draw(|frame| {
+            let frame_area = Area::from_rect(frame.area());
+            frame_handler.handle_frame(frame, FrameData { data: &mut handler_container, ui_areas: UIAreas::new(HashMap::new()), frame_area });
+        }).expect("Test Terminal should draw the frame successfully");
+
+        // THEN each item row is coloured for it's rarity in the theme
+        let theme = Theme::default_theme();
+        let buffer = terminal_manager.terminal.backend().buffer();
+        assert_eq!(theme.get_colour(ThemeColour::RareItem), buffer[(1, 3)].fg);
+        assert_eq!(theme.get_colour(ThemeColour::UncommonItem), buffer[(1, 4)].fg);
+        assert_eq!(theme.get_colour(ThemeColour::CommonItem), buffer[(1, 5)].fg);
+        // AND the focused row is still highlighted
+        assert!(buffer[(1, 2)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_handle_click() {
+        // GIVEN a ContainerFrameHandler drawn across an 80x24 terminal
+        let container = build_test_container();
+        let mut handler_container = container.clone();
+        let mut frame_handler: ContainerFrameHandler = build_testing_container_frame_handler(container);
+        let mut terminal_manager = init_test(MIN_RESOLUTION).unwrap();
+        terminal_manager.terminal.draw(|frame| {
+            let frame_area = Area::from_rect(frame.area());
+            frame_handler.handle_frame(frame, FrameData { data: &mut handler_container, ui_areas: UIAreas::new(HashMap::new()), frame_area });
+        }).expect("Test Terminal should draw the frame successfully");
+
+        // WHEN we click the second item row (below the border and headings)
+        // THEN we expect it to be focused
+        assert!(frame_handler.handle_click(Position::new(5, 3)));
+        assert_eq!(1, frame_handler.item_list_selection.get_true_index());
+        assert!(frame_handler.item_list_selection.get_selected_items().is_empty());
+
+        // AND clicking it again selects it
+        assert!(frame_handler.handle_click(Position::new(5, 3)));
+        assert_eq!(1, frame_handler.item_list_selection.get_selected_items().len());
+
+        // AND clicking the headings or past the last item does nothing
+        assert!(!frame_handler.handle_click(Position::new(5, 1)));
+        assert!(!frame_handler.handle_click(Position::new(5, 20)));
+        assert_eq!(1, frame_handler.item_list_selection.get_true_index());
+    }
+
     #[test]
     fn test_move_focus_down() {
         // GIVEN a valid view
@@ -719,4 +1062,85 @@ mod tests {
         assert_eq!(1, contents.len());
         assert_eq!("Test Item 2", contents[0].get_self_item().get_name());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_quantity_prompt() {
+        // GIVEN a view of a container holding a stack of 10 gold coins and a silver bar
+        let mut container = Container::new(Uuid::new_v4(), "Test Container".to_owned(), 'X', 0.0, 1, ContainerType::OBJECT, 100);
+        let mut coins = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::COIN, 'X', 0.01, 1);
+        coins.set_quantity(10);
+        container.add_item(coins).unwrap();
+        container.add_item(Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::SILVER, ItemForm::BAR, 'X', 1.0, 50)).unwrap();
+        let mut view: ContainerFrameHandler = build_testing_container_frame_handler(container);
+
+        // AND we've selected the stack
+        view.handle_input(Some(ENTER_KEY)).unwrap();
+
+        // WHEN we choose to move it into another container
+        view.handle_input(Some(Key::Char('c'))).unwrap();
+        // THEN we're asked how many to move
+        assert!(view.is_prompting_quantity());
+
+        // AND when we ask for 3 of them
+        view.handle_input(Some(Key::Char('3'))).unwrap();
+        let result = view.handle_input(Some(ENTER_KEY)).unwrap();
+        assert!(!view.is_prompting_quantity());
+
+        // THEN only 3 are moved
+        match result.view_specific_result {
+            Some(ContainerFrameHandlerInputResult::MoveToContainerChoice(data)) => {
+                assert_eq!(1, data.to_move.len());
+                assert_eq!(3, data.to_move[0].get_quantity());
+            },
+            _ => panic!("Expected a MoveToContainerChoice result")
+        }
+
+        // AND once they've been taken, the rest of the stack is left behind
+        let data = TakeItemsData { source: view.container.clone(), to_take: Vec::new(), position: None };
+        view.handle_callback_result(ContainerFrameHandlerInputResult::TakeItems(data));
+        assert_eq!(2, view.container.get_contents().len());
+        assert_eq!(7, view.container.get_contents()[0].get_self_item().get_quantity());
+    }
+
+    #[test]
+    fn test_sort_filter_and_search() {
+        // GIVEN a view of a container holding a silver bar, a gold bar and a bag
+        let mut container = Container::new(Uuid::new_v4(), "Test Container".to_owned(), 'X', 0.0, 1, ContainerType::OBJECT, 100);
+        container.add_item(Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::SILVER, ItemForm::BAR, 'X', 1.0, 50)).unwrap();
+        container.add_item(Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::BAR, 'X', 1.0, 100)).unwrap();
+        container.add(Container::new(Uuid::new_v4(), "Bag".to_owned(), 'X', 1.0, 5, ContainerType::OBJECT, 10)).unwrap();
+        let mut view: ContainerFrameHandler = build_testing_container_frame_handler(container);
+        let listed_names = |view: &ContainerFrameHandler| view.item_list_selection.get_items().iter().map(|i| i.get_name()).collect::<Vec<String>>();
+
+        // AND we've selected the silver bar
+        view.handle_input(Some(ENTER_KEY)).unwrap();
+        view.handle_input(Some(ENTER_KEY)).unwrap();
+
+        // WHEN we sort by name
+        view.handle_input(Some(Key::Char('s'))).unwrap();
+        // THEN the items are listed alphabetically
+        assert_eq!(vec!["Bag", "Gold Bar", "Silver Bar"], listed_names(&view));
+        // AND the silver bar is still selected
+        assert_eq!(vec![String::from("Silver Bar")], view.get_selected_items().iter().map(|i| i.get_name()).collect::<Vec<String>>());
+
+        // AND when we search for "bar"
+        for key in ['/', 'b', 'a', 'r', '\n'] {
+            view.handle_input(Some(Key::Char(key))).unwrap();
+        }
+        // THEN only the bars are listed, keeping the selection
+        assert!(!view.is_capturing_input());
+        assert_eq!(vec!["Gold Bar", "Silver Bar"], listed_names(&view));
+        assert!(view.item_list_selection.is_selected(1));
+
+        // AND when we filter to containers, nothing is left matching the search
+        for _i in 0..3 {
+            view.handle_input(Some(Key::Char('f'))).unwrap();
+        }
+        assert!(listed_names(&view).is_empty());
+
+        // AND clearing the search lists the bag
+        view.handle_input(Some(Key::Char('/'))).unwrap();
+        view.handle_input(Some(Key::Esc)).unwrap();
+        assert_eq!(vec!["Bag"], listed_names(&view));
+    }
+}
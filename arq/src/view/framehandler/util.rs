@@ -1,2 +1,3 @@
+pub mod listing;
 pub mod paging;
 pub mod tabling;
\ No newline at end of file
@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+
+use crate::map::objects::identification::Identification;
+use crate::map::objects::items::{Item, ItemCategory};
+
+/*
+ * How the items in a container view are ordered, cycled through by key
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemSort {
+    Unsorted, // The order they're held in
+    Name,
+    Weight,
+    Value,
+    ValuePerWeight,
+    Type
+}
+
+impl ItemSort {
+    pub fn next(self) -> ItemSort {
+        match self {
+            ItemSort::Unsorted => ItemSort::Name,
+            ItemSort::Name => ItemSort::Weight,
+            ItemSort::Weight => ItemSort::Value,
+            ItemSort::Value => ItemSort::ValuePerWeight,
+            ItemSort::ValuePerWeight => ItemSort::Type,
+            ItemSort::Type => ItemSort::Unsorted
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            ItemSort::Unsorted => "none",
+            ItemSort::Name => "name",
+            ItemSort::Weight => "weight",
+            ItemSort::Value => "value",
+            ItemSort::ValuePerWeight => "value/weight",
+            ItemSort::Type => "type"
+        }
+    }
+
+    // Names and types read A-Z, anything numeric is largest first
+    fn compare(self, a: &Item, b: &Item, identification: &Identification) -> Ordering {
+        match self {
+            ItemSort::Unsorted => Ordering::Equal,
+            ItemSort::Name => compare_names(a, b, identification),
+            ItemSort::Weight => b.get_total_weight().total_cmp(&a.get_total_weight()),
+            ItemSort::Value => b.get_total_value().cmp(&a.get_total_value()),
+            ItemSort::ValuePerWeight => value_per_weight(b).total_cmp(&value_per_weight(a)),
+            ItemSort::Type => type_rank(a).cmp(&type_rank(b)).then_with(|| compare_names(a, b, identification))
+        }
+    }
+}
+
+// By the names shown, so nothing unidentified is given away by where it's sorted to
+fn compare_names(a: &Item, b: &Item, identification: &Identification) -> Ordering {
+    a.get_display_name(identification).to_lowercase().cmp(&b.get_display_name(identification).to_lowercase())
+}
+
+// Weightless items are treated as the most valuable for their weight
+fn value_per_weight(item: &Item) -> f32 {
+    if item.weight > 0.0 {
+        item.value as f32 / item.weight
+    } else {
+        f32::MAX
+    }
+}
+
+fn type_rank(item: &Item) -> u8 {
+    if item.is_container() {
+        return 0;
+    }
+    match item.get_category() {
+        ItemCategory::Weapons => 1,
+        ItemCategory::Armour => 2,
+        ItemCategory::Coins => 3,
+        ItemCategory::Valuables => 4,
+        ItemCategory::Other => 5
+    }
+}
+
+/*
+ * Narrows the items in a container view down to one type, cycled through by key
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemFilter {
+    All,
+    Weapons,
+    Armour,
+    Containers,
+    Valuables
+}
+
+impl ItemFilter {
+    pub fn next(self) -> ItemFilter {
+        match self {
+            ItemFilter::All => ItemFilter::Weapons,
+            ItemFilter::Weapons => ItemFilter::Armour,
+            ItemFilter::Armour => ItemFilter::Containers,
+            ItemFilter::Containers => ItemFilter::Valuables,
+            ItemFilter::Valuables => ItemFilter::All
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            ItemFilter::All => "all",
+            ItemFilter::Weapons => "weapons",
+            ItemFilter::Armour => "armour",
+            ItemFilter::Containers => "containers",
+            ItemFilter::Valuables => "valuables"
+        }
+    }
+
+    pub fn matches(self, item: &Item) -> bool {
+        match self {
+            ItemFilter::All => true,
+            ItemFilter::Weapons => item.get_category() == ItemCategory::Weapons,
+            ItemFilter::Armour => item.get_category() == ItemCategory::Armour,
+            ItemFilter::Containers => item.is_container(),
+            ItemFilter::Valuables => matches!(item.get_category(), ItemCategory::Coins | ItemCategory::Valuables)
+        }
+    }
+}
+
+/*
+ * The sort, filter and search text applied to the items listed in a container view
+ */
+#[derive(Clone, Debug)]
+pub struct ItemListing {
+    pub sort: ItemSort,
+    pub filter: ItemFilter,
+    pub search: String,
+    // Whether the search is still being typed
+    pub searching: bool
+}
+
+impl ItemListing {
+    pub fn new() -> ItemListing {
+        ItemListing { sort: ItemSort::Unsorted, filter: ItemFilter::All, search: String::new(), searching: false }
+    }
+
+    // The items to list, in order
+    pub fn apply(&self, items: Vec<Item>, identification: &Identification) -> Vec<Item> {
+        let search = self.search.to_lowercase();
+        let mut listed: Vec<Item> = items.into_iter()
+            .filter(|i| self.filter.matches(i))
+            .filter(|i| search.is_empty() || i.get_display_name(identification).to_lowercase().contains(&search))
+            .collect();
+        // A stable sort, so equal items keep the order they're held in
+        listed.sort_by(|a, b| self.sort.compare(a, b, identification));
+        listed
+    }
+
+    // i.e "sort: value, filter: weapons, search: gold", or empty when showing everything as-is
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.sort != ItemSort::Unsorted {
+            parts.push(format!("sort: {}", self.sort.describe()));
+        }
+        if self.filter != ItemFilter::All {
+            parts.push(format!("filter: {}", self.filter.describe()));
+        }
+        if self.searching {
+            parts.push(format!("search: {}_", self.search));
+        } else if !self.search.is_empty() {
+            parts.push(format!("search: {}", self.search));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::map::objects::identification::Identification;
+    use crate::map::objects::items::{Item, ItemForm, MaterialType, Weapon};
+    use crate::map::objects::weapon_builder::BladedWeaponType;
+    use crate::view::framehandler::util::listing::{ItemFilter, ItemListing, ItemSort};
+
+    fn build_items() -> Vec<Item> {
+        let sword = Item::weapon(Uuid::new_v4(), "".to_owned(), ItemForm::BLADED(BladedWeaponType::ARMING), MaterialType::STEEL, 'X', 4.0, 40, Weapon { damage: 10 });
+        let gold_bar = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::GOLD, ItemForm::BAR, 'X', 2.0, 100);
+        let bag = Item::container_item(Uuid::new_v4(), "Bag".to_owned(), 'X', 1.0, 5);
+        let mut coins = Item::new_with_form(Uuid::new_v4(), "".to_owned(), MaterialType::SILVER, ItemForm::COIN, 'X', 0.5, 1);
+        coins.set_quantity(10);
+        vec![sword, gold_bar, bag, coins]
+    }
+
+    fn names(items: Vec<Item>) -> Vec<String> {
+        items.iter().map(|i| i.get_name()).collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        // GIVEN a sword, a gold bar, a bag and a stack of 10 silver coins
+        let items = build_items();
+        let identification = Identification::new(String::new());
+        let mut listing = ItemListing::new();
+
+        // WHEN we cycle through each sort
+        // THEN we expect the items to be ordered by it
+        assert_eq!(vec!["Steel Sword", "Gold Bar", "Bag", "Silver Coin"], names(listing.apply(items.clone(), &identification)));
+        listing.sort = listing.sort.next();
+        assert_eq!(ItemSort::Name, listing.sort);
+        assert_eq!(vec!["Bag", "Gold Bar", "Silver Coin", "Steel Sword"], names(listing.apply(items.clone(), &identification)));
+        listing.sort = listing.sort.next();
+        // The whole stack of coins weighs the most
+        assert_eq!(vec!["Silver Coin", "Steel Sword", "Gold Bar", "Bag"], names(listing.apply(items.clone(), &identification)));
+        listing.sort = listing.sort.next();
+        assert_eq!(vec!["Gold Bar", "Steel Sword", "Silver Coin", "Bag"], names(listing.apply(items.clone(), &identification)));
+        listing.sort = listing.sort.next();
+        assert_eq!(vec!["Gold Bar", "Steel Sword", "Bag", "Silver Coin"], names(listing.apply(items.clone(), &identification)));
+        listing.sort = listing.sort.next();
+        assert_eq!(vec!["Bag", "Steel Sword", "Silver Coin", "Gold Bar"], names(listing.apply(items.clone(), &identification)));
+        assert_eq!(ItemSort::Unsorted, listing.sort.next());
+    }
+
+    #[test]
+    fn test_filter_and_search() {
+        // GIVEN a sword, a gold bar, a bag and a stack of 10 silver coins
+        let items = build_items();
+        let identification = Identification::new(String::new());
+        let mut listing = ItemListing::new();
+
+        // WHEN we filter to valuables
+        listing.filter = ItemFilter::Valuables;
+        // THEN only the bar and coins are listed
+        assert_eq!(vec!["Gold Bar", "Silver Coin"], names(listing.apply(items.clone(), &identification)));
+
+        // AND when we also search for "coin", only the coins are
+        listing.search = String::from("COIN");
+        assert_eq!(vec!["Silver Coin"], names(listing.apply(items.clone(), &identification)));
+        assert_eq!("filter: valuables, search: COIN", listing.describe());
+
+        // AND containers are only the bag
+        listing.filter = ItemFilter::Containers;
+        listing.search.clear();
+        assert_eq!(vec!["Bag"], names(listing.apply(items, &identification)));
+    }
+}
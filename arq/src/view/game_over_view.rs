@@ -7,11 +7,12 @@ use ratatui::style::Style;
 use ratatui::CompletedFrame;
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
+use crate::input::KeyInputResolver;
 use crate::map::position::Area;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::ui::UI;
 use crate::view::game_over_view::GameOverChoice::{EXIT, RESTART};
-use crate::view::{resolve_input, GenericInputResult, InputHandler, InputResult, View};
+use crate::view::{GenericInputResult, InputHandler, InputResult, View};
 use crate::widget::stateful::button_widget::build_button;
 use crate::widget::widgets::WidgetList;
 use crate::widget::{Focusable, StatefulWidgetType};
@@ -23,6 +24,7 @@ pub struct GameOver<'a, B : ratatui::backend::Backend> {
     pub message : String,
     pub ui : &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub widgets: WidgetList
 }
 
@@ -31,8 +33,8 @@ pub enum GameOverChoice {
     EXIT
 }
 
-pub fn build_game_over_menu<'a, B : ratatui::backend::Backend>(message: String, ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>) -> GameOver<'a, B> {
-    GameOver { message, ui, terminal_manager, widgets:
+pub fn build_game_over_menu<'a, B : ratatui::backend::Backend>(message: String, ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver) -> GameOver<'a, B> {
+    GameOver { message, ui, terminal_manager, key_input, widgets:
     WidgetList { widget_index: Some(0), widgets :
         vec![ build_button(7, String::from("Restart")),
               build_button(7, String::from("Exit"))
@@ -105,7 +107,7 @@ impl <B : ratatui::backend::Backend> InputHandler<GameOverChoice> for GameOver<'
             None => {}
         }
 
-        let key = resolve_input(input)?;
+        let key = self.key_input.get_or_return_input_key(input)?;
         match key {
             Key::Down => {
                 self.widgets.next_widget();
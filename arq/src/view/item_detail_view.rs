@@ -0,0 +1,218 @@
+use termion::event::Key;
+use ratatui::layout::Rect;
+use ratatui::widgets::{Block, Borders, Clear};
+use ratatui::CompletedFrame;
+
+use crate::character::equipment::get_potential_slots;
+use crate::error::errors::ErrorWrapper;
+use crate::map::objects::identification::{needs_identifying, Identification};
+use crate::map::objects::items::{Item, ItemForm, ItemType, MaterialType};
+use crate::input::KeyInputResolver;
+use crate::map::position::Area;
+use crate::terminal::terminal_manager::TerminalManager;
+use crate::ui::ui::UI;
+use crate::ui::ui_areas::UI_AREA_NAME_MAIN;
+use crate::ui::ui_layout::LayoutType;
+use crate::ui::ui_util::build_paragraph_multi;
+use crate::view::framehandler::container::InspectItemData;
+use crate::view::{verify_display_size, GenericInputResult, InputHandler, InputResult, View};
+
+const USAGE: &str = "[any key] Close";
+const PANEL_WIDTH: u16 = 52;
+
+/*
+    A panel showing everything known about a single item
+    Including how it compares to anything equipped in the slots it would use
+ */
+pub struct ItemDetailView<'a, B : ratatui::backend::Backend> {
+    ui : &'a mut UI,
+    terminal_manager : &'a mut TerminalManager<B>,
+    key_input : &'a mut dyn KeyInputResolver,
+    data: InspectItemData
+}
+
+impl <B : ratatui::backend::Backend> ItemDetailView<'_, B> {
+    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver, data: InspectItemData) -> ItemDetailView<'a, B> {
+        ItemDetailView { ui, terminal_manager, key_input, data }
+    }
+}
+
+fn describe_type(item: &Item) -> &'static str {
+    match item.item_type {
+        ItemType::WEAPON(_) => "a weapon",
+        ItemType::HEADGEAR => "a piece of headgear",
+        ItemType::TORSO => "a piece of body armour",
+        ItemType::LEGS => "a piece of leg armour",
+        ItemType::CONTAINER => "a container",
+        _ if needs_identifying(&item.get_item_form()) => "a magic item",
+        _ => "an item"
+    }
+}
+
+// The more specific kind of item where we know it, i.e "Dagger" rather than "Sword"
+fn describe_form(item: &Item) -> String {
+    match item.get_item_form() {
+        ItemForm::BLADED(weapon_type) => weapon_type.name(),
+        form => form.name()
+    }
+}
+
+fn format_difference(difference: i32) -> String {
+    if difference > 0 {
+        format!("+{}", difference)
+    } else {
+        difference.to_string()
+    }
+}
+
+// The lines of detail shown for an item, compared against anything equipped
+pub fn describe_item(item: &Item, equipped: &[Item], identification: &Identification) -> Vec<String> {
+    let material = item.get_material_type().name();
+    let summary = if !item.is_identified(identification) {
+        format!("{}, {} yet to be identified.", item.get_display_name(identification), describe_type(item))
+    } else if item.get_material_type() == MaterialType::UNKNOWN {
+        format!("{}, {}.", describe_form(item), describe_type(item))
+    } else {
+        format!("{}, {} made of {}.", describe_form(item), describe_type(item), material.to_lowercase())
+    };
+    let mut lines = vec![
+        summary,
+        format!("Material: {}", material)
+    ];
+    if item.get_quantity() > 1 {
+        lines.push(format!("Quantity: {}", item.get_quantity()));
+    }
+    lines.push(format!("Weight: {} Kg", item.get_total_weight()));
+    lines.push(format!("Value: {}", item.get_total_value()));
+    if item.get_weight() > 0.0 {
+        lines.push(format!("Value per Kg: {:.1}", item.get_value() as f32 / item.get_weight()));
+    }
+    if let Some(damage) = item.get_weapon_damage() {
+        lines.push(format!("Damage: {}", damage));
+    }
+    if let Some(handedness) = item.get_handedness() {
+        lines.push(format!("Handedness: {}", handedness.name()));
+    }
+    if let Some(rating) = item.get_armour_rating() {
+        lines.push(format!("Armour: {}", rating));
+    }
+    if let Some(slot) = item.get_equipment_slot() {
+        lines.push(format!("Equipped: {}", slot));
+    }
+
+    let potential_slots = get_potential_slots(item.item_type.clone());
+    if !potential_slots.is_empty() && !item.is_equipped() {
+        if equipped.is_empty() {
+            lines.push(String::from("Nothing equipped to compare against."));
+        }
+        for other in equipped {
+            let slot = other.get_equipment_slot().map_or_else(String::new, |s| s.to_string());
+            if let (Some(damage), Some(other_damage)) = (item.get_weapon_damage(), other.get_weapon_damage()) {
+                lines.push(format!("vs {} ({}): damage {}", other.get_name(), slot, format_difference(damage - other_damage)));
+            } else if let (Some(rating), Some(other_rating)) = (item.get_armour_rating(), other.get_armour_rating()) {
+                lines.push(format!("vs {} ({}): armour {}", other.get_name(), slot, format_difference(rating - other_rating)));
+            }
+        }
+    }
+    lines
+}
+
+impl <B : ratatui::backend::Backend> View<()> for ItemDetailView<'_, B> {
+    fn begin(&mut self) -> Result<InputResult<()>, ErrorWrapper> {
+        self.draw(None)?;
+        let mut input_result = self.handle_input(None)?;
+        while !input_result.generic_input_result.done {
+            self.draw(None)?;
+            input_result = self.handle_input(None)?;
+        }
+        self.terminal_manager.terminal.clear()?;
+        Ok(input_result)
+    }
+
+    fn draw(&mut self, _area: Option<Area>) -> Result<CompletedFrame<'_>, ErrorWrapper> {
+        let ui = &mut self.ui;
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
+
+        let title = self.data.item.get_stack_name(&self.data.identification);
+        let mut lines = describe_item(&self.data.item, &self.data.equipped, &self.data.identification);
+        lines.push(String::new());
+        lines.push(String::from(USAGE));
+
+        let frame_size = self.terminal_manager.terminal.get_frame().area();
+        let ui_layout = ui.ui_layout.as_mut().ok_or(ErrorWrapper::new_internal(String::from("Failed to get ui_layout, has it been initialised?")))?;
+        let main_area = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit)?.get_area(UI_AREA_NAME_MAIN)
+            .map(|a| a.area.to_rect())
+            .unwrap_or(frame_size);
+
+        Ok(self.terminal_manager.terminal.draw(|frame| {
+            ui.render(None, frame);
+
+            // Centered within the main window, only as tall as it needs to be
+            let width = PANEL_WIDTH.min(main_area.width.saturating_sub(2));
+            let height = (lines.len() as u16 + 2).min(main_area.height.saturating_sub(2));
+            let area = Rect::new(main_area.x + (main_area.width - width) / 2, main_area.y + (main_area.height - height) / 2, width, height);
+            frame.render_widget(Clear, area);
+            frame.render_widget(Block::default().borders(Borders::ALL).title(title), area);
+
+            let text_area = Rect::new(area.x + 1, area.y + 1, area.width.saturating_sub(2), area.height.saturating_sub(2));
+            frame.render_widget(build_paragraph_multi(lines), text_area);
+        })?)
+    }
+}
+
+impl <B : ratatui::backend::Backend> InputHandler<()> for ItemDetailView<'_, B> {
+    fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<()>, ErrorWrapper> {
+        // Any key closes the panel
+        let _key = self.key_input.get_or_return_input_key(input)?;
+        Ok(InputResult { generic_input_result: GenericInputResult { done: true, requires_view_refresh: true }, view_specific_result: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::character::equipment::EquipmentSlot;
+    use crate::map::objects::identification::{Identification, PotionType};
+    use crate::map::objects::items::{Item, ItemForm, MaterialType, Weapon};
+    use crate::map::objects::weapon_builder::BladedWeaponType;
+    use crate::view::item_detail_view::describe_item;
+
+    fn build_sword(form: BladedWeaponType, material: MaterialType, damage: i32) -> Item {
+        Item::weapon(Uuid::new_v4(), String::new(), ItemForm::BLADED(form), material, '|', 2.0, 50, Weapon { damage })
+    }
+
+    #[test]
+    fn test_describe_item() {
+        // GIVEN a steel longsword, and an equipped iron sword
+        let identification = Identification::new(String::from("test"));
+        let longsword = build_sword(BladedWeaponType::LONG, MaterialType::STEEL, 40);
+        let mut equipped = build_sword(BladedWeaponType::ARMING, MaterialType::IRON, 30);
+        equipped.set_equipment_slot(Some(EquipmentSlot::PRIMARY));
+
+        // WHEN we describe the longsword against the equipped sword
+        let lines = describe_item(&longsword, &[equipped], &identification);
+
+        // THEN we expect the full details and a comparison
+        assert_eq!(vec![
+            String::from("Longsword, a weapon made of steel."),
+            String::from("Material: Steel"),
+            String::from("Weight: 2 Kg"),
+            String::from("Value: 50"),
+            String::from("Value per Kg: 25.0"),
+            String::from("Damage: 40"),
+            String::from("Handedness: Two-handed"),
+            String::from("vs Iron Sword (PRIMARY): damage +10")
+        ], lines);
+
+        // AND with nothing equipped, there's nothing to compare against
+        let lines = describe_item(&longsword, &[], &identification);
+        assert_eq!("Nothing equipped to compare against.", lines.last().unwrap());
+
+        // AND an unidentified potion doesn't give away what it is
+        let potion = Item::magic(Uuid::new_v4(), ItemForm::POTION(PotionType::Healing), '!', 0.5, 20);
+        let summary = describe_item(&potion, &[], &identification).remove(0);
+        assert_eq!(format!("{}, a magic item yet to be identified.", potion.get_display_name(&identification)), summary);
+        assert!(!summary.contains("Healing"));
+    }
+}
@@ -0,0 +1,223 @@
+use termion::event::Key;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+use ratatui::CompletedFrame;
+
+use crate::engine::run_history::{RunHistory, RunRecord, RunSortOrder};
+use crate::error::errors::ErrorWrapper;
+use crate::input::KeyInputResolver;
+use crate::map::position::Area;
+use crate::terminal::terminal_manager::TerminalManager;
+use crate::ui::ui::UI;
+use crate::ui::ui_util::build_paragraph_multi;
+use crate::view::{verify_display_size, GenericInputResult, InputHandler, InputResult, View};
+
+/*
+    This View shows the leaderboard of past runs, and the "morgue" details of any single run
+ */
+pub struct LeaderboardView<'a, B : ratatui::backend::Backend> {
+    ui : &'a mut UI,
+    terminal_manager : &'a mut TerminalManager<B>,
+    key_input : &'a mut dyn KeyInputResolver,
+    history: RunHistory,
+    sort_order: RunSortOrder,
+    selection: usize,
+    showing_morgue: bool
+}
+
+impl <B : ratatui::backend::Backend> LeaderboardView<'_, B> {
+    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver, history: RunHistory) -> LeaderboardView<'a, B> {
+        LeaderboardView { ui, terminal_manager, key_input, history, sort_order: RunSortOrder::Score, selection: 0, showing_morgue: false }
+    }
+
+    fn sorted_runs(&self) -> Vec<RunRecord> {
+        self.history.sorted_by(self.sort_order)
+    }
+
+    fn build_run_line(position: usize, run: &RunRecord) -> String {
+        format!("{:>3}. {:<12} {:>6} {:>6} {:>7}  {}", position + 1, run.character_name, run.get_score(), run.deepest_level, run.turns, run.outcome.describe())
+    }
+
+    fn build_morgue_lines(run: &RunRecord) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} the {} {}", run.character_name, run.race, run.class),
+            run.outcome.describe(),
+            String::new(),
+            format!("Score: {}", run.get_score()),
+            format!("Deepest level: {}", if run.deepest_level == 0 { String::from("Overworld") } else { run.deepest_level.to_string() }),
+            format!("Loot value: {}", run.loot_value),
+            format!("Turns taken: {}", run.turns),
+            format!("Seed: {}", run.seed),
+            String::new(),
+            String::from("Final inventory:")
+        ];
+        if run.inventory.is_empty() {
+            lines.push(String::from("  (nothing)"));
+        }
+        for item in &run.inventory {
+            lines.push(format!("  {}", item));
+        }
+        lines.push(String::new());
+        lines.push(String::from("[Esc] Back"));
+        lines
+    }
+}
+
+impl <B : ratatui::backend::Backend> View<()> for LeaderboardView<'_, B> {
+    fn begin(&mut self) -> Result<InputResult<()>, ErrorWrapper> {
+        self.terminal_manager.terminal.clear()?;
+        self.draw(None)?;
+        let mut input_result = self.handle_input(None)?;
+        while !input_result.generic_input_result.done {
+            if input_result.generic_input_result.requires_view_refresh {
+                self.terminal_manager.terminal.clear()?;
+            }
+            self.draw(None)?;
+            input_result = self.handle_input(None)?;
+        }
+        Ok(input_result)
+    }
+
+    fn draw(&mut self, _area: Option<Area>) -> Result<CompletedFrame<'_>, ErrorWrapper> {
+        verify_display_size::<B>(self.ui, self.terminal_manager, self.key_input);
+        let _ui = &mut self.ui;
+        let runs = self.sorted_runs();
+        let selection = self.selection;
+        let sort_order = self.sort_order;
+
+        if self.showing_morgue {
+            let lines = runs.get(selection).map(LeaderboardView::<B>::build_morgue_lines)
+                .unwrap_or_default();
+            return Ok(self.terminal_manager.terminal.draw(|frame| {
+                let block = Block::default().borders(Borders::ALL).title("Morgue");
+                let area = frame.area();
+                frame.render_widget(block, area);
+                let inner = Rect::new(area.x + 2, area.y + 1, area.width.saturating_sub(4), area.height.saturating_sub(2));
+                frame.render_widget(build_paragraph_multi(lines), inner);
+            })?);
+        }
+
+        Ok(self.terminal_manager.terminal.draw(|frame| {
+            let block = Block::default().borders(Borders::ALL).title(format!("Leaderboard (sorted by {:?})", sort_order));
+            let area = frame.area();
+            frame.render_widget(block, area);
+
+            let header = format!("     {:<12} {:>6} {:>6} {:>7}  {}", "Name", "Score", "Depth", "Turns", "Fate");
+            let usage = String::from("[Up/Down] Select [Enter] Morgue [s] Sort [Esc] Back");
+            frame.render_widget(build_paragraph_multi(vec![header]), Rect::new(area.x + 2, area.y + 1, area.width.saturating_sub(4), 1));
+            frame.render_widget(build_paragraph_multi(vec![usage]), Rect::new(area.x + 2, area.y + area.height.saturating_sub(2), area.width.saturating_sub(4), 1));
+
+            let list_area = Rect::new(area.x + 2, area.y + 2, area.width.saturating_sub(4), area.height.saturating_sub(5));
+            if runs.is_empty() {
+                frame.render_widget(build_paragraph_multi(vec![String::from("No runs recorded yet.")]), list_area);
+            } else {
+                let items: Vec<ListItem> = runs.iter().enumerate()
+                    .map(|(i, run)| ListItem::new(LeaderboardView::<B>::build_run_line(i, run)))
+                    .collect();
+                let list = List::new(items)
+                    .style(Style::default().fg(Color::White))
+                    .highlight_style(Style::default().fg(Color::Red));
+                let mut list_state = ListState::default();
+                list_state.select(Some(selection));
+                frame.render_stateful_widget(list, list_area, &mut list_state);
+            }
+        })?)
+    }
+}
+
+impl <B : ratatui::backend::Backend> InputHandler<()> for LeaderboardView<'_, B> {
+    fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<()>, ErrorWrapper> {
+        let key = self.key_input.get_or_return_input_key(input)?;
+        let run_count = self.history.get_runs().len();
+        let mut done = false;
+        let mut requires_view_refresh = false;
+
+        match key {
+            Key::Esc => {
+                // Backs out of the morgue before leaving the leaderboard
+                if self.showing_morgue {
+                    self.showing_morgue = false;
+                    requires_view_refresh = true;
+                } else {
+                    done = true;
+                }
+            },
+            Key::Up if !self.showing_morgue && self.selection > 0 => {
+                self.selection -= 1;
+            },
+            Key::Down if !self.showing_morgue && self.selection + 1 < run_count => {
+                self.selection += 1;
+            },
+            Key::Char('s') if !self.showing_morgue => {
+                self.sort_order = self.sort_order.next();
+                self.selection = 0;
+            },
+            crate::global_flags::ENTER_KEY if run_count > 0 && !self.showing_morgue => {
+                self.showing_morgue = true;
+                requires_view_refresh = true;
+            },
+            _ => {}
+        }
+
+        Ok(InputResult { generic_input_result: GenericInputResult { done, requires_view_refresh }, view_specific_result: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use termion::event::Key;
+
+    use crate::engine::run_history::{RunHistory, RunOutcome, RunRecord, RunSortOrder};
+    use crate::input::MockKeyInputResolver;
+    use crate::terminal::terminal_manager::init_test;
+    use crate::ui::resolution::MIN_RESOLUTION;
+    use crate::ui::ui::build_ui;
+    use crate::view::leaderboard_view::LeaderboardView;
+    use crate::view::InputHandler;
+
+    fn build_run(name: &str, deepest_level: usize, loot_value: i32) -> RunRecord {
+        RunRecord {
+            character_name: name.to_string(),
+            race: String::from("Human"),
+            class: String::from("Warrior"),
+            seed: String::from("seed"),
+            deepest_level,
+            loot_value,
+            turns: 10,
+            outcome: RunOutcome::Escaped,
+            inventory: Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_handle_input() {
+        // GIVEN a leaderboard of 2 runs
+        let mut history = RunHistory::default();
+        history.add_run(build_run("A", 1, 0));
+        history.add_run(build_run("B", 2, 0));
+        let mut ui = build_ui();
+        let mut terminal_manager = init_test(MIN_RESOLUTION).unwrap();
+        let mut key_input = MockKeyInputResolver { key_results: VecDeque::new() };
+        let mut view = LeaderboardView::new(&mut ui, &mut terminal_manager, &mut key_input, history);
+
+        // WHEN we move down, past the end of the list
+        view.handle_input(Some(Key::Down)).unwrap();
+        view.handle_input(Some(Key::Down)).unwrap();
+        // THEN the selection stops at the last run
+        assert_eq!(1, view.selection);
+
+        // AND toggling the sort resets the selection
+        view.handle_input(Some(Key::Char('s'))).unwrap();
+        assert_eq!(RunSortOrder::Depth, view.sort_order);
+        assert_eq!(0, view.selection);
+
+        // AND escaping the morgue returns to the leaderboard, before escaping the view altogether
+        view.handle_input(Some(Key::Char('\n'))).unwrap();
+        assert!(view.showing_morgue);
+        assert!(!view.handle_input(Some(Key::Esc)).unwrap().generic_input_result.done);
+        assert!(view.handle_input(Some(Key::Esc)).unwrap().generic_input_result.done);
+    }
+}
@@ -7,11 +7,12 @@ use ratatui::CompletedFrame;
 
 use crate::engine::level::Level;
 use crate::error::errors::ErrorWrapper;
+use crate::input::KeyInputResolver;
 use crate::map::map_view_areas::MapViewAreas;
 use crate::map::position::Area;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::ui::UI;
-use crate::view::util::cell_builder::CellBuilder;
+use crate::ui::ui_wrapper::build_map_view_areas;
 use crate::view::{verify_display_size, GenericInputResult, InputHandler, InputResult, View};
 use crate::widget::stateful::map_widget::MapWidget;
 use crate::widget::{StatefulWidgetState, StatefulWidgetType};
@@ -32,6 +33,7 @@ pub struct MapView<'a, B : ratatui::backend::Backend> {
     pub ui : &'a mut UI,
     pub level : &'a mut Level,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub map_view_areas: MapViewAreas
 }
 
@@ -39,7 +41,7 @@ impl<B : ratatui::backend::Backend> MapView<'_, B> {
 
     fn blank_map_view(&mut self) -> Result<(), Error> {
         let view_area = self.map_view_areas.map_display_area;
-        let blanking_cell: Cell = CellBuilder::for_blank();
+        let blanking_cell: Cell = self.ui.build_cell_builder().for_blank();
         // Clear everything in the view area (entire internal window area)
         for view_area_x in view_area.start_position.x..view_area.end_position.x {
             for view_area_y in view_area.start_position.y..view_area.end_position.y {
@@ -55,7 +57,7 @@ impl<B : ratatui::backend::Backend> MapView<'_, B> {
     
     fn build_widget(&mut self) -> MapWidget {
         let map_view_areas = self.map_view_areas;
-        let map_widget: MapWidget = MapWidget::new(map_view_areas);
+        let map_widget: MapWidget = MapWidget::new(map_view_areas, self.ui.build_cell_builder());
         return map_widget;
     }
 }
@@ -80,13 +82,17 @@ impl<B : ratatui::backend::Backend> View<bool> for MapView<'_, B> {
     // 3. Map display area - Map co-ords (The position/size of the map 'viewfinder', the area that you can actually see the map through)
     // 3.1 The map display area is what will move with the character throughout larger maps
     fn draw(&mut self, _area: Option<Area>) -> Result<CompletedFrame, ErrorWrapper> {
+        // The map is drawn somewhere else if the terminal's been resized
+        if verify_display_size::<B>(self.ui, self.terminal_manager, self.key_input) {
+            if let Some(map_view_areas) = build_map_view_areas(self.ui, self.level) {
+                self.map_view_areas = map_view_areas;
+            }
+        }
+
         let map_display_area = self.map_view_areas.map_display_area;
         let frame_size = map_display_area.to_rect();
-        
-        let ui = &mut self.ui;
 
-        // Frame handler data
-        verify_display_size::<B>(self.terminal_manager);
+        let ui = &mut self.ui;
 
         let level = &mut self.level;
         let terminal = &mut self.terminal_manager.terminal;
@@ -1,10 +1,8 @@
 use std::convert::TryInto;
-use std::io;
 use std::io::Error;
 
 use crate::error::errors::ErrorWrapper;
 use log::info;
-use termion::input::TermRead;
 use ratatui::layout::Rect;
 use ratatui::CompletedFrame;
 use ratatui::widgets::ListState;
@@ -12,14 +10,20 @@ use ratatui::widgets::ListState;
 use crate::map::position::Area;
 use crate::menu::{Menu, Selection, ToList};
 use crate::terminal::terminal_manager::TerminalManager;
+use crate::input::{InputEvent, KeyInputResolver};
+use crate::map::position::Position;
 use crate::ui::ui::{StartMenuChoice, UI};
 use crate::ui::ui_areas::UI_AREA_NAME_MAIN;
 use crate::ui::ui_layout::LayoutType;
 use crate::view::{verify_display_size, GenericInputResult, InputResult, View};
 
+// Where the first menu entry is drawn, each of the rest is on the row below
+const MENU_START: Position = Position { x: 4, y: 4 };
+
 pub struct MenuView<'a, B : ratatui::backend::Backend> {
     pub ui : &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub(crate) menu: Menu
 }
 
@@ -29,8 +33,20 @@ impl<B : ratatui::backend::Backend> MenuView<'_, B> {
             let start_menu_mut = &mut self.menu;
 
             let last_selection = start_menu_mut.selection;
-            let key = io::stdin().keys().next().unwrap().unwrap();
-            start_menu_mut.handle_input(key);
+            match self.key_input.get_input_event()? {
+                InputEvent::Click(position) => {
+                    if let Some(index) = menu_entry_at(position, start_menu_mut.get_width()) {
+                        start_menu_mut.choose(index);
+                    }
+                },
+                InputEvent::Resize(_, _) => {
+                    self.draw(None)?;
+                    continue;
+                },
+                event => if let Some(key) = event.as_key() {
+                    start_menu_mut.handle_input(key);
+                }
+            }
             let selection = start_menu_mut.selection;
             info!("Selected menu item: {}", selection);
 
@@ -51,6 +67,15 @@ impl<B : ratatui::backend::Backend> MenuView<'_, B> {
     }
 }
 
+// The index of the menu entry on the row clicked, the menu itself checks it's in range
+fn menu_entry_at(position: Position, menu_width: usize) -> Option<usize> {
+    let within_menu = position.x >= MENU_START.x && (position.x - MENU_START.x) < menu_width as u16;
+    if within_menu && position.y >= MENU_START.y {
+        return Some((position.y - MENU_START.y) as usize);
+    }
+    None
+}
+
 impl<B : ratatui::backend::Backend> View<StartMenuChoice> for MenuView<'_, B> {
     fn begin(&mut self) -> Result<InputResult<StartMenuChoice>, ErrorWrapper> {
         self.draw(None)?;
@@ -69,7 +94,7 @@ impl<B : ratatui::backend::Backend> View<StartMenuChoice> for MenuView<'_, B> {
 
     fn draw(&mut self, _area: Option<Area>) -> Result<CompletedFrame, ErrorWrapper> {
         let ui = &mut self.ui;
-        verify_display_size::<B>(&mut self.terminal_manager);
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
 
         // TODO hookup a full-screen area for this
         let ui_areas = ui.ui_layout.as_mut().unwrap().get_ui_areas(LayoutType::SingleMainWindow);
@@ -81,9 +106,28 @@ impl<B : ratatui::backend::Backend> View<StartMenuChoice> for MenuView<'_, B> {
             let mut menu_list_state = ListState::default();
             menu_list_state.select(Some(menu_selection.try_into().unwrap()));
             let area = main_area_result.area;
-            let menu_size = Rect::new(4, 4, area.width / 2, menu.menu_titles.len().try_into().unwrap());
+            let menu_size = Rect::new(MENU_START.x, MENU_START.y, area.width / 2, menu.menu_titles.len().try_into().unwrap());
             let menu_list = menu.to_list();
             frame.render_stateful_widget(menu_list, menu_size, &mut menu_list_state);
         })?)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::map::position::Position;
+    use crate::view::menu_view::menu_entry_at;
+
+    #[test]
+    fn test_menu_entry_at() {
+        // GIVEN a menu 10 wide, starting at 4,4
+        // WHEN we click on the rows of it
+        // THEN we expect the entry on that row
+        assert_eq!(Some(0), menu_entry_at(Position::new(4, 4), 10));
+        assert_eq!(Some(2), menu_entry_at(Position::new(13, 6), 10));
+
+        // AND nothing when clicking beside or above it
+        assert_eq!(None, menu_entry_at(Position::new(14, 4), 10));
+        assert_eq!(None, menu_entry_at(Position::new(3, 4), 10));
+        assert_eq!(None, menu_entry_at(Position::new(4, 3), 10));
+    }
+}
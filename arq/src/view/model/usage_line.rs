@@ -53,4 +53,44 @@ impl UsageLine {
         }
         description
     }
+
+    // The same description, wrapped onto as many lines as needed to keep each within the given width
+    pub fn describe_lines(&self, width: usize) -> Vec<String> {
+        let mut lines = vec![String::from("")];
+        for c in self.commands.iter() {
+            let usage = c.describe_usage();
+            let line = lines.last_mut().unwrap();
+            if line.is_empty() {
+                *line = usage;
+            } else if line.len() + usage.len() + 2 <= width {
+                *line += format!(", {}", usage).as_str();
+            } else {
+                *line += ",";
+                lines.push(usage);
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::model::usage_line::{UsageCommand, UsageLine};
+
+    #[test]
+    fn test_describe_lines() {
+        // GIVEN a usage line of 3 commands
+        let usage_line = UsageLine::new(vec![
+            UsageCommand::new('o', String::from("open")),
+            UsageCommand::new('t', String::from("take")),
+            UsageCommand::new('s', String::from("sort"))
+        ]);
+
+        // WHEN it's described within enough width for all of them
+        // THEN they're all on one line
+        assert_eq!(vec![String::from("o - open, t - take, s - sort")], usage_line.describe_lines(28));
+
+        // AND otherwise it's wrapped between commands
+        assert_eq!(vec![String::from("o - open, t - take,"), String::from("s - sort")], usage_line.describe_lines(20));
+    }
 }
@@ -0,0 +1,198 @@
+use termion::event::Key;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+use ratatui::CompletedFrame;
+
+use crate::error::errors::ErrorWrapper;
+use crate::map::objects::identification::Identification;
+use crate::map::objects::items::Item;
+use crate::input::KeyInputResolver;
+use crate::map::position::Area;
+use crate::terminal::terminal_manager::TerminalManager;
+use crate::ui::theme::ThemeColour;
+use crate::ui::ui::UI;
+use crate::ui::ui_areas::UI_AREA_NAME_MAIN;
+use crate::ui::ui_layout::LayoutType;
+use crate::ui::ui_util::build_paragraph_multi;
+use crate::view::{verify_display_size, GenericInputResult, InputHandler, InputResult, View};
+
+const USAGE: &str = "[Space] Toggle [a] All [Enter] Take [Esc] Cancel";
+const PROMPT_WIDTH: u16 = 52;
+
+/*
+    A compact prompt for choosing which of several items on the floor to pick up
+    The result is the items chosen, or None if the player cancels
+ */
+pub struct PickUpView<'a, B : ratatui::backend::Backend> {
+    ui : &'a mut UI,
+    terminal_manager : &'a mut TerminalManager<B>,
+    key_input : &'a mut dyn KeyInputResolver,
+    items: Vec<Item>,
+    identification: Identification,
+    selected: Vec<bool>,
+    focus: usize
+}
+
+impl <B : ratatui::backend::Backend> PickUpView<'_, B> {
+    pub fn new<'a>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut dyn KeyInputResolver, items: Vec<Item>, identification: Identification) -> PickUpView<'a, B> {
+        let selected = vec![false; items.len()];
+        PickUpView { ui, terminal_manager, key_input, items, identification, selected, focus: 0 }
+    }
+
+    // Taking with nothing selected takes the focused item
+    fn get_chosen_items(&self) -> Vec<Item> {
+        if !self.selected.contains(&true) {
+            return self.items.get(self.focus).cloned().into_iter().collect();
+        }
+        self.items.iter().zip(self.selected.iter())
+            .filter(|(_, selected)| **selected)
+            .map(|(item, _)| item.clone())
+            .collect()
+    }
+}
+
+impl <B : ratatui::backend::Backend> View<Vec<Item>> for PickUpView<'_, B> {
+    fn begin(&mut self) -> Result<InputResult<Vec<Item>>, ErrorWrapper> {
+        self.draw(None)?;
+        let mut input_result = self.handle_input(None)?;
+        while !input_result.generic_input_result.done {
+            self.draw(None)?;
+            input_result = self.handle_input(None)?;
+        }
+        Ok(input_result)
+    }
+
+    fn draw(&mut self, _area: Option<Area>) -> Result<CompletedFrame<'_>, ErrorWrapper> {
+        let ui = &mut self.ui;
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
+
+        let identification = &self.identification;
+        let theme = ui.get_theme();
+        let lines: Vec<(String, Color)> = self.items.iter().zip(self.selected.iter())
+            .map(|(item, selected)| (format!("[{}] {}", if *selected { 'x' } else { ' ' }, item.get_stack_name(identification)), theme.get_colour(ThemeColour::for_rarity(item.get_rarity()))))
+            .collect();
+        let focus = self.focus;
+
+        let frame_size = self.terminal_manager.terminal.get_frame().area();
+        let ui_layout = ui.ui_layout.as_mut().ok_or(ErrorWrapper::new_internal(String::from("Failed to get ui_layout, has it been initialised?")))?;
+        let main_area = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit)?.get_area(UI_AREA_NAME_MAIN)
+            .map(|a| a.area.to_rect())
+            .unwrap_or(frame_size);
+
+        Ok(self.terminal_manager.terminal.draw(|frame| {
+            ui.render(None, frame);
+
+            // Centered within the main window, only as tall as it needs to be
+            let width = PROMPT_WIDTH.min(main_area.width.saturating_sub(2));
+            let height = (lines.len() as u16 + 3).min(main_area.height.saturating_sub(2));
+            let area = Rect::new(main_area.x + (main_area.width - width) / 2, main_area.y + (main_area.height - height) / 2, width, height);
+            frame.render_widget(Clear, area);
+            frame.render_widget(Block::default().borders(Borders::ALL).title("Pick up what?"), area);
+
+            let list_area = Rect::new(area.x + 1, area.y + 1, area.width.saturating_sub(2), area.height.saturating_sub(3));
+            let items: Vec<ListItem> = lines.into_iter().map(|(line, colour)| ListItem::new(line).style(Style::default().fg(colour))).collect();
+            let list = List::new(items)
+                .style(Style::default().fg(Color::White))
+                .highlight_style(Style::default().fg(Color::Green));
+            let mut list_state = ListState::default();
+            list_state.select(Some(focus));
+            frame.render_stateful_widget(list, list_area, &mut list_state);
+
+            let usage_area = Rect::new(area.x + 1, area.y + area.height.saturating_sub(2), area.width.saturating_sub(2), 1);
+            frame.render_widget(build_paragraph_multi(vec![String::from(USAGE)]), usage_area);
+        })?)
+    }
+}
+
+impl <B : ratatui::backend::Backend> InputHandler<Vec<Item>> for PickUpView<'_, B> {
+    fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<Vec<Item>>, ErrorWrapper> {
+        let key = self.key_input.get_or_return_input_key(input)?;
+        let mut done = false;
+        let mut chosen = None;
+
+        match key {
+            Key::Esc => {
+                done = true;
+            },
+            Key::Up if self.focus > 0 => {
+                self.focus -= 1;
+            },
+            Key::Down if self.focus + 1 < self.items.len() => {
+                self.focus += 1;
+            },
+            Key::Char(' ') => {
+                if let Some(selected) = self.selected.get_mut(self.focus) {
+                    *selected = !*selected;
+                }
+            },
+            Key::Char('a') => {
+                // Selects everything, unless everything is already selected
+                let select_all = self.selected.contains(&false);
+                self.selected.iter_mut().for_each(|s| *s = select_all);
+            },
+            crate::global_flags::ENTER_KEY => {
+                done = true;
+                chosen = Some(self.get_chosen_items());
+            },
+            _ => {}
+        }
+
+        Ok(InputResult { generic_input_result: GenericInputResult { done, requires_view_refresh: false }, view_specific_result: chosen })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use termion::event::Key;
+    use uuid::Uuid;
+
+    use crate::input::MockKeyInputResolver;
+    use crate::map::objects::identification::Identification;
+    use crate::map::objects::items::{Item, MaterialType};
+    use crate::terminal::terminal_manager::init_test;
+    use crate::ui::resolution::MIN_RESOLUTION;
+    use crate::ui::ui::build_ui;
+    use crate::view::pickup_view::PickUpView;
+    use crate::view::InputHandler;
+
+    fn build_item(name: &str) -> Item {
+        Item::new(Uuid::new_v4(), name.to_string(), MaterialType::UNKNOWN, 'X', 1.0, 1)
+    }
+
+    #[test]
+    fn test_handle_input() {
+        // GIVEN a prompt for 3 items
+        let mut ui = build_ui();
+        let mut terminal_manager = init_test(MIN_RESOLUTION).unwrap();
+        let items = vec![build_item("A"), build_item("B"), build_item("C")];
+        let mut key_input = MockKeyInputResolver { key_results: VecDeque::new() };
+        let mut view = PickUpView::new(&mut ui, &mut terminal_manager, &mut key_input, items, Identification::new(String::new()));
+
+        // WHEN we take without selecting anything
+        // THEN the focused item is chosen
+        let result = view.handle_input(Some(Key::Char('\n'))).unwrap();
+        assert!(result.generic_input_result.done);
+        assert_eq!(vec![String::from("A")], result.view_specific_result.unwrap().iter().map(|i| i.get_name()).collect::<Vec<String>>());
+
+        // AND when we select the last 2 items, those are chosen
+        view.handle_input(Some(Key::Down)).unwrap();
+        view.handle_input(Some(Key::Char(' '))).unwrap();
+        view.handle_input(Some(Key::Down)).unwrap();
+        view.handle_input(Some(Key::Char(' '))).unwrap();
+        let result = view.handle_input(Some(Key::Char('\n'))).unwrap();
+        assert_eq!(vec![String::from("B"), String::from("C")], result.view_specific_result.unwrap().iter().map(|i| i.get_name()).collect::<Vec<String>>());
+
+        // AND selecting all chooses everything
+        view.handle_input(Some(Key::Char('a'))).unwrap();
+        let result = view.handle_input(Some(Key::Char('\n'))).unwrap();
+        assert_eq!(3, result.view_specific_result.unwrap().len());
+
+        // AND cancelling chooses nothing
+        let result = view.handle_input(Some(Key::Esc)).unwrap();
+        assert!(result.generic_input_result.done);
+        assert!(result.view_specific_result.is_none());
+    }
+}
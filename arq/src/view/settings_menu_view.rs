@@ -5,11 +5,12 @@ use termion::event::Key;
 use ratatui::layout::Rect;
 use ratatui::CompletedFrame;
 
+use crate::input::KeyInputResolver;
 use crate::map::position::Area;
 use crate::terminal::terminal_manager::TerminalManager;
 use crate::ui::ui::UI;
 use crate::view::util::widget_menu::WidgetMenu;
-use crate::view::{resolve_input, GenericInputResult, InputHandler, InputResult, View};
+use crate::view::{GenericInputResult, InputHandler, InputResult, View};
 use crate::widget::{Focusable, StatefulWidgetType};
 
 /*
@@ -21,6 +22,7 @@ use crate::widget::{Focusable, StatefulWidgetType};
 pub struct SettingsMenuView<'a, B : ratatui::backend::Backend> {
     pub ui : &'a mut UI,
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub menu: WidgetMenu
 }
 
@@ -74,7 +76,7 @@ impl <'b, B : ratatui::backend::Backend> View<bool> for SettingsMenuView<'_, B>
 impl <COM: ratatui::backend::Backend> InputHandler<bool> for SettingsMenuView<'_, COM> {
     fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<bool>, ErrorWrapper> {
         let menu_view = &mut self.menu;
-        let key = resolve_input(input)?;
+        let key = self.key_input.get_or_return_input_key(input)?;
         let mut target_widget = None;
         match menu_view.widgets.widget_index {
             Some(idx) => {
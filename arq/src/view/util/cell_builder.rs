@@ -5,66 +5,83 @@ use ratatui::style::Color;
 use crate::character::Character;
 use crate::global_flags;
 use crate::map::objects::container::Container;
-use crate::map::tile::TileDetails;
+use crate::map::tile::{TileDetails, TileType};
 use crate::terminal::colour_mapper;
+use crate::ui::glyphs::{GlyphSet, WallJoins};
+use crate::ui::theme::{Theme, ThemeColour};
 
 #[derive(Clone)]
 #[derive(Debug)]
 pub struct CellBuilder {
+    theme: Theme,
+    glyph_set: GlyphSet
+}
+
+// Tiles with a meaning of their own are coloured by the theme, the rest keep the colour of their symbol
+fn tile_theme_colour(tile_type: &TileType) -> Option<ThemeColour> {
+    match tile_type {
+        TileType::Wall => Some(ThemeColour::Wall),
+        TileType::Room | TileType::Corridor => Some(ThemeColour::Floor),
+        TileType::Door => Some(ThemeColour::Door),
+        TileType::Entry => Some(ThemeColour::Entry),
+        TileType::Exit => Some(ThemeColour::Exit),
+        _ => None
+    }
 }
 
 impl CellBuilder {
-    pub fn from_tile(tile_details: &TileDetails) -> Cell {
-        let symbol = tile_details.symbol.character.to_string();
-        let fg = colour_mapper::map_colour(tile_details.symbol.colour);
-        let bg = ratatui::style::Color::Black;
-        let modifier = ratatui::style::Modifier::empty();
-        
-        let mut cell = Cell::default();
-        cell.set_symbol(&*symbol);
-        cell.set_bg(bg);
-        cell.set_fg(fg);
-        cell
+    pub fn new(theme: Theme, glyph_set: GlyphSet) -> CellBuilder {
+        CellBuilder { theme, glyph_set }
     }
 
-    pub fn from_character(character: &Character) -> Cell{
-        let character_colour = character.get_colour();
-        let symbol = character.get_symbol().to_string();
-        let fg = colour_mapper::map_colour(character_colour);
-        let bg = ratatui::style::Color::Black;
+    fn build_cell(&self, symbol: String, fg: Color) -> Cell {
         let mut cell = Cell::default();
         cell.set_symbol(&*symbol);
-        cell.set_bg(bg);
+        cell.set_bg(self.theme.get_colour(ThemeColour::Background));
         cell.set_fg(fg);
         cell
     }
 
-    pub fn from_container(container: &Container) -> Cell{
+    pub fn for_tile(&self, tile_details: &TileDetails, joins: WallJoins) -> Cell {
+        let symbol = self.glyph_set.tile_glyph(&tile_details.tile_type, joins, tile_details.symbol.character).to_string();
+        let fg = match tile_theme_colour(&tile_details.tile_type) {
+            Some(colour) => self.theme.get_colour(colour),
+            None => self.theme.fit_colour(colour_mapper::map_colour(tile_details.symbol.colour))
+        };
+        self.build_cell(symbol, fg)
+    }
+
+    pub fn for_player(&self, player: &Character) -> Cell{
+        let symbol = self.glyph_set.player_glyph(player.get_symbol()).to_string();
+        self.build_cell(symbol, self.theme.get_colour(ThemeColour::Player))
+    }
+
+    pub fn for_npc(&self, npc: &Character) -> Cell{
+        self.build_cell(npc.get_symbol().to_string(), self.theme.get_colour(ThemeColour::Hostile))
+    }
+
+    // Coloured only by what can be seen from outside, what's inside stays unknown until it's opened
+    pub fn for_container(&self, container: &Container) -> Cell{
         let container_item = container.get_self_item();
         let symbol = container_item.symbol.character.to_string();
-        let colour = container_item.symbol.colour;
-        let fg = colour_mapper::map_colour(colour);
-        let bg = ratatui::style::Color::Black;
-        let mut cell = Cell::default();
-        cell.set_symbol(&*symbol);
-        cell.set_bg(bg);
-        cell.set_fg(fg);
-        cell
+        let fg = self.theme.get_colour(ThemeColour::for_rarity(container_item.get_rarity()));
+        self.build_cell(symbol, fg)
     }
 
-    pub fn for_blank() -> Cell {
+    pub fn for_blank(&self) -> Cell {
+        let background = self.theme.get_colour(ThemeColour::Background);
         if global_flags::GLOBALS.debugging_map_symbols {
             let debugging_symbol = String::from('\u{2588}');
             let mut debugging_blank = Cell::default();
             debugging_blank.set_symbol(&*debugging_symbol);
-            debugging_blank.set_bg(Color::Black);
+            debugging_blank.set_bg(background);
             debugging_blank.set_fg(Color::Green);
             debugging_blank
         } else {
             let mut blank = Cell::new(" ");
-            blank.set_bg(Color::Black);
-            blank.set_fg(Color::Black);
+            blank.set_bg(background);
+            blank.set_fg(background);
             blank
         }
     }
-}
\ No newline at end of file
+}
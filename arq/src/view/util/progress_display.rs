@@ -1,8 +1,6 @@
-use std::io;
 use std::sync::mpsc::Receiver;
 
-use termion::input::TermRead;
-
+use crate::input::KeyInputResolver;
 use crate::map::position::Area;
 use crate::progress::MultiStepProgress;
 use crate::terminal::terminal_manager::TerminalManager;
@@ -14,6 +12,7 @@ use crate::view::framehandler::{FrameData, FrameHandler};
 
 pub struct ProgressDisplay<'a, B : ratatui::backend::Backend> {
     pub terminal_manager : &'a mut TerminalManager<B>,
+    pub key_input : &'a mut dyn KeyInputResolver,
     pub frame_handler: MapGenerationFrameHandler
 }
 
@@ -25,7 +24,7 @@ impl <B : ratatui::backend::Backend> ProgressDisplay<'_, B>  {
                 self.show_progress(p.clone());
                 if p.is_done() {
                     // Wait for confirmation
-                    io::stdin().keys().next().unwrap().expect("The next keyboard key should have been captured");
+                    self.key_input.get_input_key().expect("The next keyboard key should have been captured");
                     return;
                 }
             }
@@ -39,12 +38,13 @@ impl <B : ratatui::backend::Backend> ProgressDisplay<'_, B>  {
             log::info!("Showing progress: {}/{}", step_number, step_count);
             let fh = &mut self.frame_handler;
             self.terminal_manager.terminal.draw(|frame| {
-                let ui_areas= UIAreasBuilder::new(Area::from_rect(frame.size()))
+                // Progress is only shown if there's room for it
+                if let Ok((_, ui_areas)) = UIAreasBuilder::new(Area::from_rect(frame.area()))
                     .layout_type(SingleMainWindowCentered)
-                    .build().1;
-
-                let main_area = ui_areas.get_area(UI_AREA_NAME_MAIN).unwrap();
-                fh.handle_frame(frame, FrameData { data: progress.clone(), ui_areas: ui_areas.clone(), frame_area: main_area.area })
+                    .build() {
+                    let main_area = ui_areas.get_area(UI_AREA_NAME_MAIN).unwrap();
+                    fh.handle_frame(frame, FrameData { data: progress.clone(), ui_areas: ui_areas.clone(), frame_area: main_area.area })
+                }
             }).expect("The progress display should have been drawn.");
         }
     }
@@ -1,6 +1,6 @@
 use futures::future::err;
 use crate::error::errors::ErrorWrapper;
-use crate::input::KeyInputResolver;
+use crate::input::{InputEvent, KeyInputResolver};
 use termion::event::Key;
 use ratatui::CompletedFrame;
 
@@ -15,7 +15,8 @@ use crate::view::framehandler::container_choice::{ContainerChoiceFrameHandler, C
 use crate::view::framehandler::{FrameData, FrameHandler};
 use crate::view::util::callback::Callback;
 use crate::view::InputHandler;
-use crate::view::{GenericInputResult, InputResult, View};
+use crate::view::item_detail_view::ItemDetailView;
+use crate::view::{verify_display_size, GenericInputResult, InputResult, View};
 use crate::view::util::try_build_container_choice_frame_handler;
 /*
     This View is responsible for displaying/interacting with containers in the world (i.e chests, dropped items, dead bodies)
@@ -26,7 +27,7 @@ pub struct WorldContainerView<'a, B : ratatui::backend::Backend> {
     pub frame_handlers: WorldContainerViewFrameHandlers,
     pub container : Container,
     pub callback : Box<dyn FnMut(ContainerFrameHandlerInputResult) -> Option<ContainerFrameHandlerInputResult> + 'a>,
-    pub input_resolver: Box<dyn KeyInputResolver>
+    pub key_input : &'a mut dyn KeyInputResolver
 }
 
 pub struct WorldContainerViewFrameData {
@@ -92,9 +93,11 @@ impl <B: ratatui::backend::Backend> View<bool> for WorldContainerView<'_, B>  {
         let frame_handler = &mut self.frame_handlers;
         let ui = &mut self.ui;
 
+        verify_display_size::<B>(ui, self.terminal_manager, self.key_input);
+
         let ui_layout = ui.ui_layout.as_mut().unwrap();
         let frame_size = self.terminal_manager.terminal.get_frame().size();
-        let ui_areas: UIAreas = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit).clone();
+        let ui_areas: UIAreas = ui_layout.get_or_build_areas(frame_size, LayoutType::StandardSplit)?.clone();
 
         if let Some(main) = ui_areas.get_area(UI_AREA_NAME_MAIN) {
             let main_area = main.area;
@@ -115,17 +118,41 @@ impl <B: ratatui::backend::Backend> View<bool> for WorldContainerView<'_, B>  {
 
 impl <COM: ratatui::backend::Backend> InputHandler<bool> for WorldContainerView<'_, COM> {
     fn handle_input(&mut self, input: Option<Key>) -> Result<InputResult<bool>, ErrorWrapper> {
-        let key = self.input_resolver.get_or_return_input_key(input)?;
+        let continue_result = InputResult { generic_input_result: GenericInputResult { done: false, requires_view_refresh: false }, view_specific_result: None };
+        let key = match input {
+            Some(key) => key,
+            None => match self.key_input.get_input_event()? {
+                InputEvent::Click(position) => {
+                    // Clicks only go to the container list, not to any choice of container being shown over it
+                    if self.frame_handlers.choice_frame_handler.is_none() {
+                        if let Some(topmost_view) = self.frame_handlers.container_frame_handlers.last_mut() {
+                            topmost_view.handle_click(position);
+                        }
+                    }
+                    return Ok(continue_result);
+                },
+                event => match event.as_paging_key() {
+                    Some(key) => key,
+                    // i.e a resize, which the redraw after this handles
+                    None => return Ok(continue_result)
+                }
+            }
+        };
+        // Everything is typed into the container view while it's asking for a quantity or search
+        let capturing_input = self.frame_handlers.container_frame_handlers.last().is_some_and(|fh| fh.is_capturing_input());
         match key {
-            Key::Char('t') => {
+            Key::Char('t') if !capturing_input => {
                 if let Some(parent_view) = self.frame_handlers.container_frame_handlers.last_mut() {
+                    if parent_view.try_prompt_quantity('t') {
+                        return Ok(InputResult { generic_input_result: GenericInputResult { done: false, requires_view_refresh: true }, view_specific_result: None});
+                    }
                     let selected_container_items = parent_view.get_selected_items();
                     let data = TakeItemsData { source: self.container.clone(), to_take: selected_container_items, position: None };
                     let result = ContainerFrameHandlerInputResult::TakeItems(data);
                     self.trigger_callback(result);
                 }
             },
-            Key::Esc => {
+            Key::Esc if !capturing_input => {
                 // Drop the last container view and keep going
                 let container_views = &self.frame_handlers.container_frame_handlers;
                 if container_views.len() > 1 {
@@ -212,6 +239,16 @@ impl <'c, B : ratatui::backend::Backend> Callback<'c, ContainerFrameHandlerInput
                         self.ui.set_console_buffer(error.message.unwrap())
                     }
                 },
+                ContainerFrameHandlerInputResult::InspectItem(data) => {
+                    // Looking at an item may have recognised it, so it's listed by what's now known
+                    for fh in &mut self.frame_handlers.container_frame_handlers {
+                        fh.set_identification(data.identification.clone());
+                    }
+                    let mut detail_view = ItemDetailView::new(&mut *self.ui, &mut *self.terminal_manager, &mut *self.key_input, data);
+                    if let Err(e) = detail_view.begin() {
+                        log::error!("Failed to show item details: {}", e);
+                    }
+                },
                 ContainerFrameHandlerInputResult::MoveItems(ref data) => {
                     // TODO (Duplicate) make generic between world_contaienr / character_info
                     if data.target_container.is_some() {
@@ -281,22 +318,23 @@ impl FrameHandler<WorldContainerViewFrameData> for WorldContainerViewFrameHandle
 mod tests {
     use std::collections::{HashMap, VecDeque};
 
-    use crate::input::{IoKeyInputResolver, MockKeyInputResolver};
+    use crate::input::MockKeyInputResolver;
     use termion::event::Key;
     use ratatui::backend::TestBackend;
     use ratatui::layout::Rect;
     use ratatui::prelude::{Modifier, Span, Style};
-    use ratatui::style::Color::{Black, Green, Reset};
+    use ratatui::style::Color::{Black, Green, Reset, White};
     use crate::global_flags::ENTER_KEY;
-    use crate::map::map_generator::build_dev_chest;
+    use crate::test::utils::test_utils::build_dev_chest;
+    use crate::map::objects::identification::Identification;
     use crate::map::position::Area;
     use crate::terminal;
     use crate::terminal::terminal_manager::TerminalManager;
     use crate::test::utils::test_resource_loader::read_expected_buffer_file;
     use crate::ui::resolution::Resolution;
     use crate::ui::ui::{build_ui, UI};
-    use crate::ui::ui_areas::UIAreas;
-    use crate::ui::ui_layout::{LayoutType, UILayout};
+    use crate::ui::theme::Theme;
+    use crate::ui::ui_layout::UILayout;
     use crate::view::framehandler::container;
     use crate::view::model::usage_line::{UsageCommand, UsageLine};
     use crate::view::world_container_view::{WorldContainerView, WorldContainerViewFrameHandlers};
@@ -310,17 +348,20 @@ mod tests {
         ui
     }
     
-    fn build_view<'a, B: ratatui::backend::Backend>(mut ui: &'a mut UI, mut terminal_manager: &'a mut TerminalManager<B>) -> WorldContainerView<'a, B> {
+    fn build_view<'a, B: ratatui::backend::Backend>(ui: &'a mut UI, terminal_manager: &'a mut TerminalManager<B>, key_input: &'a mut MockKeyInputResolver) -> WorldContainerView<'a, B> {
         let container = build_dev_chest();
         let subview_container = container.clone();
         let view_container = container.clone();
         
         let mut commands : Vec<UsageCommand> = vec![
             UsageCommand::new('o', String::from("open") ),
-            UsageCommand::new('t', String::from("take"))
+            UsageCommand::new('t', String::from("take")),
+            UsageCommand::new('s', String::from("sort")),
+            UsageCommand::new('f', String::from("filter")),
+            UsageCommand::new('/', String::from("search"))
         ];
         let usage_line = UsageLine::new(commands);
-        let container_view = container::build_container_frame_handler(subview_container, usage_line);
+        let container_view = container::build_container_frame_handler(subview_container, usage_line, Identification::new(String::new()), Theme::default_theme());
         
         // AND we've created a WorldContainerView to view a dev testing Chest container
         let frame_handlers = WorldContainerViewFrameHandlers { container_frame_handlers: vec![container_view], choice_frame_handler: None };
@@ -331,7 +372,7 @@ mod tests {
             frame_handlers,
             container: view_container,
             callback: Box::new(|_data| {None}),
-            input_resolver: Box::new(IoKeyInputResolver {})
+            key_input
         };
         return world_container_view
     }
@@ -341,7 +382,8 @@ mod tests {
         // GIVEN a UI and terminal manager representing a 80x24 (MIN_RESOLUTION) screen
         let mut ui = build_test_minimal_ui();
         let mut terminal_manager = terminal::terminal_manager::init_test(MIN_RESOLUTION).unwrap();
-        let mut world_container_view = build_view(&mut ui, &mut terminal_manager);
+        let mut key_input = MockKeyInputResolver { key_results: VecDeque::new() };
+        let mut world_container_view = build_view(&mut ui, &mut terminal_manager, &mut key_input);
 
         // WHEN we call to draw the world container view, it should complete successfully
         world_container_view.draw(None).expect("World container view should have been drawn");
@@ -352,8 +394,6 @@ mod tests {
         // GIVEN a UI and terminal manager representing a 80x24 (MIN_RESOLUTION) screen
         let mut ui = build_test_minimal_ui();
         let mut terminal_manager = terminal::terminal_manager::init_test(MIN_RESOLUTION).unwrap();
-        let mut world_container_view = build_view(&mut ui, &mut terminal_manager);
-        
         let key_results: VecDeque<Key> = VecDeque::from([
             Key::Down, // Move down past the Bag to Item 1
             crate::global_flags::ENTER_KEY, // Start selection
@@ -362,9 +402,8 @@ mod tests {
             Key::Esc // Quit the view
         ]);
         // AND we've mocked out input to select the first 3 Test Item x's in the list (skipped past the first item, the bag)
-        let input_resolver = MockKeyInputResolver { key_results };
-        
-        world_container_view.input_resolver = Box::new(input_resolver);
+        let mut key_input = MockKeyInputResolver { key_results };
+        let mut world_container_view = build_view(&mut ui, &mut terminal_manager, &mut key_input);
         
         // WHEN we call to begin the view draw / IO loop
         let result = world_container_view.begin().unwrap();
@@ -372,8 +411,6 @@ mod tests {
         // THEN we expect to be returned here after selecting items
         // AND as the view will not have redrawn yet, we should be able to see this in the buffer
         let frame_area = world_container_view.terminal_manager.terminal.get_frame().area();
-        let mut ui_layout = world_container_view.ui.ui_layout.clone().unwrap();
-        let ui_areas: UIAreas = ui_layout.get_or_build_areas(frame_area, LayoutType::StandardSplit).clone();
         
         let mut expected_buffer= read_expected_buffer_file(String::from("resources/test/world_container_selection.txt"), Area::from_rect(frame_area));
 
@@ -386,6 +423,10 @@ mod tests {
             .fg(Reset)
             .bg(Black);
 
+        let common_style = Style::default()
+            .fg(White)
+            .bg(Black);
+
         let selected_style = Style::default()
             .fg(Green)
             .bg(Black);
@@ -396,6 +437,9 @@ mod tests {
             .add_modifier(Modifier::REVERSED);
         
         expected_buffer.set_style(Rect::new(0,0,frame_area.width, frame_area.height), background_style);
+
+        // Every item row is coloured by it's rarity (all common here) unless it's selected or highlighted
+        expected_buffer.set_style(Rect::new(2,3,54, 14), common_style);
         
         // Row 2 - For Test Item 1 (second row)
         // AND the first 2 rows of the selection should be green text on black background, with no reverse (selected_style)
@@ -9,28 +9,37 @@ use crate::character::character_details::CharacterDetails;
 #[derive(Debug)]
 pub struct CharacterStatLineWidget {
     level: i32,
-    health: i8,
+    health: u16,
+    max_health: u16,
     loot_score: i32,
     character_details : CharacterDetails
 }
 
 impl CharacterStatLineWidget {
-    pub fn new(level: i32, health: i8, character_details: CharacterDetails, loot_score: i32) -> CharacterStatLineWidget {
-        CharacterStatLineWidget { level, health, loot_score, character_details }
+    pub fn new(level: i32, health: u16, max_health: u16, character_details: CharacterDetails, loot_score: i32) -> CharacterStatLineWidget {
+        CharacterStatLineWidget { level, health, max_health, loot_score, character_details }
     }
 
     pub fn set_level(&mut self, level: i32) {
         self.level = level;
     }
 
-    pub fn get_health(&self) -> i8 {
+    pub fn get_health(&self) -> u16 {
         self.health
     }
 
-    pub fn set_health(&mut self, health: i8) {
+    pub fn set_health(&mut self, health: u16) {
         self.health = health;
     }
 
+    pub fn get_max_health(&self) -> u16 {
+        self.max_health
+    }
+
+    pub fn set_max_health(&mut self, max_health: u16) {
+        self.max_health = max_health;
+    }
+
     pub fn get_loot_score(&self) -> i32 {
         self.loot_score
     }
@@ -62,7 +71,7 @@ impl Widget for CharacterStatLineWidget {
 
         let health_offset = calculate_offset(area.x, level_header, level_text);
         let health_header = String::from("Health: ");
-        let loot_text =  format!("{:0>3}",  self.health.to_string());
+        let loot_text =  format!("{:0>3}/{:0>3}",  self.health, self.max_health);
         buf.set_string(health_offset , area.y, health_header.as_str(), Style::default().fg(Color::Green));
         buf.set_string(health_offset + health_header.len() as u16, area.y,loot_text.clone(), Style::default());
 
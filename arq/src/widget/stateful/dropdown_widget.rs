@@ -31,7 +31,10 @@ pub fn get_resolution_dropdown_options() -> Vec<DropdownOption<Resolution>> {
     let fullscreen_dropdown_option: DropdownOption<Resolution> = DropdownOption { display_name: "FULLSCREEN", value: None };
     vec! [
         fullscreen_dropdown_option,
-        min_resolution_dropdown_option
+        min_resolution_dropdown_option,
+        DropdownOption { display_name: "100x30", value: Some(Resolution::new(100, 30)) },
+        DropdownOption { display_name: "120x40", value: Some(Resolution::new(120, 40)) },
+        DropdownOption { display_name: "160x50", value: Some(Resolution::new(160, 50)) }
     ]
 }
 
@@ -8,17 +8,19 @@ use crate::map::map_view_areas::MapViewAreas;
 use crate::map::objects::container::Container;
 use crate::map::position::Position;
 use crate::map::Map;
+use crate::ui::glyphs::WallJoins;
 use crate::view::util::cell_builder::CellBuilder;
 
 #[derive(Clone)]
 #[derive(Debug)]
 pub struct MapWidget {
-    pub map_view_areas : MapViewAreas // Possibly reduced display area
+    pub map_view_areas : MapViewAreas, // Possibly reduced display area
+    cell_builder: CellBuilder
 }
 
 impl MapWidget {
-    pub(crate) const fn new(map_view_areas: MapViewAreas) -> MapWidget {
-        MapWidget { map_view_areas }
+    pub(crate) const fn new(map_view_areas: MapViewAreas, cell_builder: CellBuilder) -> MapWidget {
+        MapWidget { map_view_areas, cell_builder }
     }
     
     fn find_container<'a>(&'a self, map: &'a Map, global_position: Position) -> Option<(Position, &Container)> {
@@ -45,27 +47,27 @@ impl MapWidget {
 
             // Check for the player
             if global_position == player_mut.get_global_position() {
-                return CellBuilder::from_character(player_mut);
+                return self.cell_builder.for_player(player_mut);
             }
 
             let characters = &level.characters;
             if let Some(npc) = characters.get_npcs().iter().find(|npc| npc.get_global_position().equals(global_position)).cloned() {
-                return CellBuilder::from_character(&npc);
+                return self.cell_builder.for_npc(&npc);
             }
 
             if let Some(container_entry) = self.find_container(map, global_position) {
                 let container = container_entry.1;
-                return CellBuilder::from_container(container);
+                return self.cell_builder.for_container(container);
             }
 
             // Otherwise, just draw the tile
             if let Some(tile) = tile_result {
-                return CellBuilder::from_tile(&tile);
+                return self.cell_builder.for_tile(&tile, WallJoins::find(tiles, global_position));
             }
         }
 
         // Draw out of range cell
-        return CellBuilder::for_blank()
+        self.cell_builder.for_blank()
     }
 }
 
@@ -1,7 +1,6 @@
-use crate::settings::Settings;
-use crate::ui::resolution::Resolution;
+use crate::settings::{Setting, Settings};
 use crate::widget::stateful::boolean_widget::build_boolean_widget;
-use crate::widget::stateful::dropdown_widget::{build_dropdown, DropdownOption};
+use crate::widget::stateful::dropdown_widget::{build_dropdown, DropdownOption, DropdownSetting};
 use crate::widget::stateful::number_widget::build_number_input_with_value;
 use crate::widget::stateful::text_widget::build_text_input;
 use crate::widget::{Focusable, StatefulWidgetState};
@@ -54,16 +53,26 @@ pub fn build_settings_widgets(settings : &Settings) -> Vec<StatefulWidgetState>
     }
 
     for setting in &settings.dropdown_settings {
-        let mut options : Vec<String> = Vec::new();
-
-        let chosen_option_name = String::from(setting.value.chosen_option.display_name);
-        options.push(chosen_option_name.clone());
-        let other_options : Vec<DropdownOption<Resolution>> = setting.value.options.iter().filter(|o| String::from(o.display_name) != chosen_option_name ).map(|o| o.clone()).collect();
-        for option in &other_options {
-            options.push(String::from(option.display_name))
-        }
-        let dropdown = build_dropdown(setting.name.clone(), true, options);
-        widgets.push(dropdown)
+        widgets.push(build_dropdown_for_setting(setting))
+    }
+    for setting in &settings.theme_settings {
+        widgets.push(build_dropdown_for_setting(setting))
+    }
+    for setting in &settings.glyph_settings {
+        widgets.push(build_dropdown_for_setting(setting))
     }
     widgets
+}
+
+// Lists the chosen option first, followed by the rest
+fn build_dropdown_for_setting<T: Clone>(setting: &Setting<DropdownSetting<DropdownOption<T>>>) -> StatefulWidgetState {
+    let mut options : Vec<String> = Vec::new();
+
+    let chosen_option_name = String::from(setting.value.chosen_option.display_name);
+    options.push(chosen_option_name.clone());
+    let other_options : Vec<DropdownOption<T>> = setting.value.options.iter().filter(|o| String::from(o.display_name) != chosen_option_name ).map(|o| o.clone()).collect();
+    for option in &other_options {
+        options.push(String::from(option.display_name))
+    }
+    build_dropdown(setting.name.clone(), true, options)
 }
\ No newline at end of file